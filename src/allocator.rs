@@ -0,0 +1,338 @@
+//! # Counting Allocator
+//!
+//! The lab keeps saying "clone allocates, move doesn't". This module lets it
+//! prove it: [`CountingAllocator`] wraps the system allocator and counts every
+//! allocation, deallocation and reallocation made by the current thread.
+//!
+//! It is opt-in. A binary installs it as its global allocator:
+//!
+//! ```ignore
+//! use introspective_strings::allocator::CountingAllocator;
+//! use std::alloc::System;
+//!
+//! #[global_allocator]
+//! static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+//! ```
+//!
+//! and then attributes allocations to an operation with [`track`]:
+//!
+//! ```ignore
+//! let (cloned, stats) = track(|| original.clone());
+//! assert_eq!(stats.allocs, 1);
+//! ```
+
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Allocation activity counted over some span of execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Number of fresh allocations
+    pub allocs: u64,
+    /// Number of deallocations
+    pub deallocs: u64,
+    /// Number of reallocations
+    pub reallocs: u64,
+    /// Reallocations that had to move the data to a new address
+    pub reallocs_moved: u64,
+    /// Reallocations the allocator satisfied in place
+    pub reallocs_in_place: u64,
+    /// Bytes requested by allocations and reallocations
+    pub bytes_allocated: u64,
+    /// Bytes released by deallocations and reallocations
+    pub bytes_deallocated: u64,
+    /// Bytes copied by reallocations that moved
+    pub bytes_copied: u64,
+}
+
+impl AllocStats {
+    const ZERO: AllocStats = AllocStats {
+        allocs: 0,
+        deallocs: 0,
+        reallocs: 0,
+        reallocs_moved: 0,
+        reallocs_in_place: 0,
+        bytes_allocated: 0,
+        bytes_deallocated: 0,
+        bytes_copied: 0,
+    };
+
+    /// The activity between an `earlier` snapshot and this one
+    pub fn since(&self, earlier: &AllocStats) -> AllocStats {
+        AllocStats {
+            allocs: self.allocs.wrapping_sub(earlier.allocs),
+            deallocs: self.deallocs.wrapping_sub(earlier.deallocs),
+            reallocs: self.reallocs.wrapping_sub(earlier.reallocs),
+            reallocs_moved: self.reallocs_moved.wrapping_sub(earlier.reallocs_moved),
+            reallocs_in_place: self
+                .reallocs_in_place
+                .wrapping_sub(earlier.reallocs_in_place),
+            bytes_allocated: self.bytes_allocated.wrapping_sub(earlier.bytes_allocated),
+            bytes_deallocated: self
+                .bytes_deallocated
+                .wrapping_sub(earlier.bytes_deallocated),
+            bytes_copied: self.bytes_copied.wrapping_sub(earlier.bytes_copied),
+        }
+    }
+
+    /// Whether no allocator activity happened at all
+    pub fn is_zero(&self) -> bool {
+        *self == AllocStats::ZERO
+    }
+
+    /// Net change in live heap bytes
+    pub fn net_bytes(&self) -> i64 {
+        self.bytes_allocated as i64 - self.bytes_deallocated as i64
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "no allocations");
+        }
+
+        write!(f, "{} alloc, {} dealloc", self.allocs, self.deallocs)?;
+        if self.reallocs > 0 {
+            write!(
+                f,
+                ", {} realloc ({} moved, {} in place, {} B copied)",
+                self.reallocs, self.reallocs_moved, self.reallocs_in_place, self.bytes_copied
+            )?;
+        }
+        write!(
+            f,
+            " | {} B requested, {} B released",
+            self.bytes_allocated, self.bytes_deallocated
+        )
+    }
+}
+
+thread_local! {
+    // Const-initialized `Cell` of a `Copy` type: accessing it never
+    // allocates, which is essential inside an allocator
+    static COUNTERS: Cell<AllocStats> = const { Cell::new(AllocStats::ZERO) };
+}
+
+/// Set the first time the counting allocator serves a request
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Set by [`CountingAllocator::assume_global`]: only then are heap blocks
+/// the system allocator's to measure
+static SYSTEM_BACKED: AtomicBool = AtomicBool::new(false);
+
+fn record(update: impl FnOnce(&mut AllocStats)) {
+    // `try_with` fails only while the thread is being torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut stats = counters.get();
+        update(&mut stats);
+        counters.set(stats);
+    });
+}
+
+/// A global allocator wrapper that counts allocator activity per thread
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl<A> CountingAllocator<A> {
+    /// Wraps `inner`, which does the actual allocating
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+impl CountingAllocator<System> {
+    /// Declares this allocator the global one, so [`usable_size`] can ask
+    /// malloc how big heap blocks are
+    ///
+    /// # Safety
+    ///
+    /// `self` must be the process's `#[global_allocator]`. Otherwise heap
+    /// blocks may come from another allocator, and measuring them with
+    /// `malloc_usable_size` is undefined behavior.
+    pub unsafe fn assume_global(&self) {
+        SYSTEM_BACKED.store(true, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            record(|s| {
+                s.allocs += 1;
+                s.bytes_allocated += layout.size() as u64;
+            });
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(|s| {
+                s.allocs += 1;
+                s.bytes_allocated += layout.size() as u64;
+            });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        record(|s| {
+            s.deallocs += 1;
+            s.bytes_deallocated += layout.size() as u64;
+        });
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(|s| {
+                s.reallocs += 1;
+                s.bytes_allocated += new_size as u64;
+                s.bytes_deallocated += layout.size() as u64;
+                if new_ptr == ptr {
+                    s.reallocs_in_place += 1;
+                } else {
+                    s.reallocs_moved += 1;
+                    s.bytes_copied += layout.size().min(new_size) as u64;
+                }
+            });
+        }
+        new_ptr
+    }
+}
+
+/// Whether a [`CountingAllocator`] is installed as the global allocator
+///
+/// Without it every count stays at zero, so callers should report
+/// allocations as unavailable rather than as "none".
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Total allocator activity of the current thread so far
+pub fn thread_stats() -> AllocStats {
+    COUNTERS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Size of the block the allocator actually reserved for `ptr`
+///
+/// Allocators round requests up to a size class, so this is usually more
+/// than the size that was asked for. Only available on Linux, through
+/// `malloc_usable_size`, and only once the global allocator has been
+/// declared a [`CountingAllocator`] wrapping [`System`] with
+/// [`assume_global`](CountingAllocator::assume_global): any other allocator
+/// (jemalloc, mimalloc, ...) hands out blocks that are not malloc's to
+/// measure, so the size is `None` unless the allocator is known.
+///
+/// # Safety
+///
+/// `ptr` must point to the start of a live allocation made by the global
+/// allocator.
+#[cfg(target_os = "linux")]
+pub unsafe fn usable_size(ptr: *const u8) -> Option<usize> {
+    SYSTEM_BACKED
+        .load(Ordering::Relaxed)
+        .then(|| libc::malloc_usable_size(ptr as *mut libc::c_void))
+}
+
+/// The allocator cannot be asked outside Linux
+///
+/// # Safety
+///
+/// Always safe; `unsafe` only to match the Linux signature.
+#[cfg(not(target_os = "linux"))]
+pub unsafe fn usable_size(_ptr: *const u8) -> Option<usize> {
+    None
+}
+
+/// Requested sizes the allocator rounds up to the same block size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SizeClass {
+    /// Smallest request measured in this class
+    pub smallest: usize,
+    /// Largest request measured in this class
+    pub largest: usize,
+    /// Usable size of the block every request in the class gets
+    pub block_size: usize,
+}
+
+impl SizeClass {
+    /// Bytes the allocator adds to the smallest request of the class
+    pub fn max_rounding(&self) -> usize {
+        self.block_size - self.smallest
+    }
+}
+
+/// Allocates buffers of every size in `requests` and groups consecutive
+/// requests that get the same block size
+///
+/// A request can be served from a larger block that happens to be free, so
+/// each size is allocated several times at once and the smallest block is
+/// its size class. Empty when block sizes cannot be reported (see
+/// [`usable_size`]).
+pub fn size_classes(requests: impl IntoIterator<Item = usize>) -> Vec<SizeClass> {
+    const PROBES: usize = 4;
+
+    let mut classes: Vec<SizeClass> = Vec::new();
+    for request in requests.into_iter().filter(|&n| n > 0) {
+        let buffers: [Vec<u8>; PROBES] = std::array::from_fn(|_| Vec::with_capacity(request));
+        // SAFETY: a non-empty `Vec` owns a live allocation from the global
+        // allocator, starting at its pointer
+        let Some(block_size) = buffers
+            .iter()
+            .map(|buffer| unsafe { usable_size(buffer.as_ptr()) })
+            .min()
+            .flatten()
+        else {
+            return Vec::new();
+        };
+        match classes.last_mut() {
+            Some(class) if class.block_size == block_size => class.largest = request,
+            _ => classes.push(SizeClass {
+                smallest: request,
+                largest: request,
+                block_size,
+            }),
+        }
+    }
+    classes
+}
+
+/// Runs `f` and returns its result with the allocations it made
+///
+/// Only the current thread is counted, so allocations made concurrently by
+/// other threads are not attributed to `f`.
+pub fn track<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let before = thread_stats();
+    let result = f();
+    let stats = thread_stats().since(&before);
+    (result, stats)
+}
+
+/// Like [`track`], but `None` when no counting allocator is installed
+pub fn track_if_installed<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    let (result, stats) = track(f);
+    (result, is_installed().then_some(stats))
+}
+
+/// Runs `f` without counting the allocations it makes
+///
+/// For bookkeeping that runs inside an operation being [`track`]ed but is
+/// not part of it, such as recording a trace event.
+pub fn untracked<R>(f: impl FnOnce() -> R) -> R {
+    let before = thread_stats();
+    let result = f();
+    let _ = COUNTERS.try_with(|counters| counters.set(before));
+    result
+}
@@ -0,0 +1,266 @@
+//! # Concurrent Batches
+//!
+//! Processes many inputs on spawned tasks with the controls real services
+//! need:
+//!
+//! - a **concurrency limit** - a semaphore permit is taken before the work
+//!   starts, so at most [`BatchConfig::max_concurrent`] inputs are processed
+//!   at once while the rest wait in line;
+//! - a **timeout** per task, counted from when it gets its permit;
+//! - **cancellation** through a `CancellationToken` - tasks still waiting
+//!   never start, and running ones stop at their next `.await`.
+//!
+//! Every task reports its own [`Outcome`]. A task that panics or is aborted
+//! surfaces as a `JoinError`, whose kind is kept alongside.
+//!
+//! ```ignore
+//! let batch = Batch::spawn(inputs, &BatchConfig::default(), &token, |input| async move {
+//!     async_process_string(input, Workload::CpuTransform).await
+//! });
+//! for result in batch.join().await {
+//!     println!("#{}: {}", result.index, result.outcome);
+//! }
+//! ```
+
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::{self, JoinError, JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn, Instrument};
+
+/// Limits applied to a batch
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    /// Most inputs processed at once
+    pub max_concurrent: usize,
+    /// Longest one input may take once it has started
+    pub timeout: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// How a task ended
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "outcome", content = "detail", rename_all = "snake_case")]
+pub enum Outcome<T> {
+    /// Finished with a value
+    Ok(T),
+    /// Finished with an error
+    Failed(String),
+    /// Ran out of time and was stopped
+    TimedOut,
+    /// Stopped by the cancellation token, or aborted
+    Cancelled,
+    /// Panicked, with the panic message when it was a string
+    Panicked(String),
+}
+
+impl<T> Outcome<T> {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Outcome::Ok(_))
+    }
+
+    /// The value, if the task finished with one
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Outcome::Ok(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> fmt::Display for Outcome<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Ok(_) => write!(f, "ok"),
+            Outcome::Failed(error) => write!(f, "failed: {}", error),
+            Outcome::TimedOut => write!(f, "timed out"),
+            Outcome::Cancelled => write!(f, "cancelled"),
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// Kind of a `JoinError`: why a task could not be joined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinErrorKind {
+    /// The task was aborted
+    Cancelled,
+    /// The task panicked
+    Panic,
+}
+
+/// What became of one input
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskResult<T> {
+    /// Position of the input in the batch
+    pub index: usize,
+    #[serde(flatten)]
+    pub outcome: Outcome<T>,
+    /// Set when the task ended in a `JoinError` rather than returning
+    pub join_error: Option<JoinErrorKind>,
+    /// Time spent waiting for a permit; `None` if the task did not return
+    pub queued_nanos: Option<u128>,
+    /// Time spent processing; `None` if it never started or did not return
+    pub run_nanos: Option<u128>,
+}
+
+/// What a task returns when it ends normally
+struct Finished<T> {
+    outcome: Outcome<T>,
+    queued_nanos: u128,
+    run_nanos: Option<u128>,
+}
+
+/// Spawned tasks processing a batch of inputs
+pub struct Batch<T> {
+    handles: Vec<JoinHandle<Finished<T>>>,
+}
+
+impl<T: Send + 'static> Batch<T> {
+    /// Spawns a task applying `op` to every input, within `config`'s limits
+    ///
+    /// Returns as soon as the tasks are spawned; [`join`](Batch::join)
+    /// collects their results.
+    pub fn spawn<I, S, F, Fut, E>(
+        inputs: I,
+        config: &BatchConfig,
+        cancel: &CancellationToken,
+        op: F,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Send + 'static,
+        F: Fn(S) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        E: fmt::Display,
+    {
+        let semaphore = Arc::new(Semaphore::new(config.max_concurrent.max(1)));
+        let op = Arc::new(op);
+        let timeout = config.timeout;
+
+        let handles = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let semaphore = Arc::clone(&semaphore);
+                let op = Arc::clone(&op);
+                let cancel = cancel.clone();
+                let work = async move {
+                    let spawned = Instant::now();
+                    let permit = tokio::select! {
+                        biased;
+                        _ = cancel.cancelled() => {
+                            debug!(index, "Cancelled before starting");
+                            return Finished {
+                                outcome: Outcome::Cancelled,
+                                queued_nanos: spawned.elapsed().as_nanos(),
+                                run_nanos: None,
+                            };
+                        }
+                        permit = semaphore.acquire_owned() => {
+                            permit.expect("the batch never closes its semaphore")
+                        }
+                    };
+                    let queued_nanos = spawned.elapsed().as_nanos();
+
+                    let started = Instant::now();
+                    let outcome = tokio::select! {
+                        biased;
+                        _ = cancel.cancelled() => Outcome::Cancelled,
+                        result = tokio::time::timeout(timeout, op(input)) => match result {
+                            Ok(Ok(value)) => Outcome::Ok(value),
+                            Ok(Err(error)) => Outcome::Failed(error.to_string()),
+                            Err(_) => Outcome::TimedOut,
+                        },
+                    };
+                    drop(permit);
+
+                    Finished {
+                        outcome,
+                        queued_nanos,
+                        run_nanos: Some(started.elapsed().as_nanos()),
+                    }
+                };
+                // Tasks stay in the spawner's span, so their spans nest below it
+                task::spawn(work.in_current_span())
+            })
+            .collect();
+
+        Self { handles }
+    }
+
+    /// Number of tasks in the batch
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Aborts every task that has not finished
+    pub fn abort(&self) {
+        for handle in &self.handles {
+            handle.abort();
+        }
+    }
+
+    /// Waits for every task and reports each one's outcome, in input order
+    pub async fn join(self) -> Vec<TaskResult<T>> {
+        let mut results = Vec::with_capacity(self.handles.len());
+        for (index, handle) in self.handles.into_iter().enumerate() {
+            let result = match handle.await {
+                Ok(finished) => TaskResult {
+                    index,
+                    outcome: finished.outcome,
+                    join_error: None,
+                    queued_nanos: Some(finished.queued_nanos),
+                    run_nanos: finished.run_nanos,
+                },
+                Err(error) => {
+                    let (outcome, kind) = join_error(error);
+                    TaskResult {
+                        index,
+                        outcome,
+                        join_error: Some(kind),
+                        queued_nanos: None,
+                        run_nanos: None,
+                    }
+                }
+            };
+            if !result.outcome.is_ok() {
+                warn!(index, join_error = ?result.join_error, "Task {}", result.outcome);
+            }
+            results.push(result);
+        }
+        results
+    }
+}
+
+/// The outcome a `JoinError` stands for, and its kind
+fn join_error<T>(error: JoinError) -> (Outcome<T>, JoinErrorKind) {
+    if error.is_cancelled() {
+        return (Outcome::Cancelled, JoinErrorKind::Cancelled);
+    }
+
+    let payload = error.into_panic();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string());
+    (Outcome::Panicked(message), JoinErrorKind::Panic)
+}
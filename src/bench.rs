@@ -0,0 +1,165 @@
+//! # Statistical Benchmarking
+//!
+//! A single `Instant::now()` sample of an 80 ns operation is mostly noise:
+//! timer overhead, cache misses and scheduler interrupts dominate. This
+//! module measures an operation properly:
+//!
+//! 1. **Warmup** - run it a number of times so caches and branch predictors
+//!    settle before anything is recorded
+//! 2. **Many iterations** - one sample per run, read from the low-overhead
+//!    [`quanta`] TSC clock
+//! 3. **Black boxing** - inputs and outputs pass through
+//!    [`std::hint::black_box`] so the optimizer cannot hoist or delete the work
+//! 4. **Outlier rejection** - samples outside Tukey's fences (1.5 × IQR
+//!    beyond the quartiles) are discarded before computing statistics
+
+use crate::theme;
+use quanta::Clock;
+use serde::Serialize;
+use std::fmt;
+use std::hint::black_box;
+use std::sync::OnceLock;
+
+/// How a benchmark is run
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Runs discarded before sampling starts
+    pub warmup_iterations: usize,
+    /// Number of samples recorded
+    pub iterations: usize,
+    /// Whether to discard outliers before computing statistics
+    pub reject_outliers: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 100,
+            iterations: 1_000,
+            reject_outliers: true,
+        }
+    }
+}
+
+/// Summary statistics of a set of timing samples, in nanoseconds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Distribution {
+    /// Samples the statistics were computed from
+    pub samples: usize,
+    /// Samples discarded as outliers
+    pub outliers: usize,
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub p99_nanos: f64,
+    pub max_nanos: f64,
+    pub mean_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+impl Distribution {
+    /// Computes the distribution of `samples`, optionally rejecting outliers
+    pub fn from_samples(mut samples: Vec<u64>, reject_outliers: bool) -> Self {
+        samples.sort_unstable();
+        let total = samples.len();
+
+        if reject_outliers && total >= 4 {
+            let q1 = percentile(&samples, 0.25);
+            let q3 = percentile(&samples, 0.75);
+            let fence = 1.5 * (q3 - q1);
+            let (low, high) = (q1 - fence, q3 + fence);
+            samples.retain(|&s| (low..=high).contains(&(s as f64)));
+        }
+
+        let n = samples.len().max(1) as f64;
+        let mean = samples.iter().map(|&s| s as f64).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        Self {
+            samples: samples.len(),
+            outliers: total - samples.len(),
+            min_nanos: samples.first().copied().unwrap_or_default() as f64,
+            median_nanos: percentile(&samples, 0.5),
+            p99_nanos: percentile(&samples, 0.99),
+            max_nanos: samples.last().copied().unwrap_or_default() as f64,
+            mean_nanos: mean,
+            stddev_nanos: variance.sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let glyphs = theme::glyphs();
+        let dot = glyphs.separator;
+        write!(
+            f,
+            "min {} {dot} median {} {dot} p99 {} {dot} {} {} (n={}, {} outliers rejected)",
+            format_nanos(self.min_nanos),
+            format_nanos(self.median_nanos),
+            format_nanos(self.p99_nanos),
+            glyphs.sigma,
+            format_nanos(self.stddev_nanos),
+            self.samples,
+            self.outliers
+        )
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[u64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1] as f64
+}
+
+/// Formats a duration in nanoseconds with a human-friendly unit
+pub fn format_nanos(nanos: f64) -> String {
+    if nanos < 1_000.0 {
+        format!("{:.0} ns", nanos)
+    } else if nanos < 1_000_000.0 {
+        format!("{:.2} {}s", nanos / 1_000.0, theme::glyphs().micro)
+    } else {
+        format!("{:.2} ms", nanos / 1_000_000.0)
+    }
+}
+
+/// The shared calibrated clock (calibration takes a few milliseconds)
+fn clock() -> &'static Clock {
+    static CLOCK: OnceLock<Clock> = OnceLock::new();
+    CLOCK.get_or_init(Clock::new)
+}
+
+/// Benchmarks `f`, returning the output of its last run and the timing
+/// distribution
+///
+/// `input` is passed through [`black_box`] before every call and each output
+/// is black-boxed too, so the work cannot be optimized away.
+pub fn run<I, T>(config: &BenchConfig, input: I, mut f: impl FnMut(&I) -> T) -> (T, Distribution) {
+    let input = &input;
+
+    for _ in 0..config.warmup_iterations {
+        black_box(f(black_box(input)));
+    }
+
+    let clock = clock();
+    let iterations = config.iterations.max(1);
+    let mut samples = Vec::with_capacity(iterations);
+    let mut last = None;
+
+    for _ in 0..iterations {
+        let start = clock.raw();
+        let output = black_box(f(black_box(input)));
+        let end = clock.raw();
+        samples.push(clock.delta_as_nanos(start, end));
+        last = Some(output);
+    }
+
+    let distribution = Distribution::from_samples(samples, config.reject_outliers);
+    (last.expect("at least one iteration"), distribution)
+}
@@ -0,0 +1,331 @@
+//! # Full Case Mapping
+//!
+//! `str::to_uppercase` and `str::to_lowercase` implement the default full
+//! case mappings, which is already more than byte-wise ASCII casing ("ß"
+//! uppercases to "SS"). Real text needs a few more operations:
+//!
+//! - **Case folding** - a mapping meant for caseless comparison rather than
+//!   display: "Straße" and "STRASSE" fold to the same string
+//! - **Titlecasing** - the first cased letter of every word (UAX #29 word
+//!   boundaries) is titlecased, the rest lowercased. Titlecase is not
+//!   uppercase: "ǆ" becomes "ǅ" and "ﬁ" becomes "Fi"
+//! - **Context** - "Σ" lowercases to "ς" at the end of a word and to "σ"
+//!   elsewhere
+//! - **Language tailoring** - Turkish and Azeri pair dotted "İ/i" and dotless
+//!   "I/ı"; Lithuanian keeps the dot of "i" when it carries an accent
+//!
+//! The conditions follow the Unicode Standard, section 3.13, and
+//! `SpecialCasing.txt`.
+
+mod tables;
+
+use crate::grapheme::range_cmp;
+use crate::normalize::{self, NormalizationForm};
+use crate::word;
+use std::fmt;
+use unicode_normalization::char::canonical_combining_class;
+
+const COMBINING_DOT_ABOVE: char = '\u{307}';
+/// Canonical combining class of marks drawn above the base letter
+const CCC_ABOVE: u8 = 230;
+
+/// A case operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseMapping {
+    Lower,
+    Upper,
+    Title,
+    /// Full case folding, for caseless comparison
+    Fold,
+}
+
+impl CaseMapping {
+    /// Name used when reporting timings
+    pub fn name(&self) -> &'static str {
+        match self {
+            CaseMapping::Lower => "to_lowercase",
+            CaseMapping::Upper => "to_uppercase",
+            CaseMapping::Title => "to_titlecase",
+            CaseMapping::Fold => "case_fold",
+        }
+    }
+}
+
+impl fmt::Display for CaseMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The language whose casing rules apply
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CaseLocale {
+    /// The default, language-independent mappings
+    #[default]
+    Root,
+    /// Turkish and Azeri: dotted and dotless i are separate letters
+    Turkic,
+    /// Lithuanian: an accented i keeps its dot
+    Lithuanian,
+}
+
+impl CaseLocale {
+    /// Picks the tailoring for a BCP 47 language tag such as `tr-TR`
+    pub fn from_language(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "tr" | "az" => CaseLocale::Turkic,
+            "lt" => CaseLocale::Lithuanian,
+            _ => CaseLocale::Root,
+        }
+    }
+
+    /// Short name, e.g. `tr/az`
+    pub fn name(&self) -> &'static str {
+        match self {
+            CaseLocale::Root => "root",
+            CaseLocale::Turkic => "tr/az",
+            CaseLocale::Lithuanian => "lt",
+        }
+    }
+}
+
+impl fmt::Display for CaseLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| range_cmp(c, lo, hi))
+        .is_ok()
+}
+
+fn lookup(c: char, table: &'static [(char, &'static str)]) -> Option<&'static str> {
+    table
+        .binary_search_by_key(&c, |&(k, _)| k)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Whether `c` has case: a lowercase, uppercase or titlecase letter
+pub fn is_cased(c: char) -> bool {
+    c.is_lowercase() || c.is_uppercase() || in_ranges(c, tables::TITLECASE_LETTER)
+}
+
+/// Whether `c` is skipped when looking for the cased letters around a
+/// position, such as accents and apostrophes
+pub fn is_case_ignorable(c: char) -> bool {
+    in_ranges(c, tables::CASE_IGNORABLE)
+}
+
+fn is_soft_dotted(c: char) -> bool {
+    in_ranges(c, tables::SOFT_DOTTED)
+}
+
+/// The casing contexts of section 3.13, evaluated at byte offset `i` of `s`
+struct Context<'a> {
+    before: &'a str,
+    after: &'a str,
+}
+
+impl<'a> Context<'a> {
+    fn at(s: &'a str, i: usize, c: char) -> Self {
+        Self {
+            before: &s[..i],
+            after: &s[i + c.len_utf8()..],
+        }
+    }
+
+    /// Final_Sigma: preceded by a cased letter and not followed by one,
+    /// skipping case-ignorable characters in both directions
+    fn is_final_sigma(&self) -> bool {
+        let mut before = self
+            .before
+            .chars()
+            .rev()
+            .skip_while(|&c| is_case_ignorable(c));
+        let mut after = self.after.chars().skip_while(|&c| is_case_ignorable(c));
+        before.next().is_some_and(is_cased) && !after.next().is_some_and(is_cased)
+    }
+
+    /// After_Soft_Dotted: a soft-dotted letter precedes, with no base
+    /// character or other mark above in between
+    fn is_after_soft_dotted(&self) -> bool {
+        self.before_above_marks(is_soft_dotted)
+    }
+
+    /// After_I: an uppercase I precedes, with no base character or other
+    /// mark above in between
+    fn is_after_i(&self) -> bool {
+        self.before_above_marks(|c| c == 'I')
+    }
+
+    /// More_Above: a mark above follows, with no base character in between
+    fn has_more_above(&self) -> bool {
+        for c in self.after.chars() {
+            match canonical_combining_class(c) {
+                CCC_ABOVE => return true,
+                0 => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Before_Dot: U+0307 follows, with no base character or other mark
+    /// above in between
+    fn is_before_dot(&self) -> bool {
+        for c in self.after.chars() {
+            if c == COMBINING_DOT_ABOVE {
+                return true;
+            }
+            if matches!(canonical_combining_class(c), 0 | CCC_ABOVE) {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn before_above_marks(&self, base: impl Fn(char) -> bool) -> bool {
+        for c in self.before.chars().rev() {
+            if base(c) {
+                return true;
+            }
+            if matches!(canonical_combining_class(c), 0 | CCC_ABOVE) {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+fn push_lower(out: &mut String, c: char, context: &Context<'_>, locale: CaseLocale) {
+    match (locale, c) {
+        (CaseLocale::Turkic, 'İ') => out.push('i'),
+        (CaseLocale::Turkic, COMBINING_DOT_ABOVE) if context.is_after_i() => {}
+        (CaseLocale::Turkic, 'I') if !context.is_before_dot() => out.push('ı'),
+        (CaseLocale::Lithuanian, 'I' | 'J' | 'Į') if context.has_more_above() => {
+            out.extend(c.to_lowercase());
+            out.push(COMBINING_DOT_ABOVE);
+        }
+        (CaseLocale::Lithuanian, 'Ì') => out.push_str("i\u{307}\u{300}"),
+        (CaseLocale::Lithuanian, 'Í') => out.push_str("i\u{307}\u{301}"),
+        (CaseLocale::Lithuanian, 'Ĩ') => out.push_str("i\u{307}\u{303}"),
+        (_, 'Σ') if context.is_final_sigma() => out.push('ς'),
+        _ => out.extend(c.to_lowercase()),
+    }
+}
+
+fn push_upper(out: &mut String, c: char, context: &Context<'_>, locale: CaseLocale, title: bool) {
+    match (locale, c) {
+        (CaseLocale::Turkic, 'i') => out.push('İ'),
+        (CaseLocale::Lithuanian, COMBINING_DOT_ABOVE) if context.is_after_soft_dotted() => {}
+        _ => match lookup(c, tables::TITLE).filter(|_| title) {
+            Some(title) => out.push_str(title),
+            None => out.extend(c.to_uppercase()),
+        },
+    }
+}
+
+fn push_fold(out: &mut String, c: char, locale: CaseLocale) {
+    match (locale, c) {
+        (CaseLocale::Turkic, 'I') => out.push('ı'),
+        (CaseLocale::Turkic, 'İ') => out.push('i'),
+        _ => match lookup(c, tables::FOLD) {
+            Some(folded) => out.push_str(folded),
+            None => out.extend(c.to_lowercase()),
+        },
+    }
+}
+
+/// Lowercases `s`, applying the final sigma rule and `locale`'s tailoring
+pub fn to_lowercase(s: &str, locale: CaseLocale) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        push_lower(&mut out, c, &Context::at(s, i, c), locale);
+    }
+    out
+}
+
+/// Uppercases `s`, applying `locale`'s tailoring
+pub fn to_uppercase(s: &str, locale: CaseLocale) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        push_upper(&mut out, c, &Context::at(s, i, c), locale, false);
+    }
+    out
+}
+
+/// Titlecases every word of `s`: the first cased letter of each word is
+/// titlecased and the rest of the word lowercased
+pub fn to_titlecase(s: &str, locale: CaseLocale) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut offset = 0;
+
+    for segment in word::word_bounds(s) {
+        let mut titled = None;
+        for (i, c) in segment.char_indices() {
+            let context = Context::at(s, offset + i, c);
+            match titled {
+                None if is_cased(c) => {
+                    push_upper(&mut out, c, &context, locale, true);
+                    titled = Some(c);
+                }
+                None => out.push(c),
+                // A dot above the titlecased letter stays with it: dropped
+                // after a soft-dotted letter in Lithuanian (as in uppercase)
+                // and kept on a Turkish capital I
+                Some(base)
+                    if c == COMBINING_DOT_ABOVE && context.before_above_marks(|b| b == base) =>
+                {
+                    match (locale, base) {
+                        (CaseLocale::Lithuanian, base) if is_soft_dotted(base) => {}
+                        (CaseLocale::Turkic, 'I') => out.push(c),
+                        _ => push_lower(&mut out, c, &context, locale),
+                    }
+                }
+                Some(_) => push_lower(&mut out, c, &context, locale),
+            }
+        }
+        offset += segment.len();
+    }
+
+    out
+}
+
+/// Applies full case folding to `s`
+///
+/// Folding erases case distinctions for comparison; unlike lowercasing it
+/// maps "ß" to "ss" and "ς" to "σ". The result is not meant for display.
+pub fn fold(s: &str, locale: CaseLocale) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        push_fold(&mut out, c, locale);
+    }
+    out
+}
+
+/// Applies `mapping` to `s`
+pub fn map(s: &str, mapping: CaseMapping, locale: CaseLocale) -> String {
+    match mapping {
+        CaseMapping::Lower => to_lowercase(s, locale),
+        CaseMapping::Upper => to_uppercase(s, locale),
+        CaseMapping::Title => to_titlecase(s, locale),
+        CaseMapping::Fold => fold(s, locale),
+    }
+}
+
+/// Canonical caseless match: whether `a` and `b` are equal ignoring case
+/// and canonical equivalence (`NFD(fold(NFD(x)))` on both sides)
+pub fn eq_caseless(a: &str, b: &str) -> bool {
+    let key = |s: &str| {
+        let folded = fold(
+            &normalize::normalize(s, NormalizationForm::Nfd),
+            CaseLocale::Root,
+        );
+        normalize::normalize(&folded, NormalizationForm::Nfd).into_owned()
+    };
+    a == b || key(a) == key(b)
+}
@@ -0,0 +1,349 @@
+//! Unicode 17.0.0 property tables for full case mapping
+//!
+//! Generated by `tools/unicode_tables.py` from the Unicode Character Database:
+//! `UnicodeData.txt`, `SpecialCasing.txt`, `CaseFolding.txt`, `PropList.txt`
+//! and `WordBreakProperty.txt`. Do not edit by hand.
+//!
+//! The mapping tables only list code points whose mapping differs from the
+//! one the standard library already implements, so most lookups fall
+//! through to [`char::to_lowercase`] or [`char::to_uppercase`].
+
+/// `Case_Ignorable`: general category Mn, Me, Cf, Lm or Sk, or word break
+/// category MidLetter, MidNumLet or Single_Quote
+#[rustfmt::skip]
+pub(super) const CASE_IGNORABLE: &[(char, char)] = &[
+    ('\u{27}', '\u{27}'), ('\u{2e}', '\u{2e}'), ('\u{3a}', '\u{3a}'), ('\u{5e}', '\u{5e}'),
+    ('\u{60}', '\u{60}'), ('\u{a8}', '\u{a8}'), ('\u{ad}', '\u{ad}'), ('\u{af}', '\u{af}'),
+    ('\u{b4}', '\u{b4}'), ('\u{b7}', '\u{b8}'), ('\u{2b0}', '\u{36f}'), ('\u{374}', '\u{375}'),
+    ('\u{37a}', '\u{37a}'), ('\u{384}', '\u{385}'), ('\u{387}', '\u{387}'), ('\u{483}', '\u{489}'),
+    ('\u{559}', '\u{559}'), ('\u{55f}', '\u{55f}'), ('\u{591}', '\u{5bd}'), ('\u{5bf}', '\u{5bf}'),
+    ('\u{5c1}', '\u{5c2}'), ('\u{5c4}', '\u{5c5}'), ('\u{5c7}', '\u{5c7}'), ('\u{5f4}', '\u{5f4}'),
+    ('\u{600}', '\u{605}'), ('\u{610}', '\u{61a}'), ('\u{61c}', '\u{61c}'), ('\u{640}', '\u{640}'),
+    ('\u{64b}', '\u{65f}'), ('\u{670}', '\u{670}'), ('\u{6d6}', '\u{6dd}'), ('\u{6df}', '\u{6e8}'),
+    ('\u{6ea}', '\u{6ed}'), ('\u{70f}', '\u{70f}'), ('\u{711}', '\u{711}'), ('\u{730}', '\u{74a}'),
+    ('\u{7a6}', '\u{7b0}'), ('\u{7eb}', '\u{7f5}'), ('\u{7fa}', '\u{7fa}'), ('\u{7fd}', '\u{7fd}'),
+    ('\u{816}', '\u{82d}'), ('\u{859}', '\u{85b}'), ('\u{888}', '\u{888}'), ('\u{890}', '\u{891}'),
+    ('\u{897}', '\u{89f}'), ('\u{8c9}', '\u{902}'), ('\u{93a}', '\u{93a}'), ('\u{93c}', '\u{93c}'),
+    ('\u{941}', '\u{948}'), ('\u{94d}', '\u{94d}'), ('\u{951}', '\u{957}'), ('\u{962}', '\u{963}'),
+    ('\u{971}', '\u{971}'), ('\u{981}', '\u{981}'), ('\u{9bc}', '\u{9bc}'), ('\u{9c1}', '\u{9c4}'),
+    ('\u{9cd}', '\u{9cd}'), ('\u{9e2}', '\u{9e3}'), ('\u{9fe}', '\u{9fe}'), ('\u{a01}', '\u{a02}'),
+    ('\u{a3c}', '\u{a3c}'), ('\u{a41}', '\u{a42}'), ('\u{a47}', '\u{a48}'), ('\u{a4b}', '\u{a4d}'),
+    ('\u{a51}', '\u{a51}'), ('\u{a70}', '\u{a71}'), ('\u{a75}', '\u{a75}'), ('\u{a81}', '\u{a82}'),
+    ('\u{abc}', '\u{abc}'), ('\u{ac1}', '\u{ac5}'), ('\u{ac7}', '\u{ac8}'), ('\u{acd}', '\u{acd}'),
+    ('\u{ae2}', '\u{ae3}'), ('\u{afa}', '\u{aff}'), ('\u{b01}', '\u{b01}'), ('\u{b3c}', '\u{b3c}'),
+    ('\u{b3f}', '\u{b3f}'), ('\u{b41}', '\u{b44}'), ('\u{b4d}', '\u{b4d}'), ('\u{b55}', '\u{b56}'),
+    ('\u{b62}', '\u{b63}'), ('\u{b82}', '\u{b82}'), ('\u{bc0}', '\u{bc0}'), ('\u{bcd}', '\u{bcd}'),
+    ('\u{c00}', '\u{c00}'), ('\u{c04}', '\u{c04}'), ('\u{c3c}', '\u{c3c}'), ('\u{c3e}', '\u{c40}'),
+    ('\u{c46}', '\u{c48}'), ('\u{c4a}', '\u{c4d}'), ('\u{c55}', '\u{c56}'), ('\u{c62}', '\u{c63}'),
+    ('\u{c81}', '\u{c81}'), ('\u{cbc}', '\u{cbc}'), ('\u{cbf}', '\u{cbf}'), ('\u{cc6}', '\u{cc6}'),
+    ('\u{ccc}', '\u{ccd}'), ('\u{ce2}', '\u{ce3}'), ('\u{d00}', '\u{d01}'), ('\u{d3b}', '\u{d3c}'),
+    ('\u{d41}', '\u{d44}'), ('\u{d4d}', '\u{d4d}'), ('\u{d62}', '\u{d63}'), ('\u{d81}', '\u{d81}'),
+    ('\u{dca}', '\u{dca}'), ('\u{dd2}', '\u{dd4}'), ('\u{dd6}', '\u{dd6}'), ('\u{e31}', '\u{e31}'),
+    ('\u{e34}', '\u{e3a}'), ('\u{e46}', '\u{e4e}'), ('\u{eb1}', '\u{eb1}'), ('\u{eb4}', '\u{ebc}'),
+    ('\u{ec6}', '\u{ec6}'), ('\u{ec8}', '\u{ece}'), ('\u{f18}', '\u{f19}'), ('\u{f35}', '\u{f35}'),
+    ('\u{f37}', '\u{f37}'), ('\u{f39}', '\u{f39}'), ('\u{f71}', '\u{f7e}'), ('\u{f80}', '\u{f84}'),
+    ('\u{f86}', '\u{f87}'), ('\u{f8d}', '\u{f97}'), ('\u{f99}', '\u{fbc}'), ('\u{fc6}', '\u{fc6}'),
+    ('\u{102d}', '\u{1030}'), ('\u{1032}', '\u{1037}'), ('\u{1039}', '\u{103a}'),
+    ('\u{103d}', '\u{103e}'), ('\u{1058}', '\u{1059}'), ('\u{105e}', '\u{1060}'),
+    ('\u{1071}', '\u{1074}'), ('\u{1082}', '\u{1082}'), ('\u{1085}', '\u{1086}'),
+    ('\u{108d}', '\u{108d}'), ('\u{109d}', '\u{109d}'), ('\u{10fc}', '\u{10fc}'),
+    ('\u{135d}', '\u{135f}'), ('\u{1712}', '\u{1714}'), ('\u{1732}', '\u{1733}'),
+    ('\u{1752}', '\u{1753}'), ('\u{1772}', '\u{1773}'), ('\u{17b4}', '\u{17b5}'),
+    ('\u{17b7}', '\u{17bd}'), ('\u{17c6}', '\u{17c6}'), ('\u{17c9}', '\u{17d3}'),
+    ('\u{17d7}', '\u{17d7}'), ('\u{17dd}', '\u{17dd}'), ('\u{180b}', '\u{180f}'),
+    ('\u{1843}', '\u{1843}'), ('\u{1885}', '\u{1886}'), ('\u{18a9}', '\u{18a9}'),
+    ('\u{1920}', '\u{1922}'), ('\u{1927}', '\u{1928}'), ('\u{1932}', '\u{1932}'),
+    ('\u{1939}', '\u{193b}'), ('\u{1a17}', '\u{1a18}'), ('\u{1a1b}', '\u{1a1b}'),
+    ('\u{1a56}', '\u{1a56}'), ('\u{1a58}', '\u{1a5e}'), ('\u{1a60}', '\u{1a60}'),
+    ('\u{1a62}', '\u{1a62}'), ('\u{1a65}', '\u{1a6c}'), ('\u{1a73}', '\u{1a7c}'),
+    ('\u{1a7f}', '\u{1a7f}'), ('\u{1aa7}', '\u{1aa7}'), ('\u{1ab0}', '\u{1add}'),
+    ('\u{1ae0}', '\u{1aeb}'), ('\u{1b00}', '\u{1b03}'), ('\u{1b34}', '\u{1b34}'),
+    ('\u{1b36}', '\u{1b3a}'), ('\u{1b3c}', '\u{1b3c}'), ('\u{1b42}', '\u{1b42}'),
+    ('\u{1b6b}', '\u{1b73}'), ('\u{1b80}', '\u{1b81}'), ('\u{1ba2}', '\u{1ba5}'),
+    ('\u{1ba8}', '\u{1ba9}'), ('\u{1bab}', '\u{1bad}'), ('\u{1be6}', '\u{1be6}'),
+    ('\u{1be8}', '\u{1be9}'), ('\u{1bed}', '\u{1bed}'), ('\u{1bef}', '\u{1bf1}'),
+    ('\u{1c2c}', '\u{1c33}'), ('\u{1c36}', '\u{1c37}'), ('\u{1c78}', '\u{1c7d}'),
+    ('\u{1cd0}', '\u{1cd2}'), ('\u{1cd4}', '\u{1ce0}'), ('\u{1ce2}', '\u{1ce8}'),
+    ('\u{1ced}', '\u{1ced}'), ('\u{1cf4}', '\u{1cf4}'), ('\u{1cf8}', '\u{1cf9}'),
+    ('\u{1d2c}', '\u{1d6a}'), ('\u{1d78}', '\u{1d78}'), ('\u{1d9b}', '\u{1dff}'),
+    ('\u{1fbd}', '\u{1fbd}'), ('\u{1fbf}', '\u{1fc1}'), ('\u{1fcd}', '\u{1fcf}'),
+    ('\u{1fdd}', '\u{1fdf}'), ('\u{1fed}', '\u{1fef}'), ('\u{1ffd}', '\u{1ffe}'),
+    ('\u{200b}', '\u{200f}'), ('\u{2018}', '\u{2019}'), ('\u{2024}', '\u{2024}'),
+    ('\u{2027}', '\u{2027}'), ('\u{202a}', '\u{202e}'), ('\u{2060}', '\u{2064}'),
+    ('\u{2066}', '\u{206f}'), ('\u{2071}', '\u{2071}'), ('\u{207f}', '\u{207f}'),
+    ('\u{2090}', '\u{209c}'), ('\u{20d0}', '\u{20f0}'), ('\u{2c7c}', '\u{2c7d}'),
+    ('\u{2cef}', '\u{2cf1}'), ('\u{2d6f}', '\u{2d6f}'), ('\u{2d7f}', '\u{2d7f}'),
+    ('\u{2de0}', '\u{2dff}'), ('\u{2e2f}', '\u{2e2f}'), ('\u{3005}', '\u{3005}'),
+    ('\u{302a}', '\u{302d}'), ('\u{3031}', '\u{3035}'), ('\u{303b}', '\u{303b}'),
+    ('\u{3099}', '\u{309e}'), ('\u{30fc}', '\u{30fe}'), ('\u{a015}', '\u{a015}'),
+    ('\u{a4f8}', '\u{a4fd}'), ('\u{a60c}', '\u{a60c}'), ('\u{a66f}', '\u{a672}'),
+    ('\u{a674}', '\u{a67d}'), ('\u{a67f}', '\u{a67f}'), ('\u{a69c}', '\u{a69f}'),
+    ('\u{a6f0}', '\u{a6f1}'), ('\u{a700}', '\u{a721}'), ('\u{a770}', '\u{a770}'),
+    ('\u{a788}', '\u{a78a}'), ('\u{a7f1}', '\u{a7f4}'), ('\u{a7f8}', '\u{a7f9}'),
+    ('\u{a802}', '\u{a802}'), ('\u{a806}', '\u{a806}'), ('\u{a80b}', '\u{a80b}'),
+    ('\u{a825}', '\u{a826}'), ('\u{a82c}', '\u{a82c}'), ('\u{a8c4}', '\u{a8c5}'),
+    ('\u{a8e0}', '\u{a8f1}'), ('\u{a8ff}', '\u{a8ff}'), ('\u{a926}', '\u{a92d}'),
+    ('\u{a947}', '\u{a951}'), ('\u{a980}', '\u{a982}'), ('\u{a9b3}', '\u{a9b3}'),
+    ('\u{a9b6}', '\u{a9b9}'), ('\u{a9bc}', '\u{a9bd}'), ('\u{a9cf}', '\u{a9cf}'),
+    ('\u{a9e5}', '\u{a9e6}'), ('\u{aa29}', '\u{aa2e}'), ('\u{aa31}', '\u{aa32}'),
+    ('\u{aa35}', '\u{aa36}'), ('\u{aa43}', '\u{aa43}'), ('\u{aa4c}', '\u{aa4c}'),
+    ('\u{aa70}', '\u{aa70}'), ('\u{aa7c}', '\u{aa7c}'), ('\u{aab0}', '\u{aab0}'),
+    ('\u{aab2}', '\u{aab4}'), ('\u{aab7}', '\u{aab8}'), ('\u{aabe}', '\u{aabf}'),
+    ('\u{aac1}', '\u{aac1}'), ('\u{aadd}', '\u{aadd}'), ('\u{aaec}', '\u{aaed}'),
+    ('\u{aaf3}', '\u{aaf4}'), ('\u{aaf6}', '\u{aaf6}'), ('\u{ab5b}', '\u{ab5f}'),
+    ('\u{ab69}', '\u{ab6b}'), ('\u{abe5}', '\u{abe5}'), ('\u{abe8}', '\u{abe8}'),
+    ('\u{abed}', '\u{abed}'), ('\u{fb1e}', '\u{fb1e}'), ('\u{fbb2}', '\u{fbc2}'),
+    ('\u{fe00}', '\u{fe0f}'), ('\u{fe13}', '\u{fe13}'), ('\u{fe20}', '\u{fe2f}'),
+    ('\u{fe52}', '\u{fe52}'), ('\u{fe55}', '\u{fe55}'), ('\u{feff}', '\u{feff}'),
+    ('\u{ff07}', '\u{ff07}'), ('\u{ff0e}', '\u{ff0e}'), ('\u{ff1a}', '\u{ff1a}'),
+    ('\u{ff3e}', '\u{ff3e}'), ('\u{ff40}', '\u{ff40}'), ('\u{ff70}', '\u{ff70}'),
+    ('\u{ff9e}', '\u{ff9f}'), ('\u{ffe3}', '\u{ffe3}'), ('\u{fff9}', '\u{fffb}'),
+    ('\u{101fd}', '\u{101fd}'), ('\u{102e0}', '\u{102e0}'), ('\u{10376}', '\u{1037a}'),
+    ('\u{10780}', '\u{10785}'), ('\u{10787}', '\u{107b0}'), ('\u{107b2}', '\u{107ba}'),
+    ('\u{10a01}', '\u{10a03}'), ('\u{10a05}', '\u{10a06}'), ('\u{10a0c}', '\u{10a0f}'),
+    ('\u{10a38}', '\u{10a3a}'), ('\u{10a3f}', '\u{10a3f}'), ('\u{10ae5}', '\u{10ae6}'),
+    ('\u{10d24}', '\u{10d27}'), ('\u{10d4e}', '\u{10d4e}'), ('\u{10d69}', '\u{10d6d}'),
+    ('\u{10d6f}', '\u{10d6f}'), ('\u{10eab}', '\u{10eac}'), ('\u{10ec5}', '\u{10ec5}'),
+    ('\u{10efa}', '\u{10eff}'), ('\u{10f46}', '\u{10f50}'), ('\u{10f82}', '\u{10f85}'),
+    ('\u{11001}', '\u{11001}'), ('\u{11038}', '\u{11046}'), ('\u{11070}', '\u{11070}'),
+    ('\u{11073}', '\u{11074}'), ('\u{1107f}', '\u{11081}'), ('\u{110b3}', '\u{110b6}'),
+    ('\u{110b9}', '\u{110ba}'), ('\u{110bd}', '\u{110bd}'), ('\u{110c2}', '\u{110c2}'),
+    ('\u{110cd}', '\u{110cd}'), ('\u{11100}', '\u{11102}'), ('\u{11127}', '\u{1112b}'),
+    ('\u{1112d}', '\u{11134}'), ('\u{11173}', '\u{11173}'), ('\u{11180}', '\u{11181}'),
+    ('\u{111b6}', '\u{111be}'), ('\u{111c9}', '\u{111cc}'), ('\u{111cf}', '\u{111cf}'),
+    ('\u{1122f}', '\u{11231}'), ('\u{11234}', '\u{11234}'), ('\u{11236}', '\u{11237}'),
+    ('\u{1123e}', '\u{1123e}'), ('\u{11241}', '\u{11241}'), ('\u{112df}', '\u{112df}'),
+    ('\u{112e3}', '\u{112ea}'), ('\u{11300}', '\u{11301}'), ('\u{1133b}', '\u{1133c}'),
+    ('\u{11340}', '\u{11340}'), ('\u{11366}', '\u{1136c}'), ('\u{11370}', '\u{11374}'),
+    ('\u{113bb}', '\u{113c0}'), ('\u{113ce}', '\u{113ce}'), ('\u{113d0}', '\u{113d0}'),
+    ('\u{113d2}', '\u{113d2}'), ('\u{113e1}', '\u{113e2}'), ('\u{11438}', '\u{1143f}'),
+    ('\u{11442}', '\u{11444}'), ('\u{11446}', '\u{11446}'), ('\u{1145e}', '\u{1145e}'),
+    ('\u{114b3}', '\u{114b8}'), ('\u{114ba}', '\u{114ba}'), ('\u{114bf}', '\u{114c0}'),
+    ('\u{114c2}', '\u{114c3}'), ('\u{115b2}', '\u{115b5}'), ('\u{115bc}', '\u{115bd}'),
+    ('\u{115bf}', '\u{115c0}'), ('\u{115dc}', '\u{115dd}'), ('\u{11633}', '\u{1163a}'),
+    ('\u{1163d}', '\u{1163d}'), ('\u{1163f}', '\u{11640}'), ('\u{116ab}', '\u{116ab}'),
+    ('\u{116ad}', '\u{116ad}'), ('\u{116b0}', '\u{116b5}'), ('\u{116b7}', '\u{116b7}'),
+    ('\u{1171d}', '\u{1171d}'), ('\u{1171f}', '\u{1171f}'), ('\u{11722}', '\u{11725}'),
+    ('\u{11727}', '\u{1172b}'), ('\u{1182f}', '\u{11837}'), ('\u{11839}', '\u{1183a}'),
+    ('\u{1193b}', '\u{1193c}'), ('\u{1193e}', '\u{1193e}'), ('\u{11943}', '\u{11943}'),
+    ('\u{119d4}', '\u{119d7}'), ('\u{119da}', '\u{119db}'), ('\u{119e0}', '\u{119e0}'),
+    ('\u{11a01}', '\u{11a0a}'), ('\u{11a33}', '\u{11a38}'), ('\u{11a3b}', '\u{11a3e}'),
+    ('\u{11a47}', '\u{11a47}'), ('\u{11a51}', '\u{11a56}'), ('\u{11a59}', '\u{11a5b}'),
+    ('\u{11a8a}', '\u{11a96}'), ('\u{11a98}', '\u{11a99}'), ('\u{11b60}', '\u{11b60}'),
+    ('\u{11b62}', '\u{11b64}'), ('\u{11b66}', '\u{11b66}'), ('\u{11c30}', '\u{11c36}'),
+    ('\u{11c38}', '\u{11c3d}'), ('\u{11c3f}', '\u{11c3f}'), ('\u{11c92}', '\u{11ca7}'),
+    ('\u{11caa}', '\u{11cb0}'), ('\u{11cb2}', '\u{11cb3}'), ('\u{11cb5}', '\u{11cb6}'),
+    ('\u{11d31}', '\u{11d36}'), ('\u{11d3a}', '\u{11d3a}'), ('\u{11d3c}', '\u{11d3d}'),
+    ('\u{11d3f}', '\u{11d45}'), ('\u{11d47}', '\u{11d47}'), ('\u{11d90}', '\u{11d91}'),
+    ('\u{11d95}', '\u{11d95}'), ('\u{11d97}', '\u{11d97}'), ('\u{11dd9}', '\u{11dd9}'),
+    ('\u{11ef3}', '\u{11ef4}'), ('\u{11f00}', '\u{11f01}'), ('\u{11f36}', '\u{11f3a}'),
+    ('\u{11f40}', '\u{11f40}'), ('\u{11f42}', '\u{11f42}'), ('\u{11f5a}', '\u{11f5a}'),
+    ('\u{13430}', '\u{13440}'), ('\u{13447}', '\u{13455}'), ('\u{1611e}', '\u{16129}'),
+    ('\u{1612d}', '\u{1612f}'), ('\u{16af0}', '\u{16af4}'), ('\u{16b30}', '\u{16b36}'),
+    ('\u{16b40}', '\u{16b43}'), ('\u{16d40}', '\u{16d42}'), ('\u{16d6b}', '\u{16d6c}'),
+    ('\u{16f4f}', '\u{16f4f}'), ('\u{16f8f}', '\u{16f9f}'), ('\u{16fe0}', '\u{16fe1}'),
+    ('\u{16fe3}', '\u{16fe4}'), ('\u{16ff2}', '\u{16ff3}'), ('\u{1aff0}', '\u{1aff3}'),
+    ('\u{1aff5}', '\u{1affb}'), ('\u{1affd}', '\u{1affe}'), ('\u{1bc9d}', '\u{1bc9e}'),
+    ('\u{1bca0}', '\u{1bca3}'), ('\u{1cf00}', '\u{1cf2d}'), ('\u{1cf30}', '\u{1cf46}'),
+    ('\u{1d167}', '\u{1d169}'), ('\u{1d173}', '\u{1d182}'), ('\u{1d185}', '\u{1d18b}'),
+    ('\u{1d1aa}', '\u{1d1ad}'), ('\u{1d242}', '\u{1d244}'), ('\u{1da00}', '\u{1da36}'),
+    ('\u{1da3b}', '\u{1da6c}'), ('\u{1da75}', '\u{1da75}'), ('\u{1da84}', '\u{1da84}'),
+    ('\u{1da9b}', '\u{1da9f}'), ('\u{1daa1}', '\u{1daaf}'), ('\u{1e000}', '\u{1e006}'),
+    ('\u{1e008}', '\u{1e018}'), ('\u{1e01b}', '\u{1e021}'), ('\u{1e023}', '\u{1e024}'),
+    ('\u{1e026}', '\u{1e02a}'), ('\u{1e030}', '\u{1e06d}'), ('\u{1e08f}', '\u{1e08f}'),
+    ('\u{1e130}', '\u{1e13d}'), ('\u{1e2ae}', '\u{1e2ae}'), ('\u{1e2ec}', '\u{1e2ef}'),
+    ('\u{1e4eb}', '\u{1e4ef}'), ('\u{1e5ee}', '\u{1e5ef}'), ('\u{1e6e3}', '\u{1e6e3}'),
+    ('\u{1e6e6}', '\u{1e6e6}'), ('\u{1e6ee}', '\u{1e6ef}'), ('\u{1e6f5}', '\u{1e6f5}'),
+    ('\u{1e6ff}', '\u{1e6ff}'), ('\u{1e8d0}', '\u{1e8d6}'), ('\u{1e944}', '\u{1e94b}'),
+    ('\u{1f3fb}', '\u{1f3ff}'), ('\u{e0001}', '\u{e0001}'), ('\u{e0020}', '\u{e007f}'),
+    ('\u{e0100}', '\u{e01ef}'),
+];
+
+/// General category `Lt`, the only cased letters that are neither
+/// `Lowercase` nor `Uppercase`
+#[rustfmt::skip]
+pub(super) const TITLECASE_LETTER: &[(char, char)] = &[
+    ('\u{1c5}', '\u{1c5}'), ('\u{1c8}', '\u{1c8}'), ('\u{1cb}', '\u{1cb}'), ('\u{1f2}', '\u{1f2}'),
+    ('\u{1f88}', '\u{1f8f}'), ('\u{1f98}', '\u{1f9f}'), ('\u{1fa8}', '\u{1faf}'),
+    ('\u{1fbc}', '\u{1fbc}'), ('\u{1fcc}', '\u{1fcc}'), ('\u{1ffc}', '\u{1ffc}'),
+];
+
+/// `Soft_Dotted`: letters whose dot disappears under an accent
+#[rustfmt::skip]
+pub(super) const SOFT_DOTTED: &[(char, char)] = &[
+    ('\u{69}', '\u{6a}'), ('\u{12f}', '\u{12f}'), ('\u{249}', '\u{249}'), ('\u{268}', '\u{268}'),
+    ('\u{29d}', '\u{29d}'), ('\u{2b2}', '\u{2b2}'), ('\u{3f3}', '\u{3f3}'), ('\u{456}', '\u{456}'),
+    ('\u{458}', '\u{458}'), ('\u{1d62}', '\u{1d62}'), ('\u{1d96}', '\u{1d96}'),
+    ('\u{1da4}', '\u{1da4}'), ('\u{1da8}', '\u{1da8}'), ('\u{1e2d}', '\u{1e2d}'),
+    ('\u{1ecb}', '\u{1ecb}'), ('\u{2071}', '\u{2071}'), ('\u{2148}', '\u{2149}'),
+    ('\u{2c7c}', '\u{2c7c}'), ('\u{1d422}', '\u{1d423}'), ('\u{1d456}', '\u{1d457}'),
+    ('\u{1d48a}', '\u{1d48b}'), ('\u{1d4be}', '\u{1d4bf}'), ('\u{1d4f2}', '\u{1d4f3}'),
+    ('\u{1d526}', '\u{1d527}'), ('\u{1d55a}', '\u{1d55b}'), ('\u{1d58e}', '\u{1d58f}'),
+    ('\u{1d5c2}', '\u{1d5c3}'), ('\u{1d5f6}', '\u{1d5f7}'), ('\u{1d62a}', '\u{1d62b}'),
+    ('\u{1d65e}', '\u{1d65f}'), ('\u{1d692}', '\u{1d693}'), ('\u{1df1a}', '\u{1df1a}'),
+    ('\u{1e04c}', '\u{1e04d}'), ('\u{1e068}', '\u{1e068}'),
+];
+
+/// Full case folding (statuses C and F) where it differs from lowercasing
+#[rustfmt::skip]
+pub(super) const FOLD: &[(char, &str)] = &[
+    ('\u{b5}', "\u{3bc}"), ('\u{df}', "ss"), ('\u{149}', "\u{2bc}n"), ('\u{17f}', "s"),
+    ('\u{1f0}', "j\u{30c}"), ('\u{345}', "\u{3b9}"), ('\u{390}', "\u{3b9}\u{308}\u{301}"),
+    ('\u{3b0}', "\u{3c5}\u{308}\u{301}"), ('\u{3c2}', "\u{3c3}"), ('\u{3d0}', "\u{3b2}"),
+    ('\u{3d1}', "\u{3b8}"), ('\u{3d5}', "\u{3c6}"), ('\u{3d6}', "\u{3c0}"), ('\u{3f0}', "\u{3ba}"),
+    ('\u{3f1}', "\u{3c1}"), ('\u{3f5}', "\u{3b5}"), ('\u{587}', "\u{565}\u{582}"),
+    ('\u{13a0}', "\u{13a0}"), ('\u{13a1}', "\u{13a1}"), ('\u{13a2}', "\u{13a2}"),
+    ('\u{13a3}', "\u{13a3}"), ('\u{13a4}', "\u{13a4}"), ('\u{13a5}', "\u{13a5}"),
+    ('\u{13a6}', "\u{13a6}"), ('\u{13a7}', "\u{13a7}"), ('\u{13a8}', "\u{13a8}"),
+    ('\u{13a9}', "\u{13a9}"), ('\u{13aa}', "\u{13aa}"), ('\u{13ab}', "\u{13ab}"),
+    ('\u{13ac}', "\u{13ac}"), ('\u{13ad}', "\u{13ad}"), ('\u{13ae}', "\u{13ae}"),
+    ('\u{13af}', "\u{13af}"), ('\u{13b0}', "\u{13b0}"), ('\u{13b1}', "\u{13b1}"),
+    ('\u{13b2}', "\u{13b2}"), ('\u{13b3}', "\u{13b3}"), ('\u{13b4}', "\u{13b4}"),
+    ('\u{13b5}', "\u{13b5}"), ('\u{13b6}', "\u{13b6}"), ('\u{13b7}', "\u{13b7}"),
+    ('\u{13b8}', "\u{13b8}"), ('\u{13b9}', "\u{13b9}"), ('\u{13ba}', "\u{13ba}"),
+    ('\u{13bb}', "\u{13bb}"), ('\u{13bc}', "\u{13bc}"), ('\u{13bd}', "\u{13bd}"),
+    ('\u{13be}', "\u{13be}"), ('\u{13bf}', "\u{13bf}"), ('\u{13c0}', "\u{13c0}"),
+    ('\u{13c1}', "\u{13c1}"), ('\u{13c2}', "\u{13c2}"), ('\u{13c3}', "\u{13c3}"),
+    ('\u{13c4}', "\u{13c4}"), ('\u{13c5}', "\u{13c5}"), ('\u{13c6}', "\u{13c6}"),
+    ('\u{13c7}', "\u{13c7}"), ('\u{13c8}', "\u{13c8}"), ('\u{13c9}', "\u{13c9}"),
+    ('\u{13ca}', "\u{13ca}"), ('\u{13cb}', "\u{13cb}"), ('\u{13cc}', "\u{13cc}"),
+    ('\u{13cd}', "\u{13cd}"), ('\u{13ce}', "\u{13ce}"), ('\u{13cf}', "\u{13cf}"),
+    ('\u{13d0}', "\u{13d0}"), ('\u{13d1}', "\u{13d1}"), ('\u{13d2}', "\u{13d2}"),
+    ('\u{13d3}', "\u{13d3}"), ('\u{13d4}', "\u{13d4}"), ('\u{13d5}', "\u{13d5}"),
+    ('\u{13d6}', "\u{13d6}"), ('\u{13d7}', "\u{13d7}"), ('\u{13d8}', "\u{13d8}"),
+    ('\u{13d9}', "\u{13d9}"), ('\u{13da}', "\u{13da}"), ('\u{13db}', "\u{13db}"),
+    ('\u{13dc}', "\u{13dc}"), ('\u{13dd}', "\u{13dd}"), ('\u{13de}', "\u{13de}"),
+    ('\u{13df}', "\u{13df}"), ('\u{13e0}', "\u{13e0}"), ('\u{13e1}', "\u{13e1}"),
+    ('\u{13e2}', "\u{13e2}"), ('\u{13e3}', "\u{13e3}"), ('\u{13e4}', "\u{13e4}"),
+    ('\u{13e5}', "\u{13e5}"), ('\u{13e6}', "\u{13e6}"), ('\u{13e7}', "\u{13e7}"),
+    ('\u{13e8}', "\u{13e8}"), ('\u{13e9}', "\u{13e9}"), ('\u{13ea}', "\u{13ea}"),
+    ('\u{13eb}', "\u{13eb}"), ('\u{13ec}', "\u{13ec}"), ('\u{13ed}', "\u{13ed}"),
+    ('\u{13ee}', "\u{13ee}"), ('\u{13ef}', "\u{13ef}"), ('\u{13f0}', "\u{13f0}"),
+    ('\u{13f1}', "\u{13f1}"), ('\u{13f2}', "\u{13f2}"), ('\u{13f3}', "\u{13f3}"),
+    ('\u{13f4}', "\u{13f4}"), ('\u{13f5}', "\u{13f5}"), ('\u{13f8}', "\u{13f0}"),
+    ('\u{13f9}', "\u{13f1}"), ('\u{13fa}', "\u{13f2}"), ('\u{13fb}', "\u{13f3}"),
+    ('\u{13fc}', "\u{13f4}"), ('\u{13fd}', "\u{13f5}"), ('\u{1c80}', "\u{432}"),
+    ('\u{1c81}', "\u{434}"), ('\u{1c82}', "\u{43e}"), ('\u{1c83}', "\u{441}"),
+    ('\u{1c84}', "\u{442}"), ('\u{1c85}', "\u{442}"), ('\u{1c86}', "\u{44a}"),
+    ('\u{1c87}', "\u{463}"), ('\u{1c88}', "\u{a64b}"), ('\u{1e96}', "h\u{331}"),
+    ('\u{1e97}', "t\u{308}"), ('\u{1e98}', "w\u{30a}"), ('\u{1e99}', "y\u{30a}"),
+    ('\u{1e9a}', "a\u{2be}"), ('\u{1e9b}', "\u{1e61}"), ('\u{1e9e}', "ss"),
+    ('\u{1f50}', "\u{3c5}\u{313}"), ('\u{1f52}', "\u{3c5}\u{313}\u{300}"),
+    ('\u{1f54}', "\u{3c5}\u{313}\u{301}"), ('\u{1f56}', "\u{3c5}\u{313}\u{342}"),
+    ('\u{1f80}', "\u{1f00}\u{3b9}"), ('\u{1f81}', "\u{1f01}\u{3b9}"),
+    ('\u{1f82}', "\u{1f02}\u{3b9}"), ('\u{1f83}', "\u{1f03}\u{3b9}"),
+    ('\u{1f84}', "\u{1f04}\u{3b9}"), ('\u{1f85}', "\u{1f05}\u{3b9}"),
+    ('\u{1f86}', "\u{1f06}\u{3b9}"), ('\u{1f87}', "\u{1f07}\u{3b9}"),
+    ('\u{1f88}', "\u{1f00}\u{3b9}"), ('\u{1f89}', "\u{1f01}\u{3b9}"),
+    ('\u{1f8a}', "\u{1f02}\u{3b9}"), ('\u{1f8b}', "\u{1f03}\u{3b9}"),
+    ('\u{1f8c}', "\u{1f04}\u{3b9}"), ('\u{1f8d}', "\u{1f05}\u{3b9}"),
+    ('\u{1f8e}', "\u{1f06}\u{3b9}"), ('\u{1f8f}', "\u{1f07}\u{3b9}"),
+    ('\u{1f90}', "\u{1f20}\u{3b9}"), ('\u{1f91}', "\u{1f21}\u{3b9}"),
+    ('\u{1f92}', "\u{1f22}\u{3b9}"), ('\u{1f93}', "\u{1f23}\u{3b9}"),
+    ('\u{1f94}', "\u{1f24}\u{3b9}"), ('\u{1f95}', "\u{1f25}\u{3b9}"),
+    ('\u{1f96}', "\u{1f26}\u{3b9}"), ('\u{1f97}', "\u{1f27}\u{3b9}"),
+    ('\u{1f98}', "\u{1f20}\u{3b9}"), ('\u{1f99}', "\u{1f21}\u{3b9}"),
+    ('\u{1f9a}', "\u{1f22}\u{3b9}"), ('\u{1f9b}', "\u{1f23}\u{3b9}"),
+    ('\u{1f9c}', "\u{1f24}\u{3b9}"), ('\u{1f9d}', "\u{1f25}\u{3b9}"),
+    ('\u{1f9e}', "\u{1f26}\u{3b9}"), ('\u{1f9f}', "\u{1f27}\u{3b9}"),
+    ('\u{1fa0}', "\u{1f60}\u{3b9}"), ('\u{1fa1}', "\u{1f61}\u{3b9}"),
+    ('\u{1fa2}', "\u{1f62}\u{3b9}"), ('\u{1fa3}', "\u{1f63}\u{3b9}"),
+    ('\u{1fa4}', "\u{1f64}\u{3b9}"), ('\u{1fa5}', "\u{1f65}\u{3b9}"),
+    ('\u{1fa6}', "\u{1f66}\u{3b9}"), ('\u{1fa7}', "\u{1f67}\u{3b9}"),
+    ('\u{1fa8}', "\u{1f60}\u{3b9}"), ('\u{1fa9}', "\u{1f61}\u{3b9}"),
+    ('\u{1faa}', "\u{1f62}\u{3b9}"), ('\u{1fab}', "\u{1f63}\u{3b9}"),
+    ('\u{1fac}', "\u{1f64}\u{3b9}"), ('\u{1fad}', "\u{1f65}\u{3b9}"),
+    ('\u{1fae}', "\u{1f66}\u{3b9}"), ('\u{1faf}', "\u{1f67}\u{3b9}"),
+    ('\u{1fb2}', "\u{1f70}\u{3b9}"), ('\u{1fb3}', "\u{3b1}\u{3b9}"), ('\u{1fb4}', "\u{3ac}\u{3b9}"),
+    ('\u{1fb6}', "\u{3b1}\u{342}"), ('\u{1fb7}', "\u{3b1}\u{342}\u{3b9}"),
+    ('\u{1fbc}', "\u{3b1}\u{3b9}"), ('\u{1fbe}', "\u{3b9}"), ('\u{1fc2}', "\u{1f74}\u{3b9}"),
+    ('\u{1fc3}', "\u{3b7}\u{3b9}"), ('\u{1fc4}', "\u{3ae}\u{3b9}"), ('\u{1fc6}', "\u{3b7}\u{342}"),
+    ('\u{1fc7}', "\u{3b7}\u{342}\u{3b9}"), ('\u{1fcc}', "\u{3b7}\u{3b9}"),
+    ('\u{1fd2}', "\u{3b9}\u{308}\u{300}"), ('\u{1fd3}', "\u{3b9}\u{308}\u{301}"),
+    ('\u{1fd6}', "\u{3b9}\u{342}"), ('\u{1fd7}', "\u{3b9}\u{308}\u{342}"),
+    ('\u{1fe2}', "\u{3c5}\u{308}\u{300}"), ('\u{1fe3}', "\u{3c5}\u{308}\u{301}"),
+    ('\u{1fe4}', "\u{3c1}\u{313}"), ('\u{1fe6}', "\u{3c5}\u{342}"),
+    ('\u{1fe7}', "\u{3c5}\u{308}\u{342}"), ('\u{1ff2}', "\u{1f7c}\u{3b9}"),
+    ('\u{1ff3}', "\u{3c9}\u{3b9}"), ('\u{1ff4}', "\u{3ce}\u{3b9}"), ('\u{1ff6}', "\u{3c9}\u{342}"),
+    ('\u{1ff7}', "\u{3c9}\u{342}\u{3b9}"), ('\u{1ffc}', "\u{3c9}\u{3b9}"), ('\u{ab70}', "\u{13a0}"),
+    ('\u{ab71}', "\u{13a1}"), ('\u{ab72}', "\u{13a2}"), ('\u{ab73}', "\u{13a3}"),
+    ('\u{ab74}', "\u{13a4}"), ('\u{ab75}', "\u{13a5}"), ('\u{ab76}', "\u{13a6}"),
+    ('\u{ab77}', "\u{13a7}"), ('\u{ab78}', "\u{13a8}"), ('\u{ab79}', "\u{13a9}"),
+    ('\u{ab7a}', "\u{13aa}"), ('\u{ab7b}', "\u{13ab}"), ('\u{ab7c}', "\u{13ac}"),
+    ('\u{ab7d}', "\u{13ad}"), ('\u{ab7e}', "\u{13ae}"), ('\u{ab7f}', "\u{13af}"),
+    ('\u{ab80}', "\u{13b0}"), ('\u{ab81}', "\u{13b1}"), ('\u{ab82}', "\u{13b2}"),
+    ('\u{ab83}', "\u{13b3}"), ('\u{ab84}', "\u{13b4}"), ('\u{ab85}', "\u{13b5}"),
+    ('\u{ab86}', "\u{13b6}"), ('\u{ab87}', "\u{13b7}"), ('\u{ab88}', "\u{13b8}"),
+    ('\u{ab89}', "\u{13b9}"), ('\u{ab8a}', "\u{13ba}"), ('\u{ab8b}', "\u{13bb}"),
+    ('\u{ab8c}', "\u{13bc}"), ('\u{ab8d}', "\u{13bd}"), ('\u{ab8e}', "\u{13be}"),
+    ('\u{ab8f}', "\u{13bf}"), ('\u{ab90}', "\u{13c0}"), ('\u{ab91}', "\u{13c1}"),
+    ('\u{ab92}', "\u{13c2}"), ('\u{ab93}', "\u{13c3}"), ('\u{ab94}', "\u{13c4}"),
+    ('\u{ab95}', "\u{13c5}"), ('\u{ab96}', "\u{13c6}"), ('\u{ab97}', "\u{13c7}"),
+    ('\u{ab98}', "\u{13c8}"), ('\u{ab99}', "\u{13c9}"), ('\u{ab9a}', "\u{13ca}"),
+    ('\u{ab9b}', "\u{13cb}"), ('\u{ab9c}', "\u{13cc}"), ('\u{ab9d}', "\u{13cd}"),
+    ('\u{ab9e}', "\u{13ce}"), ('\u{ab9f}', "\u{13cf}"), ('\u{aba0}', "\u{13d0}"),
+    ('\u{aba1}', "\u{13d1}"), ('\u{aba2}', "\u{13d2}"), ('\u{aba3}', "\u{13d3}"),
+    ('\u{aba4}', "\u{13d4}"), ('\u{aba5}', "\u{13d5}"), ('\u{aba6}', "\u{13d6}"),
+    ('\u{aba7}', "\u{13d7}"), ('\u{aba8}', "\u{13d8}"), ('\u{aba9}', "\u{13d9}"),
+    ('\u{abaa}', "\u{13da}"), ('\u{abab}', "\u{13db}"), ('\u{abac}', "\u{13dc}"),
+    ('\u{abad}', "\u{13dd}"), ('\u{abae}', "\u{13de}"), ('\u{abaf}', "\u{13df}"),
+    ('\u{abb0}', "\u{13e0}"), ('\u{abb1}', "\u{13e1}"), ('\u{abb2}', "\u{13e2}"),
+    ('\u{abb3}', "\u{13e3}"), ('\u{abb4}', "\u{13e4}"), ('\u{abb5}', "\u{13e5}"),
+    ('\u{abb6}', "\u{13e6}"), ('\u{abb7}', "\u{13e7}"), ('\u{abb8}', "\u{13e8}"),
+    ('\u{abb9}', "\u{13e9}"), ('\u{abba}', "\u{13ea}"), ('\u{abbb}', "\u{13eb}"),
+    ('\u{abbc}', "\u{13ec}"), ('\u{abbd}', "\u{13ed}"), ('\u{abbe}', "\u{13ee}"),
+    ('\u{abbf}', "\u{13ef}"), ('\u{fb00}', "ff"), ('\u{fb01}', "fi"), ('\u{fb02}', "fl"),
+    ('\u{fb03}', "ffi"), ('\u{fb04}', "ffl"), ('\u{fb05}', "st"), ('\u{fb06}', "st"),
+    ('\u{fb13}', "\u{574}\u{576}"), ('\u{fb14}', "\u{574}\u{565}"), ('\u{fb15}', "\u{574}\u{56b}"),
+    ('\u{fb16}', "\u{57e}\u{576}"), ('\u{fb17}', "\u{574}\u{56d}"),
+];
+
+/// Full titlecase mappings where they differ from uppercasing
+#[rustfmt::skip]
+pub(super) const TITLE: &[(char, &str)] = &[
+    ('\u{df}', "Ss"), ('\u{1c4}', "\u{1c5}"), ('\u{1c5}', "\u{1c5}"), ('\u{1c6}', "\u{1c5}"),
+    ('\u{1c7}', "\u{1c8}"), ('\u{1c8}', "\u{1c8}"), ('\u{1c9}', "\u{1c8}"), ('\u{1ca}', "\u{1cb}"),
+    ('\u{1cb}', "\u{1cb}"), ('\u{1cc}', "\u{1cb}"), ('\u{1f1}', "\u{1f2}"), ('\u{1f2}', "\u{1f2}"),
+    ('\u{1f3}', "\u{1f2}"), ('\u{587}', "\u{535}\u{582}"), ('\u{10d0}', "\u{10d0}"),
+    ('\u{10d1}', "\u{10d1}"), ('\u{10d2}', "\u{10d2}"), ('\u{10d3}', "\u{10d3}"),
+    ('\u{10d4}', "\u{10d4}"), ('\u{10d5}', "\u{10d5}"), ('\u{10d6}', "\u{10d6}"),
+    ('\u{10d7}', "\u{10d7}"), ('\u{10d8}', "\u{10d8}"), ('\u{10d9}', "\u{10d9}"),
+    ('\u{10da}', "\u{10da}"), ('\u{10db}', "\u{10db}"), ('\u{10dc}', "\u{10dc}"),
+    ('\u{10dd}', "\u{10dd}"), ('\u{10de}', "\u{10de}"), ('\u{10df}', "\u{10df}"),
+    ('\u{10e0}', "\u{10e0}"), ('\u{10e1}', "\u{10e1}"), ('\u{10e2}', "\u{10e2}"),
+    ('\u{10e3}', "\u{10e3}"), ('\u{10e4}', "\u{10e4}"), ('\u{10e5}', "\u{10e5}"),
+    ('\u{10e6}', "\u{10e6}"), ('\u{10e7}', "\u{10e7}"), ('\u{10e8}', "\u{10e8}"),
+    ('\u{10e9}', "\u{10e9}"), ('\u{10ea}', "\u{10ea}"), ('\u{10eb}', "\u{10eb}"),
+    ('\u{10ec}', "\u{10ec}"), ('\u{10ed}', "\u{10ed}"), ('\u{10ee}', "\u{10ee}"),
+    ('\u{10ef}', "\u{10ef}"), ('\u{10f0}', "\u{10f0}"), ('\u{10f1}', "\u{10f1}"),
+    ('\u{10f2}', "\u{10f2}"), ('\u{10f3}', "\u{10f3}"), ('\u{10f4}', "\u{10f4}"),
+    ('\u{10f5}', "\u{10f5}"), ('\u{10f6}', "\u{10f6}"), ('\u{10f7}', "\u{10f7}"),
+    ('\u{10f8}', "\u{10f8}"), ('\u{10f9}', "\u{10f9}"), ('\u{10fa}', "\u{10fa}"),
+    ('\u{10fd}', "\u{10fd}"), ('\u{10fe}', "\u{10fe}"), ('\u{10ff}', "\u{10ff}"),
+    ('\u{1f80}', "\u{1f88}"), ('\u{1f81}', "\u{1f89}"), ('\u{1f82}', "\u{1f8a}"),
+    ('\u{1f83}', "\u{1f8b}"), ('\u{1f84}', "\u{1f8c}"), ('\u{1f85}', "\u{1f8d}"),
+    ('\u{1f86}', "\u{1f8e}"), ('\u{1f87}', "\u{1f8f}"), ('\u{1f88}', "\u{1f88}"),
+    ('\u{1f89}', "\u{1f89}"), ('\u{1f8a}', "\u{1f8a}"), ('\u{1f8b}', "\u{1f8b}"),
+    ('\u{1f8c}', "\u{1f8c}"), ('\u{1f8d}', "\u{1f8d}"), ('\u{1f8e}', "\u{1f8e}"),
+    ('\u{1f8f}', "\u{1f8f}"), ('\u{1f90}', "\u{1f98}"), ('\u{1f91}', "\u{1f99}"),
+    ('\u{1f92}', "\u{1f9a}"), ('\u{1f93}', "\u{1f9b}"), ('\u{1f94}', "\u{1f9c}"),
+    ('\u{1f95}', "\u{1f9d}"), ('\u{1f96}', "\u{1f9e}"), ('\u{1f97}', "\u{1f9f}"),
+    ('\u{1f98}', "\u{1f98}"), ('\u{1f99}', "\u{1f99}"), ('\u{1f9a}', "\u{1f9a}"),
+    ('\u{1f9b}', "\u{1f9b}"), ('\u{1f9c}', "\u{1f9c}"), ('\u{1f9d}', "\u{1f9d}"),
+    ('\u{1f9e}', "\u{1f9e}"), ('\u{1f9f}', "\u{1f9f}"), ('\u{1fa0}', "\u{1fa8}"),
+    ('\u{1fa1}', "\u{1fa9}"), ('\u{1fa2}', "\u{1faa}"), ('\u{1fa3}', "\u{1fab}"),
+    ('\u{1fa4}', "\u{1fac}"), ('\u{1fa5}', "\u{1fad}"), ('\u{1fa6}', "\u{1fae}"),
+    ('\u{1fa7}', "\u{1faf}"), ('\u{1fa8}', "\u{1fa8}"), ('\u{1fa9}', "\u{1fa9}"),
+    ('\u{1faa}', "\u{1faa}"), ('\u{1fab}', "\u{1fab}"), ('\u{1fac}', "\u{1fac}"),
+    ('\u{1fad}', "\u{1fad}"), ('\u{1fae}', "\u{1fae}"), ('\u{1faf}', "\u{1faf}"),
+    ('\u{1fb2}', "\u{1fba}\u{345}"), ('\u{1fb3}', "\u{1fbc}"), ('\u{1fb4}', "\u{386}\u{345}"),
+    ('\u{1fb7}', "\u{391}\u{342}\u{345}"), ('\u{1fbc}', "\u{1fbc}"),
+    ('\u{1fc2}', "\u{1fca}\u{345}"), ('\u{1fc3}', "\u{1fcc}"), ('\u{1fc4}', "\u{389}\u{345}"),
+    ('\u{1fc7}', "\u{397}\u{342}\u{345}"), ('\u{1fcc}', "\u{1fcc}"),
+    ('\u{1ff2}', "\u{1ffa}\u{345}"), ('\u{1ff3}', "\u{1ffc}"), ('\u{1ff4}', "\u{38f}\u{345}"),
+    ('\u{1ff7}', "\u{3a9}\u{342}\u{345}"), ('\u{1ffc}', "\u{1ffc}"), ('\u{fb00}', "Ff"),
+    ('\u{fb01}', "Fi"), ('\u{fb02}', "Fl"), ('\u{fb03}', "Ffi"), ('\u{fb04}', "Ffl"),
+    ('\u{fb05}', "St"), ('\u{fb06}', "St"), ('\u{fb13}', "\u{544}\u{576}"),
+    ('\u{fb14}', "\u{544}\u{565}"), ('\u{fb15}', "\u{544}\u{56b}"), ('\u{fb16}', "\u{54e}\u{576}"),
+    ('\u{fb17}', "\u{544}\u{56d}"),
+];
@@ -0,0 +1,1197 @@
+//! # Laboratory Demonstrations
+//!
+//! Each demonstration performs its string experiments and returns a
+//! structured report describing what happened. Nothing here prints to the
+//! terminal - presentation is left to the [`render`](crate::render) module
+//! (or to your own tooling).
+
+use crate::allocator::{self, track_if_installed, AllocStats, SizeClass};
+use crate::batch::{Batch, BatchConfig, Outcome, TaskResult};
+use crate::bench::BenchConfig;
+use crate::case::{self, CaseLocale, CaseMapping};
+use crate::growth::{self, CapacityOp, CapacityStep, GrowthCurve};
+use crate::inspector::*;
+use crate::lifetime::Tracked;
+use crate::normalize::{self, NormalizationForm, QuickCheck};
+use crate::pipeline::{self, Journey, Stage};
+use crate::raw::{self, HeapDump, RawHeader, SharedAllocation};
+use crate::transformer::*;
+use crate::workload::{self, CopyStep, EchoServer, Workload, WorkloadReport};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Inputs the demonstrations run on
+///
+/// Every field has a default matching the lab's classic session, so
+/// `DemoInputs::default()` reproduces it exactly.
+#[derive(Debug, Clone)]
+pub struct DemoInputs {
+    /// Sample text replacing each demo's own default, for the demos that
+    /// work on a single string
+    pub text: Option<String>,
+    /// Second string for the interleave operations
+    pub other: Option<String>,
+    /// Initial capacity of the string in the capacity demo
+    pub capacity: usize,
+    /// How many times the transformations demo repeats its pattern
+    pub repeat: usize,
+    /// Length the growth demo pushes a string up to
+    pub growth_target: usize,
+    /// Bytes per push in the growth demo, one curve per chunk size
+    pub chunks: Vec<usize>,
+    /// Inputs the concurrency demo processes at once
+    pub max_concurrent: usize,
+    /// Per-input timeout in the concurrency demo, in milliseconds
+    pub timeout_ms: u64,
+    /// When the concurrency demo cancels what is left, in milliseconds
+    pub cancel_after_ms: u64,
+    /// Stages of the pipeline demo, in order
+    pub stages: Vec<Stage>,
+}
+
+impl Default for DemoInputs {
+    fn default() -> Self {
+        Self {
+            text: None,
+            other: None,
+            capacity: 8,
+            repeat: 5,
+            growth_target: 1000,
+            chunks: vec![1, 100],
+            max_concurrent: 2,
+            timeout_ms: 250,
+            cancel_after_ms: 420,
+            stages: vec![
+                Stage::Forward,
+                Stage::Push("!".to_string()),
+                Stage::Normalize(NormalizationForm::Nfc),
+                Stage::CaseMap(CaseMapping::Upper, CaseLocale::Root),
+                Stage::Forward,
+            ],
+        }
+    }
+}
+
+impl DemoInputs {
+    /// The sample text, or `default` when none was given
+    pub fn text_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.text.as_deref().unwrap_or(default)
+    }
+
+    /// The second string, or `default` when none was given
+    pub fn other_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.other.as_deref().unwrap_or(default)
+    }
+}
+
+/// Size of a string type's handle (the part that lives on the stack)
+#[derive(Debug, Clone)]
+pub struct TypeSize {
+    /// Name of the type, e.g. `String`
+    pub type_name: &'static str,
+    /// `size_of` the type in bytes
+    pub size: usize,
+    /// What the bytes are made of
+    pub layout: &'static str,
+}
+
+/// Report for the string types demonstration
+#[derive(Debug, Clone)]
+pub struct StringTypesReport {
+    pub literal: StringMemoryInfo,
+    pub owned: StringMemoryInfo,
+    pub boxed: StringMemoryInfo,
+    pub sizes: Vec<TypeSize>,
+    pub literal_bytes: ByteReport,
+}
+
+/// Report for the ownership demonstration
+#[derive(Debug, Clone)]
+pub struct OwnershipReport {
+    pub original: StringMemoryInfo,
+    pub moved: LayoutComparison,
+    pub cloned: LayoutComparison,
+    /// Bytes allocated by the clone
+    pub clone_allocated: usize,
+    /// Bytes copied by the clone
+    pub clone_copied: usize,
+}
+
+/// Report for the capacity demonstration
+#[derive(Debug, Clone)]
+pub struct CapacityReport {
+    pub empty: StringMemoryInfo,
+    pub within_capacity: LayoutComparison,
+    pub reallocation: LayoutComparison,
+}
+
+/// Report for the clone-on-write demonstration
+#[derive(Debug, Clone)]
+pub struct CowReport {
+    pub borrowed: StringMemoryInfo,
+    pub to_owned: LayoutComparison,
+}
+
+/// Outcome of a single spawned async task
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskReport {
+    pub name: String,
+    #[serde(flatten)]
+    pub result: TaskResult<WorkloadReport>,
+}
+
+impl TaskReport {
+    /// The task's output, or `None` if the task did not finish
+    pub fn output(&self) -> Option<&str> {
+        self.result
+            .outcome
+            .value()
+            .map(|report| report.output.as_str())
+    }
+
+    /// Where the task copied the string and allocated buffers for it
+    pub fn steps(&self) -> &[CopyStep] {
+        self.result
+            .outcome
+            .value()
+            .map_or(&[], |report| report.steps.as_slice())
+    }
+}
+
+/// Report for the async operations demonstration
+#[derive(Debug, Clone)]
+pub struct AsyncReport {
+    pub tasks: Vec<TaskReport>,
+}
+
+impl AsyncReport {
+    /// Whether every task completed successfully
+    pub fn all_succeeded(&self) -> bool {
+        self.tasks.iter().all(|t| t.result.outcome.is_ok())
+    }
+}
+
+/// One input of the concurrency demonstration and what became of it
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchTask {
+    pub input: String,
+    #[serde(flatten)]
+    pub result: TaskResult<String>,
+}
+
+/// Report for the concurrency controls demonstration
+#[derive(Debug, Clone)]
+pub struct ConcurrencyReport {
+    pub max_concurrent: usize,
+    pub timeout_ms: u64,
+    /// When the cancellation token fired
+    pub cancel_after_ms: u64,
+    pub tasks: Vec<BatchTask>,
+}
+
+impl ConcurrencyReport {
+    /// Number of tasks whose outcome matches `predicate`
+    pub fn count(&self, predicate: impl Fn(&Outcome<String>) -> bool) -> usize {
+        self.tasks
+            .iter()
+            .filter(|t| predicate(&t.result.outcome))
+            .count()
+    }
+}
+
+/// Report for the channel pipeline demonstration
+#[derive(Debug, Clone)]
+pub struct PipelineReport {
+    pub stages: Vec<String>,
+    pub journeys: Vec<Journey>,
+}
+
+/// Report for the transformations demonstration
+#[derive(Debug, Clone)]
+pub struct TransformationsReport {
+    pub reverse_input: String,
+    pub reverse: TimedResult<String>,
+    pub reversed_bytes: ByteReport,
+    pub upper_input: String,
+    pub upper: TimedResult<String>,
+    pub repeat_pattern: String,
+    pub repeat_count: usize,
+    pub repeat: TimedResult<String>,
+    pub interleave_inputs: (String, String),
+    pub interleave: TimedResult<String>,
+    /// The same operations benchmarked statistically
+    pub benchmarks: Vec<TimedResult<String>>,
+    pub operations_count: usize,
+}
+
+/// Report for the Unicode demonstration
+#[derive(Debug, Clone)]
+pub struct UnicodeReport {
+    pub samples: Vec<ByteReport>,
+    /// The string used for the byte vs char indexing comparison
+    pub mixed: String,
+    /// Text with multi-code-point characters: accents, a flag, a ZWJ family
+    pub cluster_input: String,
+    /// `cluster_input` reversed by `char` (tears clusters apart)
+    pub char_reverse: TimedResult<String>,
+    /// `cluster_input` reversed by grapheme cluster
+    pub grapheme_reverse: TimedResult<String>,
+    /// The two strings interleaved for the interleave comparison
+    pub interleave_inputs: (String, String),
+    /// Interleaved by `char`
+    pub char_interleave: TimedResult<String>,
+    /// Interleaved by grapheme cluster
+    pub grapheme_interleave: TimedResult<String>,
+}
+
+impl UnicodeReport {
+    /// Each character of `mixed` with its UTF-8 width in bytes
+    pub fn char_widths(&self) -> Vec<(char, usize)> {
+        self.mixed.chars().map(|c| (c, c.len_utf8())).collect()
+    }
+}
+
+/// Two strings normalized to the same form
+#[derive(Debug, Clone)]
+pub struct NormalizedPair {
+    pub form: NormalizationForm,
+    /// Quick-check answers for the left and right input
+    pub quick_checks: (QuickCheck, QuickCheck),
+    pub left: TimedResult<Cow<'static, str>>,
+    pub right: TimedResult<Cow<'static, str>>,
+}
+
+impl NormalizedPair {
+    /// Whether both inputs normalized to the same bytes
+    pub fn equal(&self) -> bool {
+        self.left.value == self.right.value
+    }
+}
+
+/// Report for the Unicode normalization demonstration
+#[derive(Debug, Clone)]
+pub struct NormalizationReport {
+    /// The composed and decomposed spellings of "café"
+    pub inputs: (String, String),
+    /// "café" with a precomposed é (U+00E9)
+    pub composed: ByteReport,
+    /// "café" as e + U+0301 COMBINING ACUTE ACCENT
+    pub decomposed: ByteReport,
+    /// Whether the two compare equal with plain `==`
+    pub raw_equal: bool,
+    /// The composed and decomposed strings in every normalization form
+    pub canonical: Vec<NormalizedPair>,
+    /// A word with the "ﬁ" ligature and the same word spelled out
+    pub compatibility_inputs: (String, String),
+    /// The compatibility inputs in every normalization form
+    pub compatibility: Vec<NormalizedPair>,
+    /// NFC of already-normalized vs decomposed input, benchmarked
+    pub benchmarks: Vec<TimedResult<String>>,
+    pub operations_count: usize,
+}
+
+/// One case mapping applied to one input
+#[derive(Debug, Clone)]
+pub struct CaseSample {
+    /// What the sample demonstrates, with arrows spelled `->`
+    pub description: &'static str,
+    pub input: String,
+    pub mapping: CaseMapping,
+    pub locale: CaseLocale,
+    pub result: TimedResult<String>,
+}
+
+impl CaseSample {
+    /// Change in byte length from input to output
+    pub fn length_delta(&self) -> isize {
+        self.result.value.len() as isize - self.input.len() as isize
+    }
+}
+
+/// Two strings compared with and without case folding
+#[derive(Debug, Clone)]
+pub struct CaselessComparison {
+    pub left: String,
+    pub right: String,
+    /// Whether the strings are equal after `to_lowercase`
+    pub lowercase_equal: bool,
+    /// Whether they match under canonical caseless matching
+    pub caseless_equal: bool,
+}
+
+/// Report for the case mapping demonstration
+#[derive(Debug, Clone)]
+pub struct CaseMappingReport {
+    pub samples: Vec<CaseSample>,
+    pub comparisons: Vec<CaselessComparison>,
+    pub operations_count: usize,
+}
+
+/// Report for the capacity growth demonstration
+#[derive(Debug, Clone)]
+pub struct GrowthReport {
+    /// One curve per chunk size
+    pub curves: Vec<GrowthCurve>,
+    /// `reserve`, `reserve_exact` and `shrink_to` applied in sequence
+    pub steps: Vec<CapacityStep>,
+}
+
+/// Report for the allocator size class demonstration
+#[derive(Debug, Clone)]
+pub struct SizeClassReport {
+    /// Requested capacities grouped by the block size they got; empty when
+    /// the platform cannot report block sizes
+    pub classes: Vec<SizeClass>,
+    /// Strings whose capacity falls short of their block size
+    pub layouts: Vec<StringMemoryInfo>,
+}
+
+/// One way of handing the same text to several spawned tasks
+#[derive(Debug, Clone, Serialize)]
+pub struct SharingApproach {
+    pub name: &'static str,
+    /// Allocations made on this thread while handing out the text and
+    /// spawning the tasks
+    pub allocations: Option<AllocStats>,
+    pub tasks: Vec<TaskReport>,
+}
+
+/// Report for the shared ownership demonstration
+#[derive(Debug, Clone)]
+pub struct SharedReport {
+    /// An `Rc<str>` with two clones and a weak reference
+    pub rc: StringMemoryInfo,
+    pub rc_allocation: SharedAllocation,
+    /// The `Arc<str>` while the spawned tasks hold their clones
+    pub arc_allocation: SharedAllocation,
+    /// A `String` clone per task, then one `Arc<str>` shared by all
+    pub approaches: Vec<SharingApproach>,
+}
+
+/// Report for inspecting a single piece of text
+#[derive(Debug, Clone)]
+pub struct InspectionReport {
+    /// The text held in each string type
+    pub layouts: Vec<StringMemoryInfo>,
+    pub bytes: ByteReport,
+    /// The raw bytes of the `String`, `&str` and `Box<str>` objects
+    pub headers: Vec<RawHeader>,
+    /// The `String`'s heap buffer, spare capacity included
+    pub heap: HeapDump,
+}
+
+/// Report for applying a single operation
+#[derive(Debug, Clone)]
+pub struct TransformReport {
+    pub input: ByteReport,
+    pub result: TimedResult<String>,
+    pub output: ByteReport,
+    /// Input `String` against output `String`
+    pub layout: LayoutComparison,
+}
+
+/// Demonstrates basic string types and their memory layout
+#[tracing::instrument(skip(inputs))]
+pub async fn string_types(inputs: &DemoInputs) -> StringTypesReport {
+    info!("Creating various string types...");
+
+    // String literal - stored in binary's read-only data section
+    let literal: &'static str = "Rust";
+    let info_literal = literal.inspect("String literal (&'static str)");
+
+    // Owned String - heap allocated with capacity for growth
+    let owned = String::from(inputs.text_or("Rustacean"));
+    let info_owned = owned.inspect("Owned String (heap-allocated)");
+
+    // Box<str> - heap allocated but immutable, no extra capacity
+    let boxed: Box<str> = "Ferris".into();
+    let info_boxed = boxed.inspect("Boxed str");
+
+    // Demonstrate size differences
+    let sizes = vec![
+        TypeSize {
+            type_name: "&str",
+            size: std::mem::size_of::<&str>(),
+            layout: "fat pointer: ptr + len",
+        },
+        TypeSize {
+            type_name: "String",
+            size: std::mem::size_of::<String>(),
+            layout: "ptr + len + cap",
+        },
+        TypeSize {
+            type_name: "Box<str>",
+            size: std::mem::size_of::<Box<str>>(),
+            layout: "fat pointer: ptr + len",
+        },
+        TypeSize {
+            type_name: "Cow<str>",
+            size: std::mem::size_of::<Cow<str>>(),
+            layout: "enum: tag + fat pointer",
+        },
+    ];
+
+    StringTypesReport {
+        literal: info_literal,
+        owned: info_owned,
+        boxed: info_boxed,
+        sizes,
+        literal_bytes: ByteReport::new(literal, "Byte representation of 'Rust'"),
+    }
+}
+
+/// Demonstrates ownership, moves, and clones
+#[tracing::instrument(skip(inputs))]
+pub async fn ownership(inputs: &DemoInputs) -> OwnershipReport {
+    info!("Demonstrating ownership mechanics...");
+
+    let original = Tracked::new("original", inputs.text_or("Hello, Rust!"));
+    let info_original = original.inspect("Original String");
+
+    // Move - transfers ownership, no copy
+    info!("About to MOVE the string...");
+    let (moved, move_allocs) = track_if_installed(|| original);
+    // Note: `original` is now invalid - compiler prevents use
+    let info_moved = moved.inspect("After MOVE");
+
+    // Clone - creates a new heap allocation and copies data
+    info!("About to CLONE the string...");
+    let (cloned, clone_allocs) = track_if_installed(|| moved.clone());
+    let info_cloned = cloned.inspect("After CLONE");
+
+    OwnershipReport {
+        moved: LayoutComparison::between(&info_original, &info_moved, "MOVE Operation")
+            .with_allocations(move_allocs),
+        cloned: LayoutComparison::between(
+            &info_moved,
+            &info_cloned,
+            "CLONE Operation (allocation + copy)",
+        )
+        .with_allocations(clone_allocs),
+        original: info_original,
+        clone_allocated: cloned.capacity(),
+        clone_copied: cloned.len(),
+    }
+}
+
+/// Demonstrates capacity and reallocation
+#[tracing::instrument(skip(inputs), fields(capacity = inputs.capacity))]
+pub async fn capacity_and_growth(inputs: &DemoInputs) -> CapacityReport {
+    info!("Exploring how String manages capacity...");
+
+    // Create with exact capacity
+    let mut s = Tracked::new("s", String::with_capacity(inputs.capacity));
+    let info_empty = s.inspect(&format!("Empty String with capacity {}", inputs.capacity));
+
+    // Add data, within capacity unless the text is too long
+    let text = inputs.text_or("Rust");
+    let first_push = if text.len() <= s.capacity() {
+        "Within Capacity Push"
+    } else {
+        "Push Beyond Initial Capacity"
+    };
+    let ((), push_allocs) = track_if_installed(|| s.push_str(text));
+    let info_rust = s.inspect(&format!("After adding '{}' ({} bytes)", text, text.len()));
+
+    // Exceed capacity - forces reallocation
+    warn!("About to exceed capacity - reallocation will occur!");
+
+    // One byte more than the spare capacity
+    let overflow = "!".repeat(s.capacity() - s.len() + 1);
+    let ((), realloc_allocs) = track_if_installed(|| s.push_str(&overflow));
+
+    let info_reallocated = s.inspect("After exceeding capacity");
+
+    CapacityReport {
+        within_capacity: LayoutComparison::between(&info_empty, &info_rust, first_push)
+            .with_allocations(push_allocs),
+        reallocation: LayoutComparison::between(
+            &info_rust,
+            &info_reallocated,
+            "Reallocation Triggered",
+        )
+        .with_allocations(realloc_allocs),
+        empty: info_empty,
+    }
+}
+
+/// Demonstrates Clone-on-Write (Cow) optimization
+#[tracing::instrument(skip(inputs))]
+pub async fn clone_on_write(inputs: &DemoInputs) -> CowReport {
+    info!("Demonstrating Cow<str> for efficient conditional ownership...");
+
+    let static_str = inputs.text_or("Ferris the Crab");
+
+    // Cow starts borrowed - zero cost
+    let cow_borrowed: Cow<str> = Cow::Borrowed(static_str);
+    let info_borrowed = cow_borrowed.inspect("Cow::Borrowed (zero-cost)");
+
+    // Convert to owned when needed
+    let mut cow_owned = cow_borrowed.clone();
+    let ((), to_owned_allocs) = track_if_installed(|| cow_owned.to_mut().push_str(" 🦀"));
+
+    let info_owned = cow_owned.inspect("Cow::Owned (after mutation)");
+
+    CowReport {
+        to_owned: LayoutComparison::between(
+            &info_borrowed,
+            &info_owned,
+            "Cow: Borrowed -> Owned (lazy allocation)",
+        )
+        .with_allocations(to_owned_allocs),
+        borrowed: info_borrowed,
+    }
+}
+
+/// Demonstrates async string processing
+#[tracing::instrument(skip(inputs))]
+pub async fn async_operations(inputs: &DemoInputs) -> AsyncReport {
+    info!("Spawning multiple async tasks...");
+
+    let input = Tracked::new("input", inputs.text_or("Async"));
+    let (echo, workloads) = start_workloads().await;
+
+    // Spawn the workloads concurrently, each with its own copy of the input
+    let batch = spawn_workloads(workloads.iter().map(|&workload| (input.clone(), workload)));
+    let tasks = join_workloads(&workloads, batch).await;
+    drop(echo);
+
+    AsyncReport { tasks }
+}
+
+/// Starts the lab's echo server and lists the workloads the async demos
+/// spawn
+///
+/// Without an echo server the TCP workload is left out.
+async fn start_workloads() -> (Option<EchoServer>, Vec<Workload>) {
+    let echo = match EchoServer::start().await {
+        Ok(echo) => Some(echo),
+        Err(e) => {
+            error!("Could not start the echo server: {}", e);
+            None
+        }
+    };
+    let workloads = [
+        Some(Workload::FileRoundTrip),
+        echo.as_ref().map(|echo| Workload::TcpEcho(echo.addr())),
+        Some(Workload::CpuTransform),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (echo, workloads)
+}
+
+/// Spawns `async_process_string` for every input and workload pair, all
+/// at once
+fn spawn_workloads<S>(pairs: impl IntoIterator<Item = (S, Workload)>) -> Batch<WorkloadReport>
+where
+    S: AsRef<str> + Send + 'static,
+{
+    let config = BatchConfig::default();
+    Batch::spawn(
+        pairs,
+        &config,
+        &CancellationToken::new(),
+        |(input, workload)| async_process_string(input, workload),
+    )
+}
+
+/// Awaits the tasks spawned for `workloads`, in order
+async fn join_workloads(workloads: &[Workload], batch: Batch<WorkloadReport>) -> Vec<TaskReport> {
+    workloads
+        .iter()
+        .zip(batch.join().await)
+        .enumerate()
+        .map(|(i, (workload, result))| TaskReport {
+            name: format!("Task {}: {}", i + 1, workload),
+            result,
+        })
+        .collect()
+}
+
+/// Words the concurrency demo processes unless given text; `💥` makes the
+/// processing panic and `🐌` makes it sleep until stopped
+///
+/// With the default inputs every outcome shows up: `slowpoke🐌` holds a
+/// permit until the 250 ms timeout, the panicking word comes early, the other
+/// words take at most 50 ms each and are done by about 310 ms, and
+/// `sleepyhead🐌` is still running when the batch is cancelled at 420 ms.
+const BATCH_WORDS: &str =
+    "slowpoke🐌 alpha beta crash💥 gamma zeta eta theta iota kappa sleepyhead🐌";
+
+/// Processes a word for the concurrency demo: 10 ms per character, then an
+/// uppercase transform on the blocking pool
+async fn process_word(word: String) -> std::io::Result<String> {
+    if word.contains('💥') {
+        panic!("cannot process '{}'", word);
+    }
+    let per_char = if word.contains('🐌') {
+        Duration::from_secs(1)
+    } else {
+        Duration::from_millis(10)
+    };
+    tokio::time::sleep(per_char * word.chars().count() as u32).await;
+    Ok(workload::run(word, Workload::CpuTransform).await?.output)
+}
+
+/// Demonstrates a batch under a concurrency limit, per-task timeouts and
+/// cancellation, with every task's outcome reported on its own
+#[tracing::instrument(skip(inputs), fields(max_concurrent = inputs.max_concurrent))]
+pub async fn concurrency(inputs: &DemoInputs) -> ConcurrencyReport {
+    info!("Processing a batch under a concurrency limit...");
+
+    let words: Vec<String> = inputs
+        .text_or(BATCH_WORDS)
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let config = BatchConfig {
+        max_concurrent: inputs.max_concurrent,
+        timeout: Duration::from_millis(inputs.timeout_ms),
+    };
+
+    // Cancel whatever has not finished after a while
+    let cancel = CancellationToken::new();
+    let timer = task::spawn({
+        let cancel = cancel.clone();
+        let after = Duration::from_millis(inputs.cancel_after_ms);
+        async move {
+            tokio::time::sleep(after).await;
+            warn!("Cancelling the rest of the batch");
+            cancel.cancel();
+        }
+    });
+
+    // A panicking task comes back as a JoinError; its message goes to the
+    // log rather than to stderr, along with any other panic in the meantime
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|info| warn!(panic = %info, "A task panicked")));
+    let batch = Batch::spawn(words.clone(), &config, &cancel, process_word);
+    let results = batch.join().await;
+    std::panic::set_hook(hook);
+    timer.abort();
+
+    ConcurrencyReport {
+        max_concurrent: inputs.max_concurrent,
+        timeout_ms: inputs.timeout_ms,
+        cancel_after_ms: inputs.cancel_after_ms,
+        tasks: words
+            .into_iter()
+            .zip(results)
+            .map(|(input, result)| BatchTask { input, result })
+            .collect(),
+    }
+}
+
+/// Spare capacity the pipeline demo gives its strings, so a `push_str`
+/// stage can grow them in place
+const PIPELINE_SPARE: usize = 16;
+
+/// Demonstrates strings moving between tasks through channels: the handle
+/// moves, the buffer stays unless a stage replaces it
+#[tracing::instrument(skip(inputs), fields(stages = inputs.stages.len()))]
+pub async fn pipeline(inputs: &DemoInputs) -> PipelineReport {
+    info!("Sending strings through a channel pipeline...");
+
+    let strings = [inputs.text_or("Hello, pipeline"), inputs.other_or("Grüße")].map(|text| {
+        let mut s = String::with_capacity(text.len() + PIPELINE_SPARE);
+        s.push_str(text);
+        s
+    });
+    let journeys = pipeline::run(strings, &inputs.stages, 1).await;
+
+    for journey in journeys.iter().filter(|j| !j.zero_copy_hops()) {
+        warn!("A channel hop changed the buffer of '{}'", journey.input);
+    }
+
+    PipelineReport {
+        stages: inputs.stages.iter().map(ToString::to_string).collect(),
+        journeys,
+    }
+}
+
+/// Demonstrates `Rc<str>` and `Arc<str>`: one allocation holding the
+/// reference counts and the bytes, shared by every clone
+#[tracing::instrument(skip(inputs))]
+pub async fn shared_ownership(inputs: &DemoInputs) -> SharedReport {
+    info!("Sharing one allocation between several owners...");
+
+    let text = inputs.text_or("Shared");
+
+    // Rc: clones and weak references only touch the counts
+    let rc: Rc<str> = Rc::from(text);
+    let clones = [Rc::clone(&rc), Rc::clone(&rc)];
+    let weak = Rc::downgrade(&rc);
+    let info_rc = rc.inspect("Rc<str> with 2 clones and 1 weak reference");
+    let rc_allocation = raw::rc_str_allocation(&rc);
+    drop((clones, weak));
+
+    let (echo, workloads) = start_workloads().await;
+
+    // A String clone per task: every task gets its own copy
+    let input = String::from(text);
+    let (batch, cloned_allocs) = track_if_installed(|| {
+        spawn_workloads(workloads.iter().map(|&workload| (input.clone(), workload)))
+    });
+    let cloned_tasks = join_workloads(&workloads, batch).await;
+
+    // One Arc shared by all tasks: a clone is a count increment. The header
+    // is read while a clone per task is held here, not once the tasks run,
+    // so the counts do not depend on how far the tasks got
+    let shared: Arc<str> = Arc::from(text);
+    let held: Vec<Arc<str>> = workloads.iter().map(|_| Arc::clone(&shared)).collect();
+    let arc_allocation = raw::arc_str_allocation(&shared);
+    drop(held);
+    let (batch, shared_allocs) = track_if_installed(|| {
+        spawn_workloads(
+            workloads
+                .iter()
+                .map(|&workload| (Arc::clone(&shared), workload)),
+        )
+    });
+    let shared_tasks = join_workloads(&workloads, batch).await;
+    drop(echo);
+
+    if let (Some(cloned), Some(shared)) = (cloned_allocs, shared_allocs) {
+        info!(
+            cloned = cloned.allocs,
+            shared = shared.allocs,
+            "Sharing saved {} allocations",
+            cloned.allocs.saturating_sub(shared.allocs)
+        );
+    }
+
+    SharedReport {
+        rc: info_rc,
+        rc_allocation,
+        arc_allocation,
+        approaches: vec![
+            SharingApproach {
+                name: "String clone per task",
+                allocations: cloned_allocs,
+                tasks: cloned_tasks,
+            },
+            SharingApproach {
+                name: "Arc<str> shared by all tasks",
+                allocations: shared_allocs,
+                tasks: shared_tasks,
+            },
+        ],
+    }
+}
+
+/// Demonstrates string transformations with timing
+#[tracing::instrument(skip(inputs), fields(repeat = inputs.repeat))]
+pub async fn transformations(inputs: &DemoInputs) -> TransformationsReport {
+    info!("Performing various string transformations...");
+
+    let mut manipulator = StringManipulator::new();
+
+    // Reverse
+    let reverse_input = inputs.text_or("Hello, World!");
+    let reverse = manipulator.reverse(reverse_input);
+    let reversed_bytes = ByteReport::new(&reverse.value, "Reversed bytes");
+
+    // Uppercase (demonstrates Unicode case mapping)
+    let upper_input = inputs.text_or("Straße"); // German street
+    let upper = manipulator.to_upper(upper_input);
+
+    // Repeat
+    let repeat_pattern = inputs.text_or("Rust ");
+    let repeat_count = inputs.repeat;
+    let repeat = manipulator.repeat(repeat_pattern, repeat_count);
+
+    // Interleave
+    let interleave_inputs = (inputs.text_or("RUST"), inputs.other_or("rust"));
+    let interleave = manipulator.interleave(interleave_inputs.0, interleave_inputs.1);
+
+    // A single sample is mostly noise - benchmark the same operations
+    let config = BenchConfig::default();
+    let benchmarks = [
+        Operation::Reverse(reverse_input),
+        Operation::ToUpper(upper_input),
+        Operation::Repeat(repeat_pattern, repeat_count),
+        Operation::Interleave(interleave_inputs.0, interleave_inputs.1),
+    ]
+    .into_iter()
+    .map(|operation| manipulator.benchmark(operation, &config))
+    .collect();
+
+    TransformationsReport {
+        reverse_input: reverse_input.to_string(),
+        reverse,
+        reversed_bytes,
+        upper_input: upper_input.to_string(),
+        upper,
+        repeat_pattern: repeat_pattern.to_string(),
+        repeat_count,
+        repeat,
+        interleave_inputs: (
+            interleave_inputs.0.to_string(),
+            interleave_inputs.1.to_string(),
+        ),
+        interleave,
+        benchmarks,
+        operations_count: manipulator.operations_count,
+    }
+}
+
+/// Demonstrates UTF-8 and Unicode handling
+#[tracing::instrument(skip(inputs))]
+pub async fn unicode(inputs: &DemoInputs) -> UnicodeReport {
+    info!("Exploring UTF-8 encoding...");
+
+    // ASCII - 1 byte per character
+    let ascii = "Rust";
+    // Multi-byte UTF-8
+    let emoji = "🦀🚀";
+    // Mixed
+    let mixed = "Rust 🦀";
+
+    // Characters built from several code points
+    let combining = "Cafe\u{301}";
+    let flag = "🇯🇵";
+    let family = "👨\u{200d}👩\u{200d}👧";
+
+    warn!("mixed[5] would PANIC! Use .chars().nth(n) instead");
+
+    // Reversing by char breaks all of them; by grapheme cluster keeps them
+    let mut manipulator = StringManipulator::new();
+    let cluster_input = match &inputs.text {
+        Some(text) => text.clone(),
+        None => format!("{} {} {}", combining, flag, family),
+    };
+    let char_reverse = manipulator.reverse(&cluster_input);
+    let grapheme_reverse = manipulator.reverse_graphemes(&cluster_input);
+
+    let interleave_inputs = (inputs.text_or("e\u{301}e\u{301}"), inputs.other_or("🇫🇷🇩🇪"));
+    let char_interleave = manipulator.interleave(interleave_inputs.0, interleave_inputs.1);
+    let grapheme_interleave =
+        manipulator.interleave_graphemes(interleave_inputs.0, interleave_inputs.1);
+
+    UnicodeReport {
+        samples: vec![
+            ByteReport::new(ascii, "ASCII string (1 byte/char)"),
+            ByteReport::new(emoji, "Emoji (4 bytes/char)"),
+            ByteReport::new(mixed, "Mixed ASCII + Emoji"),
+            ByteReport::new(combining, "Combining accent (e + U+0301)"),
+            ByteReport::new(flag, "Flag (two regional indicators)"),
+            ByteReport::new(family, "Family (emoji joined by ZWJ)"),
+        ],
+        mixed: inputs.text_or(mixed).to_string(),
+        cluster_input,
+        char_reverse,
+        grapheme_reverse,
+        interleave_inputs: (
+            interleave_inputs.0.to_string(),
+            interleave_inputs.1.to_string(),
+        ),
+        char_interleave,
+        grapheme_interleave,
+    }
+}
+
+/// Demonstrates Unicode normalization and string equality
+#[tracing::instrument]
+pub async fn normalization() -> NormalizationReport {
+    info!("Comparing strings that look identical...");
+
+    let composed = "caf\u{e9}";
+    let decomposed = "cafe\u{301}";
+    let raw_equal = composed == decomposed;
+    if !raw_equal {
+        warn!("'{}' != '{}' until normalized", composed, decomposed);
+    }
+
+    let mut manipulator = StringManipulator::new();
+    let mut normalize_pair = |left: &'static str, right: &'static str| -> Vec<NormalizedPair> {
+        NormalizationForm::ALL
+            .into_iter()
+            .map(|form| NormalizedPair {
+                form,
+                quick_checks: (
+                    normalize::quick_check(left, form),
+                    normalize::quick_check(right, form),
+                ),
+                left: manipulator.normalize(left, form),
+                right: manipulator.normalize(right, form),
+            })
+            .collect()
+    };
+
+    let canonical = normalize_pair(composed, decomposed);
+
+    // Canonical forms keep the ligature; only compatibility forms fold it
+    let compatibility_inputs = ("\u{fb01}le", "file");
+    let compatibility = normalize_pair(compatibility_inputs.0, compatibility_inputs.1);
+
+    // The quick check makes already-normalized input nearly free
+    let config = BenchConfig::default();
+    let benchmarks = [
+        Operation::Normalize(composed, NormalizationForm::Nfc),
+        Operation::Normalize(decomposed, NormalizationForm::Nfc),
+    ]
+    .into_iter()
+    .map(|operation| manipulator.benchmark(operation, &config))
+    .collect();
+
+    NormalizationReport {
+        inputs: (composed.to_string(), decomposed.to_string()),
+        composed: ByteReport::new(composed, "Precomposed é (U+00E9)"),
+        decomposed: ByteReport::new(decomposed, "Decomposed é (e + U+0301)"),
+        raw_equal,
+        canonical,
+        compatibility_inputs: (
+            compatibility_inputs.0.to_string(),
+            compatibility_inputs.1.to_string(),
+        ),
+        compatibility,
+        benchmarks,
+        operations_count: manipulator.operations_count,
+    }
+}
+
+/// Demonstrates full Unicode case mapping
+#[tracing::instrument(skip(inputs))]
+pub async fn case_mapping(inputs: &DemoInputs) -> CaseMappingReport {
+    info!("Exploring case mapping beyond to_uppercase...");
+
+    let mut manipulator = StringManipulator::new();
+    let mut samples: Vec<CaseSample> = [
+        (
+            "Case folding for comparison",
+            "Straße",
+            CaseMapping::Fold,
+            CaseLocale::Root,
+        ),
+        (
+            "Final sigma depends on context",
+            "ΟΔΥΣΣΕΥΣ",
+            CaseMapping::Lower,
+            CaseLocale::Root,
+        ),
+        (
+            "Titlecase per word, not uppercase",
+            "ǆemal's ﬁsh-and-chips, 3.14 ΟΔΟΣ",
+            CaseMapping::Title,
+            CaseLocale::Root,
+        ),
+        (
+            "Root lowercase keeps the dot of İ",
+            "İSTANBUL",
+            CaseMapping::Lower,
+            CaseLocale::Root,
+        ),
+        (
+            "Turkish lowercase: İ -> i, I -> ı",
+            "İSTANBUL",
+            CaseMapping::Lower,
+            CaseLocale::Turkic,
+        ),
+        (
+            "Turkish uppercase: i -> İ",
+            "istanbul",
+            CaseMapping::Upper,
+            CaseLocale::Turkic,
+        ),
+        (
+            "Turkish titlecase",
+            "izmir ırmak",
+            CaseMapping::Title,
+            CaseLocale::Turkic,
+        ),
+        (
+            "Lithuanian lowercase keeps the dot under accents",
+            "ĨÌ",
+            CaseMapping::Lower,
+            CaseLocale::Lithuanian,
+        ),
+        (
+            "Lithuanian uppercase drops it again",
+            "i\u{307}\u{303}",
+            CaseMapping::Upper,
+            CaseLocale::Lithuanian,
+        ),
+    ]
+    .into_iter()
+    .map(|(description, input, mapping, locale)| CaseSample {
+        description,
+        input: input.to_string(),
+        mapping,
+        locale,
+        result: manipulator.case_map(input, mapping, locale),
+    })
+    .collect();
+
+    // The sample text through every mapping
+    if let Some(text) = &inputs.text {
+        for mapping in [
+            CaseMapping::Lower,
+            CaseMapping::Upper,
+            CaseMapping::Title,
+            CaseMapping::Fold,
+        ] {
+            samples.push(CaseSample {
+                description: "Your text",
+                input: text.clone(),
+                mapping,
+                locale: CaseLocale::Root,
+                result: manipulator.case_map(text, mapping, CaseLocale::Root),
+            });
+        }
+    }
+
+    let comparisons = [
+        ("Straße", "STRASSE"),
+        ("\u{fb01}le", "FILE"),
+        ("Cafe\u{301}", "CAFÉ"),
+        ("ǅemal", "ǄEMAL"),
+    ]
+    .into_iter()
+    .map(|(left, right)| CaselessComparison {
+        left: left.to_string(),
+        right: right.to_string(),
+        lowercase_equal: left.to_lowercase() == right.to_lowercase(),
+        caseless_equal: case::eq_caseless(left, right),
+    })
+    .collect();
+
+    CaseMappingReport {
+        samples,
+        comparisons,
+        operations_count: manipulator.operations_count,
+    }
+}
+
+/// Records how capacity grows push by push, and what the explicit
+/// capacity methods do
+#[tracing::instrument(skip(inputs), fields(target = inputs.growth_target))]
+pub async fn capacity_growth(inputs: &DemoInputs) -> GrowthReport {
+    info!("Recording capacity growth curves...");
+
+    let curves: Vec<GrowthCurve> = inputs
+        .chunks
+        .iter()
+        .map(|&chunk| growth::push_curve(inputs.growth_target, chunk))
+        .collect();
+
+    for curve in &curves {
+        info!(
+            chunk = curve.chunk,
+            reallocations = curve.events.len(),
+            moved = curve.moves(),
+            "Pushed {} bytes in {} pushes",
+            curve.target,
+            curve.pushes
+        );
+        if !curve.matches_rule() {
+            warn!(
+                chunk = curve.chunk,
+                "Growth deviated from the amortized rule"
+            );
+        }
+    }
+
+    let steps = growth::capacity_steps(&[
+        CapacityOp::PushStr(5),
+        CapacityOp::Reserve(10),
+        CapacityOp::Reserve(10),
+        CapacityOp::ReserveExact(20),
+        CapacityOp::PushStr(30),
+        CapacityOp::ShrinkTo(100),
+        CapacityOp::ShrinkTo(40),
+        CapacityOp::ShrinkToFit,
+    ]);
+
+    GrowthReport { curves, steps }
+}
+
+/// Large capacities the size class demo requests after every size in
+/// `1..=256`
+const SIZE_CLASS_REQUESTS: [usize; 4] = [1000, 4096, 100_000, 1 << 20];
+
+/// Shows how the allocator rounds requested capacities up to its size
+/// classes
+#[tracing::instrument(skip(inputs))]
+pub async fn size_classes(inputs: &DemoInputs) -> SizeClassReport {
+    info!("Asking the allocator for its real block sizes...");
+
+    let classes = allocator::size_classes((1..=256).chain(SIZE_CLASS_REQUESTS));
+    if classes.is_empty() {
+        warn!("The allocator's block sizes are not available on this platform");
+    }
+
+    let text = inputs.text_or("Rust");
+    let layouts = [text.len(), 25, 100]
+        .into_iter()
+        .map(|capacity| {
+            let mut s = String::with_capacity(capacity);
+            s.push_str(text);
+            s.inspect(&format!("String::with_capacity({})", capacity))
+        })
+        .collect();
+
+    SizeClassReport { classes, layouts }
+}
+
+/// Inspects `text` held in each of the common string types
+///
+/// The `String` is created with `capacity` (at least the text's length)
+/// when given, to show spare capacity.
+#[tracing::instrument(skip(text), fields(text_len = text.len()))]
+pub fn inspection(text: &str, capacity: Option<usize>) -> InspectionReport {
+    let mut owned = String::with_capacity(capacity.unwrap_or(0));
+    owned.push_str(text);
+    let boxed: Box<str> = text.into();
+    let rc: Rc<str> = Rc::from(text);
+    let arc: Arc<str> = Arc::from(text);
+    let cow: Cow<str> = Cow::Borrowed(text);
+
+    InspectionReport {
+        layouts: vec![
+            owned.inspect("String"),
+            text.inspect("&str"),
+            boxed.inspect("Box<str>"),
+            rc.inspect("Rc<str>"),
+            arc.inspect("Arc<str>"),
+            cow.inspect("Cow<str>"),
+        ],
+        bytes: ByteReport::new(text, "Byte representation"),
+        headers: vec![
+            raw::string_header(&owned),
+            raw::str_header(&text),
+            raw::boxed_str_header(&boxed),
+        ],
+        heap: HeapDump::of_string(&owned),
+    }
+}
+
+/// Applies a single operation, timed once or benchmarked with `bench`
+#[tracing::instrument(skip(operation, bench), fields(operation = operation.name()))]
+pub fn transform(operation: Operation<'_>, bench: Option<&BenchConfig>) -> TransformReport {
+    let mut manipulator = StringManipulator::new();
+    let input = operation.input().to_string();
+
+    let result = match bench {
+        Some(config) => manipulator.benchmark(operation, config),
+        None => manipulator.run(operation),
+    };
+
+    TransformReport {
+        input: ByteReport::new(&input, "Input"),
+        output: ByteReport::new(&result.value, "Output"),
+        layout: LayoutComparison::between(
+            &input.inspect("Input String"),
+            &result.value.inspect("Output String"),
+            operation.name(),
+        ),
+        result,
+    }
+}
@@ -0,0 +1,228 @@
+//! # Grapheme Cluster Segmentation
+//!
+//! A `char` is a Unicode scalar value, not a "character" as a reader sees
+//! it. "é" may be `e` + U+0301 COMBINING ACUTE ACCENT, 🇯🇵 is two regional
+//! indicator symbols, and 👨‍👩‍👧 is three emoji glued together with zero-width
+//! joiners. Reversing such strings `char` by `char` tears them apart.
+//!
+//! This module implements the extended grapheme cluster rules of
+//! [UAX #29](https://www.unicode.org/reports/tr29/) (Unicode 17.0.0), so the
+//! lab can split text into the units a user perceives as characters.
+
+mod tables;
+
+use std::cmp::Ordering;
+
+/// The `Grapheme_Cluster_Break` property of a code point, extended with the
+/// two other properties the rules need (`Extended_Pictographic` and
+/// `Indic_Conjunct_Break=Consonant`, both of which only occur on `Other`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphemeCat {
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+    ExtendedPictographic,
+    InCBConsonant,
+    Other,
+}
+
+use GraphemeCat::*;
+
+/// First and last precomposed Hangul syllables
+const HANGUL_FIRST: u32 = 0xAC00;
+const HANGUL_LAST: u32 = 0xD7A3;
+/// Number of trailing consonants (plus "none") per Hangul vowel
+const HANGUL_T_COUNT: u32 = 28;
+
+/// Orders the range `lo..=hi` relative to `c`, for binary searching tables
+pub(crate) fn range_cmp(c: char, lo: char, hi: char) -> Ordering {
+    if c < lo {
+        Ordering::Greater
+    } else if c > hi {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Looks up the break category of a code point
+pub fn category(c: char) -> GraphemeCat {
+    let cp = c as u32;
+    if (HANGUL_FIRST..=HANGUL_LAST).contains(&cp) {
+        // Syllables without a trailing consonant are LV, the rest LVT
+        return if (cp - HANGUL_FIRST).is_multiple_of(HANGUL_T_COUNT) {
+            LV
+        } else {
+            LVT
+        };
+    }
+
+    tables::GRAPHEME_CAT
+        .binary_search_by(|&(lo, hi, _)| range_cmp(c, lo, hi))
+        .map_or(Other, |i| tables::GRAPHEME_CAT[i].2)
+}
+
+/// The `Indic_Conjunct_Break` property, needed by rule GB9c
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InCB {
+    Consonant,
+    Linker,
+    Extend,
+    None,
+}
+
+fn incb(c: char, cat: GraphemeCat) -> InCB {
+    if cat == InCBConsonant {
+        InCB::Consonant
+    } else if tables::INCB_LINKER.binary_search(&c).is_ok() {
+        InCB::Linker
+    } else if tables::INCB_EXTEND
+        .binary_search_by(|&(lo, hi)| range_cmp(c, lo, hi))
+        .is_ok()
+    {
+        InCB::Extend
+    } else {
+        InCB::None
+    }
+}
+
+/// What the rules need to remember about the cluster so far
+#[derive(Debug, Clone, Copy)]
+struct BreakState {
+    prev: GraphemeCat,
+    /// GB11: seen `ExtPict Extend*`, and whether a ZWJ followed
+    emoji: EmojiState,
+    /// GB9c: seen `Consonant [Extend Linker]*`, and whether a Linker was in it
+    conjunct: ConjunctState,
+    /// GB12/13: number of consecutive regional indicators
+    regional_indicators: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmojiState {
+    None,
+    Pictographic,
+    PictographicZwj,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConjunctState {
+    None,
+    Consonant,
+    ConsonantLinker,
+}
+
+impl BreakState {
+    fn new(first: char) -> Self {
+        let cat = category(first);
+        let mut state = Self {
+            prev: Other,
+            emoji: EmojiState::None,
+            conjunct: ConjunctState::None,
+            regional_indicators: 0,
+        };
+        state.advance(first, cat);
+        state
+    }
+
+    /// Records `c` as the latest code point of the cluster
+    fn advance(&mut self, c: char, cat: GraphemeCat) {
+        self.emoji = match (cat, self.emoji) {
+            (ExtendedPictographic, _) => EmojiState::Pictographic,
+            (Extend, EmojiState::Pictographic) => EmojiState::Pictographic,
+            (ZWJ, EmojiState::Pictographic) => EmojiState::PictographicZwj,
+            _ => EmojiState::None,
+        };
+
+        self.conjunct = match (incb(c, cat), self.conjunct) {
+            (InCB::Consonant, _) => ConjunctState::Consonant,
+            (_, ConjunctState::None) => ConjunctState::None,
+            (InCB::Linker, _) => ConjunctState::ConsonantLinker,
+            (InCB::Extend, state) => state,
+            (InCB::None, _) => ConjunctState::None,
+        };
+
+        self.regional_indicators = if cat == RegionalIndicator {
+            self.regional_indicators + 1
+        } else {
+            0
+        };
+
+        self.prev = cat;
+    }
+
+    /// Whether there is a grapheme cluster boundary before `next`
+    fn is_boundary(&self, next: char, next_cat: GraphemeCat) -> bool {
+        match (self.prev, next_cat) {
+            // GB3
+            (CR, LF) => false,
+            // GB4, GB5
+            (Control | CR | LF, _) | (_, Control | CR | LF) => true,
+            // GB6, GB7, GB8: Hangul syllable sequences
+            (L, L | V | LV | LVT) | (LV | V, V | T) | (LVT | T, T) => false,
+            // GB9, GB9a, GB9b
+            (_, Extend | ZWJ | SpacingMark) | (Prepend, _) => false,
+            // GB9c: Indic conjuncts
+            _ if self.conjunct == ConjunctState::ConsonantLinker
+                && incb(next, next_cat) == InCB::Consonant =>
+            {
+                false
+            }
+            // GB11: emoji ZWJ sequences
+            (ZWJ, ExtendedPictographic) if self.emoji == EmojiState::PictographicZwj => false,
+            // GB12, GB13: flags are pairs of regional indicators
+            (RegionalIndicator, RegionalIndicator) => self.regional_indicators.is_multiple_of(2),
+            // GB999
+            _ => true,
+        }
+    }
+}
+
+/// Iterator over the extended grapheme clusters of a string
+#[derive(Debug, Clone)]
+pub struct Graphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut state = BreakState::new(first);
+
+        let end = chars
+            .find(|&(_, c)| {
+                let cat = category(c);
+                let boundary = state.is_boundary(c, cat);
+                state.advance(c, cat);
+                boundary
+            })
+            .map_or(self.rest.len(), |(i, _)| i);
+
+        let (cluster, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(cluster)
+    }
+}
+
+/// Splits `s` into extended grapheme clusters
+pub fn graphemes(s: &str) -> Graphemes<'_> {
+    Graphemes { rest: s }
+}
+
+/// Number of extended grapheme clusters in `s`
+pub fn grapheme_count(s: &str) -> usize {
+    graphemes(s).count()
+}
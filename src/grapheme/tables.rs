@@ -0,0 +1,578 @@
+//! Unicode 17.0.0 property tables for extended grapheme cluster segmentation
+//!
+//! Generated by `tools/unicode_tables.py` from the Unicode Character Database:
+//! `GraphemeBreakProperty.txt`, the `Extended_Pictographic` property from
+//! `emoji-data.txt`, and the `Indic_Conjunct_Break` property from
+//! `DerivedCoreProperties.txt`. Do not edit by hand.
+//!
+//! Hangul syllables (`LV`/`LVT`) are omitted: they are computed
+//! arithmetically in [`super::category`].
+
+use super::GraphemeCat::{self, *};
+
+/// Sorted, non-overlapping ranges of code points and their break category.
+/// Code points not listed are [`GraphemeCat::Other`].
+#[rustfmt::skip]
+pub(super) const GRAPHEME_CAT: &[(char, char, GraphemeCat)] = &[
+    ('\u{0}', '\u{9}', Control), ('\u{a}', '\u{a}', LF), ('\u{b}', '\u{c}', Control),
+    ('\u{d}', '\u{d}', CR), ('\u{e}', '\u{1f}', Control), ('\u{7f}', '\u{9f}', Control),
+    ('\u{a9}', '\u{a9}', ExtendedPictographic), ('\u{ad}', '\u{ad}', Control),
+    ('\u{ae}', '\u{ae}', ExtendedPictographic), ('\u{300}', '\u{36f}', Extend),
+    ('\u{483}', '\u{489}', Extend), ('\u{591}', '\u{5bd}', Extend), ('\u{5bf}', '\u{5bf}', Extend),
+    ('\u{5c1}', '\u{5c2}', Extend), ('\u{5c4}', '\u{5c5}', Extend), ('\u{5c7}', '\u{5c7}', Extend),
+    ('\u{600}', '\u{605}', Prepend), ('\u{610}', '\u{61a}', Extend),
+    ('\u{61c}', '\u{61c}', Control), ('\u{64b}', '\u{65f}', Extend), ('\u{670}', '\u{670}', Extend),
+    ('\u{6d6}', '\u{6dc}', Extend), ('\u{6dd}', '\u{6dd}', Prepend), ('\u{6df}', '\u{6e4}', Extend),
+    ('\u{6e7}', '\u{6e8}', Extend), ('\u{6ea}', '\u{6ed}', Extend), ('\u{70f}', '\u{70f}', Prepend),
+    ('\u{711}', '\u{711}', Extend), ('\u{730}', '\u{74a}', Extend), ('\u{7a6}', '\u{7b0}', Extend),
+    ('\u{7eb}', '\u{7f3}', Extend), ('\u{7fd}', '\u{7fd}', Extend), ('\u{816}', '\u{819}', Extend),
+    ('\u{81b}', '\u{823}', Extend), ('\u{825}', '\u{827}', Extend), ('\u{829}', '\u{82d}', Extend),
+    ('\u{859}', '\u{85b}', Extend), ('\u{890}', '\u{891}', Prepend), ('\u{897}', '\u{89f}', Extend),
+    ('\u{8ca}', '\u{8e1}', Extend), ('\u{8e2}', '\u{8e2}', Prepend), ('\u{8e3}', '\u{902}', Extend),
+    ('\u{903}', '\u{903}', SpacingMark), ('\u{915}', '\u{939}', InCBConsonant),
+    ('\u{93a}', '\u{93a}', Extend), ('\u{93b}', '\u{93b}', SpacingMark),
+    ('\u{93c}', '\u{93c}', Extend), ('\u{93e}', '\u{940}', SpacingMark),
+    ('\u{941}', '\u{948}', Extend), ('\u{949}', '\u{94c}', SpacingMark),
+    ('\u{94d}', '\u{94d}', Extend), ('\u{94e}', '\u{94f}', SpacingMark),
+    ('\u{951}', '\u{957}', Extend), ('\u{958}', '\u{95f}', InCBConsonant),
+    ('\u{962}', '\u{963}', Extend), ('\u{978}', '\u{97f}', InCBConsonant),
+    ('\u{981}', '\u{981}', Extend), ('\u{982}', '\u{983}', SpacingMark),
+    ('\u{995}', '\u{9a8}', InCBConsonant), ('\u{9aa}', '\u{9b0}', InCBConsonant),
+    ('\u{9b2}', '\u{9b2}', InCBConsonant), ('\u{9b6}', '\u{9b9}', InCBConsonant),
+    ('\u{9bc}', '\u{9bc}', Extend), ('\u{9be}', '\u{9be}', Extend),
+    ('\u{9bf}', '\u{9c0}', SpacingMark), ('\u{9c1}', '\u{9c4}', Extend),
+    ('\u{9c7}', '\u{9c8}', SpacingMark), ('\u{9cb}', '\u{9cc}', SpacingMark),
+    ('\u{9cd}', '\u{9cd}', Extend), ('\u{9d7}', '\u{9d7}', Extend),
+    ('\u{9dc}', '\u{9dd}', InCBConsonant), ('\u{9df}', '\u{9df}', InCBConsonant),
+    ('\u{9e2}', '\u{9e3}', Extend), ('\u{9f0}', '\u{9f1}', InCBConsonant),
+    ('\u{9fe}', '\u{9fe}', Extend), ('\u{a01}', '\u{a02}', Extend),
+    ('\u{a03}', '\u{a03}', SpacingMark), ('\u{a3c}', '\u{a3c}', Extend),
+    ('\u{a3e}', '\u{a40}', SpacingMark), ('\u{a41}', '\u{a42}', Extend),
+    ('\u{a47}', '\u{a48}', Extend), ('\u{a4b}', '\u{a4d}', Extend), ('\u{a51}', '\u{a51}', Extend),
+    ('\u{a70}', '\u{a71}', Extend), ('\u{a75}', '\u{a75}', Extend), ('\u{a81}', '\u{a82}', Extend),
+    ('\u{a83}', '\u{a83}', SpacingMark), ('\u{a95}', '\u{aa8}', InCBConsonant),
+    ('\u{aaa}', '\u{ab0}', InCBConsonant), ('\u{ab2}', '\u{ab3}', InCBConsonant),
+    ('\u{ab5}', '\u{ab9}', InCBConsonant), ('\u{abc}', '\u{abc}', Extend),
+    ('\u{abe}', '\u{ac0}', SpacingMark), ('\u{ac1}', '\u{ac5}', Extend),
+    ('\u{ac7}', '\u{ac8}', Extend), ('\u{ac9}', '\u{ac9}', SpacingMark),
+    ('\u{acb}', '\u{acc}', SpacingMark), ('\u{acd}', '\u{acd}', Extend),
+    ('\u{ae2}', '\u{ae3}', Extend), ('\u{af9}', '\u{af9}', InCBConsonant),
+    ('\u{afa}', '\u{aff}', Extend), ('\u{b01}', '\u{b01}', Extend),
+    ('\u{b02}', '\u{b03}', SpacingMark), ('\u{b15}', '\u{b28}', InCBConsonant),
+    ('\u{b2a}', '\u{b30}', InCBConsonant), ('\u{b32}', '\u{b33}', InCBConsonant),
+    ('\u{b35}', '\u{b39}', InCBConsonant), ('\u{b3c}', '\u{b3c}', Extend),
+    ('\u{b3e}', '\u{b3f}', Extend), ('\u{b40}', '\u{b40}', SpacingMark),
+    ('\u{b41}', '\u{b44}', Extend), ('\u{b47}', '\u{b48}', SpacingMark),
+    ('\u{b4b}', '\u{b4c}', SpacingMark), ('\u{b4d}', '\u{b4d}', Extend),
+    ('\u{b55}', '\u{b57}', Extend), ('\u{b5c}', '\u{b5d}', InCBConsonant),
+    ('\u{b5f}', '\u{b5f}', InCBConsonant), ('\u{b62}', '\u{b63}', Extend),
+    ('\u{b71}', '\u{b71}', InCBConsonant), ('\u{b82}', '\u{b82}', Extend),
+    ('\u{bbe}', '\u{bbe}', Extend), ('\u{bbf}', '\u{bbf}', SpacingMark),
+    ('\u{bc0}', '\u{bc0}', Extend), ('\u{bc1}', '\u{bc2}', SpacingMark),
+    ('\u{bc6}', '\u{bc8}', SpacingMark), ('\u{bca}', '\u{bcc}', SpacingMark),
+    ('\u{bcd}', '\u{bcd}', Extend), ('\u{bd7}', '\u{bd7}', Extend), ('\u{c00}', '\u{c00}', Extend),
+    ('\u{c01}', '\u{c03}', SpacingMark), ('\u{c04}', '\u{c04}', Extend),
+    ('\u{c15}', '\u{c28}', InCBConsonant), ('\u{c2a}', '\u{c39}', InCBConsonant),
+    ('\u{c3c}', '\u{c3c}', Extend), ('\u{c3e}', '\u{c40}', Extend),
+    ('\u{c41}', '\u{c44}', SpacingMark), ('\u{c46}', '\u{c48}', Extend),
+    ('\u{c4a}', '\u{c4d}', Extend), ('\u{c55}', '\u{c56}', Extend),
+    ('\u{c58}', '\u{c5a}', InCBConsonant), ('\u{c62}', '\u{c63}', Extend),
+    ('\u{c81}', '\u{c81}', Extend), ('\u{c82}', '\u{c83}', SpacingMark),
+    ('\u{cbc}', '\u{cbc}', Extend), ('\u{cbe}', '\u{cbe}', SpacingMark),
+    ('\u{cbf}', '\u{cc0}', Extend), ('\u{cc1}', '\u{cc1}', SpacingMark),
+    ('\u{cc2}', '\u{cc2}', Extend), ('\u{cc3}', '\u{cc4}', SpacingMark),
+    ('\u{cc6}', '\u{cc8}', Extend), ('\u{cca}', '\u{ccd}', Extend), ('\u{cd5}', '\u{cd6}', Extend),
+    ('\u{ce2}', '\u{ce3}', Extend), ('\u{cf3}', '\u{cf3}', SpacingMark),
+    ('\u{d00}', '\u{d01}', Extend), ('\u{d02}', '\u{d03}', SpacingMark),
+    ('\u{d15}', '\u{d3a}', InCBConsonant), ('\u{d3b}', '\u{d3c}', Extend),
+    ('\u{d3e}', '\u{d3e}', Extend), ('\u{d3f}', '\u{d40}', SpacingMark),
+    ('\u{d41}', '\u{d44}', Extend), ('\u{d46}', '\u{d48}', SpacingMark),
+    ('\u{d4a}', '\u{d4c}', SpacingMark), ('\u{d4d}', '\u{d4d}', Extend),
+    ('\u{d4e}', '\u{d4e}', Prepend), ('\u{d57}', '\u{d57}', Extend), ('\u{d62}', '\u{d63}', Extend),
+    ('\u{d81}', '\u{d81}', Extend), ('\u{d82}', '\u{d83}', SpacingMark),
+    ('\u{dca}', '\u{dca}', Extend), ('\u{dcf}', '\u{dcf}', Extend),
+    ('\u{dd0}', '\u{dd1}', SpacingMark), ('\u{dd2}', '\u{dd4}', Extend),
+    ('\u{dd6}', '\u{dd6}', Extend), ('\u{dd8}', '\u{dde}', SpacingMark),
+    ('\u{ddf}', '\u{ddf}', Extend), ('\u{df2}', '\u{df3}', SpacingMark),
+    ('\u{e31}', '\u{e31}', Extend), ('\u{e33}', '\u{e33}', SpacingMark),
+    ('\u{e34}', '\u{e3a}', Extend), ('\u{e47}', '\u{e4e}', Extend), ('\u{eb1}', '\u{eb1}', Extend),
+    ('\u{eb3}', '\u{eb3}', SpacingMark), ('\u{eb4}', '\u{ebc}', Extend),
+    ('\u{ec8}', '\u{ece}', Extend), ('\u{f18}', '\u{f19}', Extend), ('\u{f35}', '\u{f35}', Extend),
+    ('\u{f37}', '\u{f37}', Extend), ('\u{f39}', '\u{f39}', Extend),
+    ('\u{f3e}', '\u{f3f}', SpacingMark), ('\u{f71}', '\u{f7e}', Extend),
+    ('\u{f7f}', '\u{f7f}', SpacingMark), ('\u{f80}', '\u{f84}', Extend),
+    ('\u{f86}', '\u{f87}', Extend), ('\u{f8d}', '\u{f97}', Extend), ('\u{f99}', '\u{fbc}', Extend),
+    ('\u{fc6}', '\u{fc6}', Extend), ('\u{1000}', '\u{102a}', InCBConsonant),
+    ('\u{102d}', '\u{1030}', Extend), ('\u{1031}', '\u{1031}', SpacingMark),
+    ('\u{1032}', '\u{1037}', Extend), ('\u{1039}', '\u{103a}', Extend),
+    ('\u{103b}', '\u{103c}', SpacingMark), ('\u{103d}', '\u{103e}', Extend),
+    ('\u{103f}', '\u{103f}', InCBConsonant), ('\u{1050}', '\u{1055}', InCBConsonant),
+    ('\u{1056}', '\u{1057}', SpacingMark), ('\u{1058}', '\u{1059}', Extend),
+    ('\u{105a}', '\u{105d}', InCBConsonant), ('\u{105e}', '\u{1060}', Extend),
+    ('\u{1061}', '\u{1061}', InCBConsonant), ('\u{1065}', '\u{1066}', InCBConsonant),
+    ('\u{106e}', '\u{1070}', InCBConsonant), ('\u{1071}', '\u{1074}', Extend),
+    ('\u{1075}', '\u{1081}', InCBConsonant), ('\u{1082}', '\u{1082}', Extend),
+    ('\u{1084}', '\u{1084}', SpacingMark), ('\u{1085}', '\u{1086}', Extend),
+    ('\u{108d}', '\u{108d}', Extend), ('\u{108e}', '\u{108e}', InCBConsonant),
+    ('\u{109d}', '\u{109d}', Extend), ('\u{1100}', '\u{115f}', L), ('\u{1160}', '\u{11a7}', V),
+    ('\u{11a8}', '\u{11ff}', T), ('\u{135d}', '\u{135f}', Extend), ('\u{1712}', '\u{1715}', Extend),
+    ('\u{1732}', '\u{1734}', Extend), ('\u{1752}', '\u{1753}', Extend),
+    ('\u{1772}', '\u{1773}', Extend), ('\u{1780}', '\u{17b3}', InCBConsonant),
+    ('\u{17b4}', '\u{17b5}', Extend), ('\u{17b6}', '\u{17b6}', SpacingMark),
+    ('\u{17b7}', '\u{17bd}', Extend), ('\u{17be}', '\u{17c5}', SpacingMark),
+    ('\u{17c6}', '\u{17c6}', Extend), ('\u{17c7}', '\u{17c8}', SpacingMark),
+    ('\u{17c9}', '\u{17d3}', Extend), ('\u{17dd}', '\u{17dd}', Extend),
+    ('\u{180b}', '\u{180d}', Extend), ('\u{180e}', '\u{180e}', Control),
+    ('\u{180f}', '\u{180f}', Extend), ('\u{1885}', '\u{1886}', Extend),
+    ('\u{18a9}', '\u{18a9}', Extend), ('\u{1920}', '\u{1922}', Extend),
+    ('\u{1923}', '\u{1926}', SpacingMark), ('\u{1927}', '\u{1928}', Extend),
+    ('\u{1929}', '\u{192b}', SpacingMark), ('\u{1930}', '\u{1931}', SpacingMark),
+    ('\u{1932}', '\u{1932}', Extend), ('\u{1933}', '\u{1938}', SpacingMark),
+    ('\u{1939}', '\u{193b}', Extend), ('\u{1a17}', '\u{1a18}', Extend),
+    ('\u{1a19}', '\u{1a1a}', SpacingMark), ('\u{1a1b}', '\u{1a1b}', Extend),
+    ('\u{1a20}', '\u{1a54}', InCBConsonant), ('\u{1a55}', '\u{1a55}', SpacingMark),
+    ('\u{1a56}', '\u{1a56}', Extend), ('\u{1a57}', '\u{1a57}', SpacingMark),
+    ('\u{1a58}', '\u{1a5e}', Extend), ('\u{1a60}', '\u{1a60}', Extend),
+    ('\u{1a62}', '\u{1a62}', Extend), ('\u{1a65}', '\u{1a6c}', Extend),
+    ('\u{1a6d}', '\u{1a72}', SpacingMark), ('\u{1a73}', '\u{1a7c}', Extend),
+    ('\u{1a7f}', '\u{1a7f}', Extend), ('\u{1ab0}', '\u{1add}', Extend),
+    ('\u{1ae0}', '\u{1aeb}', Extend), ('\u{1b00}', '\u{1b03}', Extend),
+    ('\u{1b04}', '\u{1b04}', SpacingMark), ('\u{1b0b}', '\u{1b0c}', InCBConsonant),
+    ('\u{1b13}', '\u{1b33}', InCBConsonant), ('\u{1b34}', '\u{1b3d}', Extend),
+    ('\u{1b3e}', '\u{1b41}', SpacingMark), ('\u{1b42}', '\u{1b44}', Extend),
+    ('\u{1b45}', '\u{1b4c}', InCBConsonant), ('\u{1b6b}', '\u{1b73}', Extend),
+    ('\u{1b80}', '\u{1b81}', Extend), ('\u{1b82}', '\u{1b82}', SpacingMark),
+    ('\u{1b83}', '\u{1ba0}', InCBConsonant), ('\u{1ba1}', '\u{1ba1}', SpacingMark),
+    ('\u{1ba2}', '\u{1ba5}', Extend), ('\u{1ba6}', '\u{1ba7}', SpacingMark),
+    ('\u{1ba8}', '\u{1bad}', Extend), ('\u{1bae}', '\u{1baf}', InCBConsonant),
+    ('\u{1bbb}', '\u{1bbd}', InCBConsonant), ('\u{1be6}', '\u{1be6}', Extend),
+    ('\u{1be7}', '\u{1be7}', SpacingMark), ('\u{1be8}', '\u{1be9}', Extend),
+    ('\u{1bea}', '\u{1bec}', SpacingMark), ('\u{1bed}', '\u{1bed}', Extend),
+    ('\u{1bee}', '\u{1bee}', SpacingMark), ('\u{1bef}', '\u{1bf3}', Extend),
+    ('\u{1c24}', '\u{1c2b}', SpacingMark), ('\u{1c2c}', '\u{1c33}', Extend),
+    ('\u{1c34}', '\u{1c35}', SpacingMark), ('\u{1c36}', '\u{1c37}', Extend),
+    ('\u{1cd0}', '\u{1cd2}', Extend), ('\u{1cd4}', '\u{1ce0}', Extend),
+    ('\u{1ce1}', '\u{1ce1}', SpacingMark), ('\u{1ce2}', '\u{1ce8}', Extend),
+    ('\u{1ced}', '\u{1ced}', Extend), ('\u{1cf4}', '\u{1cf4}', Extend),
+    ('\u{1cf7}', '\u{1cf7}', SpacingMark), ('\u{1cf8}', '\u{1cf9}', Extend),
+    ('\u{1dc0}', '\u{1dff}', Extend), ('\u{200b}', '\u{200b}', Control),
+    ('\u{200c}', '\u{200c}', Extend), ('\u{200d}', '\u{200d}', ZWJ),
+    ('\u{200e}', '\u{200f}', Control), ('\u{2028}', '\u{202e}', Control),
+    ('\u{203c}', '\u{203c}', ExtendedPictographic), ('\u{2049}', '\u{2049}', ExtendedPictographic),
+    ('\u{2060}', '\u{206f}', Control), ('\u{20d0}', '\u{20f0}', Extend),
+    ('\u{2122}', '\u{2122}', ExtendedPictographic), ('\u{2139}', '\u{2139}', ExtendedPictographic),
+    ('\u{2194}', '\u{2199}', ExtendedPictographic), ('\u{21a9}', '\u{21aa}', ExtendedPictographic),
+    ('\u{231a}', '\u{231b}', ExtendedPictographic), ('\u{2328}', '\u{2328}', ExtendedPictographic),
+    ('\u{23cf}', '\u{23cf}', ExtendedPictographic), ('\u{23e9}', '\u{23f3}', ExtendedPictographic),
+    ('\u{23f8}', '\u{23fa}', ExtendedPictographic), ('\u{24c2}', '\u{24c2}', ExtendedPictographic),
+    ('\u{25aa}', '\u{25ab}', ExtendedPictographic), ('\u{25b6}', '\u{25b6}', ExtendedPictographic),
+    ('\u{25c0}', '\u{25c0}', ExtendedPictographic), ('\u{25fb}', '\u{25fe}', ExtendedPictographic),
+    ('\u{2600}', '\u{2604}', ExtendedPictographic), ('\u{260e}', '\u{260e}', ExtendedPictographic),
+    ('\u{2611}', '\u{2611}', ExtendedPictographic), ('\u{2614}', '\u{2615}', ExtendedPictographic),
+    ('\u{2618}', '\u{2618}', ExtendedPictographic), ('\u{261d}', '\u{261d}', ExtendedPictographic),
+    ('\u{2620}', '\u{2620}', ExtendedPictographic), ('\u{2622}', '\u{2623}', ExtendedPictographic),
+    ('\u{2626}', '\u{2626}', ExtendedPictographic), ('\u{262a}', '\u{262a}', ExtendedPictographic),
+    ('\u{262e}', '\u{262f}', ExtendedPictographic), ('\u{2638}', '\u{263a}', ExtendedPictographic),
+    ('\u{2640}', '\u{2640}', ExtendedPictographic), ('\u{2642}', '\u{2642}', ExtendedPictographic),
+    ('\u{2648}', '\u{2653}', ExtendedPictographic), ('\u{265f}', '\u{2660}', ExtendedPictographic),
+    ('\u{2663}', '\u{2663}', ExtendedPictographic), ('\u{2665}', '\u{2666}', ExtendedPictographic),
+    ('\u{2668}', '\u{2668}', ExtendedPictographic), ('\u{267b}', '\u{267b}', ExtendedPictographic),
+    ('\u{267e}', '\u{267f}', ExtendedPictographic), ('\u{2692}', '\u{2697}', ExtendedPictographic),
+    ('\u{2699}', '\u{2699}', ExtendedPictographic), ('\u{269b}', '\u{269c}', ExtendedPictographic),
+    ('\u{26a0}', '\u{26a1}', ExtendedPictographic), ('\u{26a7}', '\u{26a7}', ExtendedPictographic),
+    ('\u{26aa}', '\u{26ab}', ExtendedPictographic), ('\u{26b0}', '\u{26b1}', ExtendedPictographic),
+    ('\u{26bd}', '\u{26be}', ExtendedPictographic), ('\u{26c4}', '\u{26c5}', ExtendedPictographic),
+    ('\u{26c8}', '\u{26c8}', ExtendedPictographic), ('\u{26ce}', '\u{26cf}', ExtendedPictographic),
+    ('\u{26d1}', '\u{26d1}', ExtendedPictographic), ('\u{26d3}', '\u{26d4}', ExtendedPictographic),
+    ('\u{26e9}', '\u{26ea}', ExtendedPictographic), ('\u{26f0}', '\u{26f5}', ExtendedPictographic),
+    ('\u{26f7}', '\u{26fa}', ExtendedPictographic), ('\u{26fd}', '\u{26fd}', ExtendedPictographic),
+    ('\u{2702}', '\u{2702}', ExtendedPictographic), ('\u{2705}', '\u{2705}', ExtendedPictographic),
+    ('\u{2708}', '\u{270d}', ExtendedPictographic), ('\u{270f}', '\u{270f}', ExtendedPictographic),
+    ('\u{2712}', '\u{2712}', ExtendedPictographic), ('\u{2714}', '\u{2714}', ExtendedPictographic),
+    ('\u{2716}', '\u{2716}', ExtendedPictographic), ('\u{271d}', '\u{271d}', ExtendedPictographic),
+    ('\u{2721}', '\u{2721}', ExtendedPictographic), ('\u{2728}', '\u{2728}', ExtendedPictographic),
+    ('\u{2733}', '\u{2734}', ExtendedPictographic), ('\u{2744}', '\u{2744}', ExtendedPictographic),
+    ('\u{2747}', '\u{2747}', ExtendedPictographic), ('\u{274c}', '\u{274c}', ExtendedPictographic),
+    ('\u{274e}', '\u{274e}', ExtendedPictographic), ('\u{2753}', '\u{2755}', ExtendedPictographic),
+    ('\u{2757}', '\u{2757}', ExtendedPictographic), ('\u{2763}', '\u{2764}', ExtendedPictographic),
+    ('\u{2795}', '\u{2797}', ExtendedPictographic), ('\u{27a1}', '\u{27a1}', ExtendedPictographic),
+    ('\u{27b0}', '\u{27b0}', ExtendedPictographic), ('\u{27bf}', '\u{27bf}', ExtendedPictographic),
+    ('\u{2934}', '\u{2935}', ExtendedPictographic), ('\u{2b05}', '\u{2b07}', ExtendedPictographic),
+    ('\u{2b1b}', '\u{2b1c}', ExtendedPictographic), ('\u{2b50}', '\u{2b50}', ExtendedPictographic),
+    ('\u{2b55}', '\u{2b55}', ExtendedPictographic), ('\u{2cef}', '\u{2cf1}', Extend),
+    ('\u{2d7f}', '\u{2d7f}', Extend), ('\u{2de0}', '\u{2dff}', Extend),
+    ('\u{302a}', '\u{302f}', Extend), ('\u{3030}', '\u{3030}', ExtendedPictographic),
+    ('\u{303d}', '\u{303d}', ExtendedPictographic), ('\u{3099}', '\u{309a}', Extend),
+    ('\u{3297}', '\u{3297}', ExtendedPictographic), ('\u{3299}', '\u{3299}', ExtendedPictographic),
+    ('\u{a66f}', '\u{a672}', Extend), ('\u{a674}', '\u{a67d}', Extend),
+    ('\u{a69e}', '\u{a69f}', Extend), ('\u{a6f0}', '\u{a6f1}', Extend),
+    ('\u{a802}', '\u{a802}', Extend), ('\u{a806}', '\u{a806}', Extend),
+    ('\u{a80b}', '\u{a80b}', Extend), ('\u{a823}', '\u{a824}', SpacingMark),
+    ('\u{a825}', '\u{a826}', Extend), ('\u{a827}', '\u{a827}', SpacingMark),
+    ('\u{a82c}', '\u{a82c}', Extend), ('\u{a880}', '\u{a881}', SpacingMark),
+    ('\u{a8b4}', '\u{a8c3}', SpacingMark), ('\u{a8c4}', '\u{a8c5}', Extend),
+    ('\u{a8e0}', '\u{a8f1}', Extend), ('\u{a8ff}', '\u{a8ff}', Extend),
+    ('\u{a926}', '\u{a92d}', Extend), ('\u{a947}', '\u{a951}', Extend),
+    ('\u{a952}', '\u{a952}', SpacingMark), ('\u{a953}', '\u{a953}', Extend),
+    ('\u{a960}', '\u{a97c}', L), ('\u{a980}', '\u{a982}', Extend),
+    ('\u{a983}', '\u{a983}', SpacingMark), ('\u{a989}', '\u{a98b}', InCBConsonant),
+    ('\u{a98f}', '\u{a9b2}', InCBConsonant), ('\u{a9b3}', '\u{a9b3}', Extend),
+    ('\u{a9b4}', '\u{a9b5}', SpacingMark), ('\u{a9b6}', '\u{a9b9}', Extend),
+    ('\u{a9ba}', '\u{a9bb}', SpacingMark), ('\u{a9bc}', '\u{a9bd}', Extend),
+    ('\u{a9be}', '\u{a9bf}', SpacingMark), ('\u{a9c0}', '\u{a9c0}', Extend),
+    ('\u{a9e0}', '\u{a9e4}', InCBConsonant), ('\u{a9e5}', '\u{a9e5}', Extend),
+    ('\u{a9e7}', '\u{a9ef}', InCBConsonant), ('\u{a9fa}', '\u{a9fe}', InCBConsonant),
+    ('\u{aa29}', '\u{aa2e}', Extend), ('\u{aa2f}', '\u{aa30}', SpacingMark),
+    ('\u{aa31}', '\u{aa32}', Extend), ('\u{aa33}', '\u{aa34}', SpacingMark),
+    ('\u{aa35}', '\u{aa36}', Extend), ('\u{aa43}', '\u{aa43}', Extend),
+    ('\u{aa4c}', '\u{aa4c}', Extend), ('\u{aa4d}', '\u{aa4d}', SpacingMark),
+    ('\u{aa60}', '\u{aa6f}', InCBConsonant), ('\u{aa71}', '\u{aa73}', InCBConsonant),
+    ('\u{aa7a}', '\u{aa7a}', InCBConsonant), ('\u{aa7c}', '\u{aa7c}', Extend),
+    ('\u{aa7e}', '\u{aa7f}', InCBConsonant), ('\u{aab0}', '\u{aab0}', Extend),
+    ('\u{aab2}', '\u{aab4}', Extend), ('\u{aab7}', '\u{aab8}', Extend),
+    ('\u{aabe}', '\u{aabf}', Extend), ('\u{aac1}', '\u{aac1}', Extend),
+    ('\u{aae0}', '\u{aaea}', InCBConsonant), ('\u{aaeb}', '\u{aaeb}', SpacingMark),
+    ('\u{aaec}', '\u{aaed}', Extend), ('\u{aaee}', '\u{aaef}', SpacingMark),
+    ('\u{aaf5}', '\u{aaf5}', SpacingMark), ('\u{aaf6}', '\u{aaf6}', Extend),
+    ('\u{abc0}', '\u{abda}', InCBConsonant), ('\u{abe3}', '\u{abe4}', SpacingMark),
+    ('\u{abe5}', '\u{abe5}', Extend), ('\u{abe6}', '\u{abe7}', SpacingMark),
+    ('\u{abe8}', '\u{abe8}', Extend), ('\u{abe9}', '\u{abea}', SpacingMark),
+    ('\u{abec}', '\u{abec}', SpacingMark), ('\u{abed}', '\u{abed}', Extend),
+    ('\u{d7b0}', '\u{d7c6}', V), ('\u{d7cb}', '\u{d7fb}', T), ('\u{fb1e}', '\u{fb1e}', Extend),
+    ('\u{fe00}', '\u{fe0f}', Extend), ('\u{fe20}', '\u{fe2f}', Extend),
+    ('\u{feff}', '\u{feff}', Control), ('\u{ff9e}', '\u{ff9f}', Extend),
+    ('\u{fff0}', '\u{fffb}', Control), ('\u{101fd}', '\u{101fd}', Extend),
+    ('\u{102e0}', '\u{102e0}', Extend), ('\u{10376}', '\u{1037a}', Extend),
+    ('\u{10a00}', '\u{10a00}', InCBConsonant), ('\u{10a01}', '\u{10a03}', Extend),
+    ('\u{10a05}', '\u{10a06}', Extend), ('\u{10a0c}', '\u{10a0f}', Extend),
+    ('\u{10a10}', '\u{10a13}', InCBConsonant), ('\u{10a15}', '\u{10a17}', InCBConsonant),
+    ('\u{10a19}', '\u{10a35}', InCBConsonant), ('\u{10a38}', '\u{10a3a}', Extend),
+    ('\u{10a3f}', '\u{10a3f}', Extend), ('\u{10ae5}', '\u{10ae6}', Extend),
+    ('\u{10d24}', '\u{10d27}', Extend), ('\u{10d69}', '\u{10d6d}', Extend),
+    ('\u{10eab}', '\u{10eac}', Extend), ('\u{10efa}', '\u{10eff}', Extend),
+    ('\u{10f46}', '\u{10f50}', Extend), ('\u{10f82}', '\u{10f85}', Extend),
+    ('\u{11000}', '\u{11000}', SpacingMark), ('\u{11001}', '\u{11001}', Extend),
+    ('\u{11002}', '\u{11002}', SpacingMark), ('\u{11038}', '\u{11046}', Extend),
+    ('\u{11070}', '\u{11070}', Extend), ('\u{11073}', '\u{11074}', Extend),
+    ('\u{1107f}', '\u{11081}', Extend), ('\u{11082}', '\u{11082}', SpacingMark),
+    ('\u{110b0}', '\u{110b2}', SpacingMark), ('\u{110b3}', '\u{110b6}', Extend),
+    ('\u{110b7}', '\u{110b8}', SpacingMark), ('\u{110b9}', '\u{110ba}', Extend),
+    ('\u{110bd}', '\u{110bd}', Prepend), ('\u{110c2}', '\u{110c2}', Extend),
+    ('\u{110cd}', '\u{110cd}', Prepend), ('\u{11100}', '\u{11102}', Extend),
+    ('\u{11103}', '\u{11126}', InCBConsonant), ('\u{11127}', '\u{1112b}', Extend),
+    ('\u{1112c}', '\u{1112c}', SpacingMark), ('\u{1112d}', '\u{11134}', Extend),
+    ('\u{11144}', '\u{11144}', InCBConsonant), ('\u{11145}', '\u{11146}', SpacingMark),
+    ('\u{11147}', '\u{11147}', InCBConsonant), ('\u{11173}', '\u{11173}', Extend),
+    ('\u{11180}', '\u{11181}', Extend), ('\u{11182}', '\u{11182}', SpacingMark),
+    ('\u{111b3}', '\u{111b5}', SpacingMark), ('\u{111b6}', '\u{111be}', Extend),
+    ('\u{111bf}', '\u{111bf}', SpacingMark), ('\u{111c0}', '\u{111c0}', Extend),
+    ('\u{111c2}', '\u{111c3}', Prepend), ('\u{111c9}', '\u{111cc}', Extend),
+    ('\u{111ce}', '\u{111ce}', SpacingMark), ('\u{111cf}', '\u{111cf}', Extend),
+    ('\u{1122c}', '\u{1122e}', SpacingMark), ('\u{1122f}', '\u{11231}', Extend),
+    ('\u{11232}', '\u{11233}', SpacingMark), ('\u{11234}', '\u{11237}', Extend),
+    ('\u{1123e}', '\u{1123e}', Extend), ('\u{11241}', '\u{11241}', Extend),
+    ('\u{112df}', '\u{112df}', Extend), ('\u{112e0}', '\u{112e2}', SpacingMark),
+    ('\u{112e3}', '\u{112ea}', Extend), ('\u{11300}', '\u{11301}', Extend),
+    ('\u{11302}', '\u{11303}', SpacingMark), ('\u{1133b}', '\u{1133c}', Extend),
+    ('\u{1133e}', '\u{1133e}', Extend), ('\u{1133f}', '\u{1133f}', SpacingMark),
+    ('\u{11340}', '\u{11340}', Extend), ('\u{11341}', '\u{11344}', SpacingMark),
+    ('\u{11347}', '\u{11348}', SpacingMark), ('\u{1134b}', '\u{1134c}', SpacingMark),
+    ('\u{1134d}', '\u{1134d}', Extend), ('\u{11357}', '\u{11357}', Extend),
+    ('\u{11362}', '\u{11363}', SpacingMark), ('\u{11366}', '\u{1136c}', Extend),
+    ('\u{11370}', '\u{11374}', Extend), ('\u{11380}', '\u{11389}', InCBConsonant),
+    ('\u{1138b}', '\u{1138b}', InCBConsonant), ('\u{1138e}', '\u{1138e}', InCBConsonant),
+    ('\u{11390}', '\u{113b5}', InCBConsonant), ('\u{113b8}', '\u{113b8}', Extend),
+    ('\u{113b9}', '\u{113ba}', SpacingMark), ('\u{113bb}', '\u{113c0}', Extend),
+    ('\u{113c2}', '\u{113c2}', Extend), ('\u{113c5}', '\u{113c5}', Extend),
+    ('\u{113c7}', '\u{113c9}', Extend), ('\u{113ca}', '\u{113ca}', SpacingMark),
+    ('\u{113cc}', '\u{113cd}', SpacingMark), ('\u{113ce}', '\u{113d0}', Extend),
+    ('\u{113d1}', '\u{113d1}', Prepend), ('\u{113d2}', '\u{113d2}', Extend),
+    ('\u{113e1}', '\u{113e2}', Extend), ('\u{11435}', '\u{11437}', SpacingMark),
+    ('\u{11438}', '\u{1143f}', Extend), ('\u{11440}', '\u{11441}', SpacingMark),
+    ('\u{11442}', '\u{11444}', Extend), ('\u{11445}', '\u{11445}', SpacingMark),
+    ('\u{11446}', '\u{11446}', Extend), ('\u{1145e}', '\u{1145e}', Extend),
+    ('\u{114b0}', '\u{114b0}', Extend), ('\u{114b1}', '\u{114b2}', SpacingMark),
+    ('\u{114b3}', '\u{114b8}', Extend), ('\u{114b9}', '\u{114b9}', SpacingMark),
+    ('\u{114ba}', '\u{114ba}', Extend), ('\u{114bb}', '\u{114bc}', SpacingMark),
+    ('\u{114bd}', '\u{114bd}', Extend), ('\u{114be}', '\u{114be}', SpacingMark),
+    ('\u{114bf}', '\u{114c0}', Extend), ('\u{114c1}', '\u{114c1}', SpacingMark),
+    ('\u{114c2}', '\u{114c3}', Extend), ('\u{115af}', '\u{115af}', Extend),
+    ('\u{115b0}', '\u{115b1}', SpacingMark), ('\u{115b2}', '\u{115b5}', Extend),
+    ('\u{115b8}', '\u{115bb}', SpacingMark), ('\u{115bc}', '\u{115bd}', Extend),
+    ('\u{115be}', '\u{115be}', SpacingMark), ('\u{115bf}', '\u{115c0}', Extend),
+    ('\u{115dc}', '\u{115dd}', Extend), ('\u{11630}', '\u{11632}', SpacingMark),
+    ('\u{11633}', '\u{1163a}', Extend), ('\u{1163b}', '\u{1163c}', SpacingMark),
+    ('\u{1163d}', '\u{1163d}', Extend), ('\u{1163e}', '\u{1163e}', SpacingMark),
+    ('\u{1163f}', '\u{11640}', Extend), ('\u{116ab}', '\u{116ab}', Extend),
+    ('\u{116ac}', '\u{116ac}', SpacingMark), ('\u{116ad}', '\u{116ad}', Extend),
+    ('\u{116ae}', '\u{116af}', SpacingMark), ('\u{116b0}', '\u{116b7}', Extend),
+    ('\u{1171d}', '\u{1171d}', Extend), ('\u{1171e}', '\u{1171e}', SpacingMark),
+    ('\u{1171f}', '\u{1171f}', Extend), ('\u{11722}', '\u{11725}', Extend),
+    ('\u{11726}', '\u{11726}', SpacingMark), ('\u{11727}', '\u{1172b}', Extend),
+    ('\u{1182c}', '\u{1182e}', SpacingMark), ('\u{1182f}', '\u{11837}', Extend),
+    ('\u{11838}', '\u{11838}', SpacingMark), ('\u{11839}', '\u{1183a}', Extend),
+    ('\u{11900}', '\u{11906}', InCBConsonant), ('\u{11909}', '\u{11909}', InCBConsonant),
+    ('\u{1190c}', '\u{11913}', InCBConsonant), ('\u{11915}', '\u{11916}', InCBConsonant),
+    ('\u{11918}', '\u{1192f}', InCBConsonant), ('\u{11930}', '\u{11930}', Extend),
+    ('\u{11931}', '\u{11935}', SpacingMark), ('\u{11937}', '\u{11938}', SpacingMark),
+    ('\u{1193b}', '\u{1193e}', Extend), ('\u{1193f}', '\u{1193f}', Prepend),
+    ('\u{11940}', '\u{11940}', SpacingMark), ('\u{11941}', '\u{11941}', Prepend),
+    ('\u{11942}', '\u{11942}', SpacingMark), ('\u{11943}', '\u{11943}', Extend),
+    ('\u{119d1}', '\u{119d3}', SpacingMark), ('\u{119d4}', '\u{119d7}', Extend),
+    ('\u{119da}', '\u{119db}', Extend), ('\u{119dc}', '\u{119df}', SpacingMark),
+    ('\u{119e0}', '\u{119e0}', Extend), ('\u{119e4}', '\u{119e4}', SpacingMark),
+    ('\u{11a00}', '\u{11a00}', InCBConsonant), ('\u{11a01}', '\u{11a0a}', Extend),
+    ('\u{11a0b}', '\u{11a32}', InCBConsonant), ('\u{11a33}', '\u{11a38}', Extend),
+    ('\u{11a39}', '\u{11a39}', SpacingMark), ('\u{11a3b}', '\u{11a3e}', Extend),
+    ('\u{11a47}', '\u{11a47}', Extend), ('\u{11a50}', '\u{11a50}', InCBConsonant),
+    ('\u{11a51}', '\u{11a56}', Extend), ('\u{11a57}', '\u{11a58}', SpacingMark),
+    ('\u{11a59}', '\u{11a5b}', Extend), ('\u{11a5c}', '\u{11a83}', InCBConsonant),
+    ('\u{11a84}', '\u{11a89}', Prepend), ('\u{11a8a}', '\u{11a96}', Extend),
+    ('\u{11a97}', '\u{11a97}', SpacingMark), ('\u{11a98}', '\u{11a99}', Extend),
+    ('\u{11b60}', '\u{11b60}', Extend), ('\u{11b61}', '\u{11b61}', SpacingMark),
+    ('\u{11b62}', '\u{11b64}', Extend), ('\u{11b65}', '\u{11b65}', SpacingMark),
+    ('\u{11b66}', '\u{11b66}', Extend), ('\u{11b67}', '\u{11b67}', SpacingMark),
+    ('\u{11c2f}', '\u{11c2f}', SpacingMark), ('\u{11c30}', '\u{11c36}', Extend),
+    ('\u{11c38}', '\u{11c3d}', Extend), ('\u{11c3e}', '\u{11c3e}', SpacingMark),
+    ('\u{11c3f}', '\u{11c3f}', Extend), ('\u{11c92}', '\u{11ca7}', Extend),
+    ('\u{11ca9}', '\u{11ca9}', SpacingMark), ('\u{11caa}', '\u{11cb0}', Extend),
+    ('\u{11cb1}', '\u{11cb1}', SpacingMark), ('\u{11cb2}', '\u{11cb3}', Extend),
+    ('\u{11cb4}', '\u{11cb4}', SpacingMark), ('\u{11cb5}', '\u{11cb6}', Extend),
+    ('\u{11d31}', '\u{11d36}', Extend), ('\u{11d3a}', '\u{11d3a}', Extend),
+    ('\u{11d3c}', '\u{11d3d}', Extend), ('\u{11d3f}', '\u{11d45}', Extend),
+    ('\u{11d46}', '\u{11d46}', Prepend), ('\u{11d47}', '\u{11d47}', Extend),
+    ('\u{11d8a}', '\u{11d8e}', SpacingMark), ('\u{11d90}', '\u{11d91}', Extend),
+    ('\u{11d93}', '\u{11d94}', SpacingMark), ('\u{11d95}', '\u{11d95}', Extend),
+    ('\u{11d96}', '\u{11d96}', SpacingMark), ('\u{11d97}', '\u{11d97}', Extend),
+    ('\u{11ef3}', '\u{11ef4}', Extend), ('\u{11ef5}', '\u{11ef6}', SpacingMark),
+    ('\u{11f00}', '\u{11f01}', Extend), ('\u{11f02}', '\u{11f02}', Prepend),
+    ('\u{11f03}', '\u{11f03}', SpacingMark), ('\u{11f04}', '\u{11f10}', InCBConsonant),
+    ('\u{11f12}', '\u{11f33}', InCBConsonant), ('\u{11f34}', '\u{11f35}', SpacingMark),
+    ('\u{11f36}', '\u{11f3a}', Extend), ('\u{11f3e}', '\u{11f3f}', SpacingMark),
+    ('\u{11f40}', '\u{11f42}', Extend), ('\u{11f5a}', '\u{11f5a}', Extend),
+    ('\u{13430}', '\u{1343f}', Control), ('\u{13440}', '\u{13440}', Extend),
+    ('\u{13447}', '\u{13455}', Extend), ('\u{1611e}', '\u{16129}', Extend),
+    ('\u{1612a}', '\u{1612c}', SpacingMark), ('\u{1612d}', '\u{1612f}', Extend),
+    ('\u{16af0}', '\u{16af4}', Extend), ('\u{16b30}', '\u{16b36}', Extend),
+    ('\u{16d63}', '\u{16d63}', V), ('\u{16d67}', '\u{16d6a}', V),
+    ('\u{16f4f}', '\u{16f4f}', Extend), ('\u{16f51}', '\u{16f87}', SpacingMark),
+    ('\u{16f8f}', '\u{16f92}', Extend), ('\u{16fe4}', '\u{16fe4}', Extend),
+    ('\u{16ff0}', '\u{16ff1}', Extend), ('\u{1bc9d}', '\u{1bc9e}', Extend),
+    ('\u{1bca0}', '\u{1bca3}', Control), ('\u{1cf00}', '\u{1cf2d}', Extend),
+    ('\u{1cf30}', '\u{1cf46}', Extend), ('\u{1d165}', '\u{1d169}', Extend),
+    ('\u{1d16d}', '\u{1d172}', Extend), ('\u{1d173}', '\u{1d17a}', Control),
+    ('\u{1d17b}', '\u{1d182}', Extend), ('\u{1d185}', '\u{1d18b}', Extend),
+    ('\u{1d1aa}', '\u{1d1ad}', Extend), ('\u{1d242}', '\u{1d244}', Extend),
+    ('\u{1da00}', '\u{1da36}', Extend), ('\u{1da3b}', '\u{1da6c}', Extend),
+    ('\u{1da75}', '\u{1da75}', Extend), ('\u{1da84}', '\u{1da84}', Extend),
+    ('\u{1da9b}', '\u{1da9f}', Extend), ('\u{1daa1}', '\u{1daaf}', Extend),
+    ('\u{1e000}', '\u{1e006}', Extend), ('\u{1e008}', '\u{1e018}', Extend),
+    ('\u{1e01b}', '\u{1e021}', Extend), ('\u{1e023}', '\u{1e024}', Extend),
+    ('\u{1e026}', '\u{1e02a}', Extend), ('\u{1e08f}', '\u{1e08f}', Extend),
+    ('\u{1e130}', '\u{1e136}', Extend), ('\u{1e2ae}', '\u{1e2ae}', Extend),
+    ('\u{1e2ec}', '\u{1e2ef}', Extend), ('\u{1e4ec}', '\u{1e4ef}', Extend),
+    ('\u{1e5ee}', '\u{1e5ef}', Extend), ('\u{1e6e3}', '\u{1e6e3}', Extend),
+    ('\u{1e6e6}', '\u{1e6e6}', Extend), ('\u{1e6ee}', '\u{1e6ef}', Extend),
+    ('\u{1e6f5}', '\u{1e6f5}', Extend), ('\u{1e8d0}', '\u{1e8d6}', Extend),
+    ('\u{1e944}', '\u{1e94a}', Extend), ('\u{1f004}', '\u{1f004}', ExtendedPictographic),
+    ('\u{1f02c}', '\u{1f02f}', ExtendedPictographic),
+    ('\u{1f094}', '\u{1f09f}', ExtendedPictographic),
+    ('\u{1f0af}', '\u{1f0b0}', ExtendedPictographic),
+    ('\u{1f0c0}', '\u{1f0c0}', ExtendedPictographic),
+    ('\u{1f0cf}', '\u{1f0d0}', ExtendedPictographic),
+    ('\u{1f0f6}', '\u{1f0ff}', ExtendedPictographic),
+    ('\u{1f170}', '\u{1f171}', ExtendedPictographic),
+    ('\u{1f17e}', '\u{1f17f}', ExtendedPictographic),
+    ('\u{1f18e}', '\u{1f18e}', ExtendedPictographic),
+    ('\u{1f191}', '\u{1f19a}', ExtendedPictographic),
+    ('\u{1f1ae}', '\u{1f1e5}', ExtendedPictographic), ('\u{1f1e6}', '\u{1f1ff}', RegionalIndicator),
+    ('\u{1f201}', '\u{1f20f}', ExtendedPictographic),
+    ('\u{1f21a}', '\u{1f21a}', ExtendedPictographic),
+    ('\u{1f22f}', '\u{1f22f}', ExtendedPictographic),
+    ('\u{1f232}', '\u{1f23a}', ExtendedPictographic),
+    ('\u{1f23c}', '\u{1f23f}', ExtendedPictographic),
+    ('\u{1f249}', '\u{1f25f}', ExtendedPictographic),
+    ('\u{1f266}', '\u{1f321}', ExtendedPictographic),
+    ('\u{1f324}', '\u{1f393}', ExtendedPictographic),
+    ('\u{1f396}', '\u{1f397}', ExtendedPictographic),
+    ('\u{1f399}', '\u{1f39b}', ExtendedPictographic),
+    ('\u{1f39e}', '\u{1f3f0}', ExtendedPictographic),
+    ('\u{1f3f3}', '\u{1f3f5}', ExtendedPictographic),
+    ('\u{1f3f7}', '\u{1f3fa}', ExtendedPictographic), ('\u{1f3fb}', '\u{1f3ff}', Extend),
+    ('\u{1f400}', '\u{1f4fd}', ExtendedPictographic),
+    ('\u{1f4ff}', '\u{1f53d}', ExtendedPictographic),
+    ('\u{1f549}', '\u{1f54e}', ExtendedPictographic),
+    ('\u{1f550}', '\u{1f567}', ExtendedPictographic),
+    ('\u{1f56f}', '\u{1f570}', ExtendedPictographic),
+    ('\u{1f573}', '\u{1f57a}', ExtendedPictographic),
+    ('\u{1f587}', '\u{1f587}', ExtendedPictographic),
+    ('\u{1f58a}', '\u{1f58d}', ExtendedPictographic),
+    ('\u{1f590}', '\u{1f590}', ExtendedPictographic),
+    ('\u{1f595}', '\u{1f596}', ExtendedPictographic),
+    ('\u{1f5a4}', '\u{1f5a5}', ExtendedPictographic),
+    ('\u{1f5a8}', '\u{1f5a8}', ExtendedPictographic),
+    ('\u{1f5b1}', '\u{1f5b2}', ExtendedPictographic),
+    ('\u{1f5bc}', '\u{1f5bc}', ExtendedPictographic),
+    ('\u{1f5c2}', '\u{1f5c4}', ExtendedPictographic),
+    ('\u{1f5d1}', '\u{1f5d3}', ExtendedPictographic),
+    ('\u{1f5dc}', '\u{1f5de}', ExtendedPictographic),
+    ('\u{1f5e1}', '\u{1f5e1}', ExtendedPictographic),
+    ('\u{1f5e3}', '\u{1f5e3}', ExtendedPictographic),
+    ('\u{1f5e8}', '\u{1f5e8}', ExtendedPictographic),
+    ('\u{1f5ef}', '\u{1f5ef}', ExtendedPictographic),
+    ('\u{1f5f3}', '\u{1f5f3}', ExtendedPictographic),
+    ('\u{1f5fa}', '\u{1f64f}', ExtendedPictographic),
+    ('\u{1f680}', '\u{1f6c5}', ExtendedPictographic),
+    ('\u{1f6cb}', '\u{1f6d2}', ExtendedPictographic),
+    ('\u{1f6d5}', '\u{1f6e5}', ExtendedPictographic),
+    ('\u{1f6e9}', '\u{1f6e9}', ExtendedPictographic),
+    ('\u{1f6eb}', '\u{1f6f0}', ExtendedPictographic),
+    ('\u{1f6f3}', '\u{1f6ff}', ExtendedPictographic),
+    ('\u{1f7da}', '\u{1f7ff}', ExtendedPictographic),
+    ('\u{1f80c}', '\u{1f80f}', ExtendedPictographic),
+    ('\u{1f848}', '\u{1f84f}', ExtendedPictographic),
+    ('\u{1f85a}', '\u{1f85f}', ExtendedPictographic),
+    ('\u{1f888}', '\u{1f88f}', ExtendedPictographic),
+    ('\u{1f8ae}', '\u{1f8af}', ExtendedPictographic),
+    ('\u{1f8bc}', '\u{1f8bf}', ExtendedPictographic),
+    ('\u{1f8c2}', '\u{1f8cf}', ExtendedPictographic),
+    ('\u{1f8d9}', '\u{1f8ff}', ExtendedPictographic),
+    ('\u{1f90c}', '\u{1f93a}', ExtendedPictographic),
+    ('\u{1f93c}', '\u{1f945}', ExtendedPictographic),
+    ('\u{1f947}', '\u{1f9ff}', ExtendedPictographic),
+    ('\u{1fa58}', '\u{1fa5f}', ExtendedPictographic),
+    ('\u{1fa6e}', '\u{1faff}', ExtendedPictographic),
+    ('\u{1fc00}', '\u{1fffd}', ExtendedPictographic), ('\u{e0000}', '\u{e001f}', Control),
+    ('\u{e0020}', '\u{e007f}', Extend), ('\u{e0080}', '\u{e00ff}', Control),
+    ('\u{e0100}', '\u{e01ef}', Extend), ('\u{e01f0}', '\u{e0fff}', Control),
+];
+
+/// Code points with `Indic_Conjunct_Break=Extend`
+#[rustfmt::skip]
+pub(super) const INCB_EXTEND: &[(char, char)] = &[
+    ('\u{300}', '\u{36f}'), ('\u{483}', '\u{489}'), ('\u{591}', '\u{5bd}'), ('\u{5bf}', '\u{5bf}'),
+    ('\u{5c1}', '\u{5c2}'), ('\u{5c4}', '\u{5c5}'), ('\u{5c7}', '\u{5c7}'), ('\u{610}', '\u{61a}'),
+    ('\u{64b}', '\u{65f}'), ('\u{670}', '\u{670}'), ('\u{6d6}', '\u{6dc}'), ('\u{6df}', '\u{6e4}'),
+    ('\u{6e7}', '\u{6e8}'), ('\u{6ea}', '\u{6ed}'), ('\u{711}', '\u{711}'), ('\u{730}', '\u{74a}'),
+    ('\u{7a6}', '\u{7b0}'), ('\u{7eb}', '\u{7f3}'), ('\u{7fd}', '\u{7fd}'), ('\u{816}', '\u{819}'),
+    ('\u{81b}', '\u{823}'), ('\u{825}', '\u{827}'), ('\u{829}', '\u{82d}'), ('\u{859}', '\u{85b}'),
+    ('\u{897}', '\u{89f}'), ('\u{8ca}', '\u{8e1}'), ('\u{8e3}', '\u{902}'), ('\u{93a}', '\u{93a}'),
+    ('\u{93c}', '\u{93c}'), ('\u{941}', '\u{948}'), ('\u{951}', '\u{957}'), ('\u{962}', '\u{963}'),
+    ('\u{981}', '\u{981}'), ('\u{9bc}', '\u{9bc}'), ('\u{9be}', '\u{9be}'), ('\u{9c1}', '\u{9c4}'),
+    ('\u{9d7}', '\u{9d7}'), ('\u{9e2}', '\u{9e3}'), ('\u{9fe}', '\u{9fe}'), ('\u{a01}', '\u{a02}'),
+    ('\u{a3c}', '\u{a3c}'), ('\u{a41}', '\u{a42}'), ('\u{a47}', '\u{a48}'), ('\u{a4b}', '\u{a4d}'),
+    ('\u{a51}', '\u{a51}'), ('\u{a70}', '\u{a71}'), ('\u{a75}', '\u{a75}'), ('\u{a81}', '\u{a82}'),
+    ('\u{abc}', '\u{abc}'), ('\u{ac1}', '\u{ac5}'), ('\u{ac7}', '\u{ac8}'), ('\u{ae2}', '\u{ae3}'),
+    ('\u{afa}', '\u{aff}'), ('\u{b01}', '\u{b01}'), ('\u{b3c}', '\u{b3c}'), ('\u{b3e}', '\u{b3f}'),
+    ('\u{b41}', '\u{b44}'), ('\u{b55}', '\u{b57}'), ('\u{b62}', '\u{b63}'), ('\u{b82}', '\u{b82}'),
+    ('\u{bbe}', '\u{bbe}'), ('\u{bc0}', '\u{bc0}'), ('\u{bcd}', '\u{bcd}'), ('\u{bd7}', '\u{bd7}'),
+    ('\u{c00}', '\u{c00}'), ('\u{c04}', '\u{c04}'), ('\u{c3c}', '\u{c3c}'), ('\u{c3e}', '\u{c40}'),
+    ('\u{c46}', '\u{c48}'), ('\u{c4a}', '\u{c4c}'), ('\u{c55}', '\u{c56}'), ('\u{c62}', '\u{c63}'),
+    ('\u{c81}', '\u{c81}'), ('\u{cbc}', '\u{cbc}'), ('\u{cbf}', '\u{cc0}'), ('\u{cc2}', '\u{cc2}'),
+    ('\u{cc6}', '\u{cc8}'), ('\u{cca}', '\u{ccd}'), ('\u{cd5}', '\u{cd6}'), ('\u{ce2}', '\u{ce3}'),
+    ('\u{d00}', '\u{d01}'), ('\u{d3b}', '\u{d3c}'), ('\u{d3e}', '\u{d3e}'), ('\u{d41}', '\u{d44}'),
+    ('\u{d57}', '\u{d57}'), ('\u{d62}', '\u{d63}'), ('\u{d81}', '\u{d81}'), ('\u{dca}', '\u{dca}'),
+    ('\u{dcf}', '\u{dcf}'), ('\u{dd2}', '\u{dd4}'), ('\u{dd6}', '\u{dd6}'), ('\u{ddf}', '\u{ddf}'),
+    ('\u{e31}', '\u{e31}'), ('\u{e34}', '\u{e3a}'), ('\u{e47}', '\u{e4e}'), ('\u{eb1}', '\u{eb1}'),
+    ('\u{eb4}', '\u{ebc}'), ('\u{ec8}', '\u{ece}'), ('\u{f18}', '\u{f19}'), ('\u{f35}', '\u{f35}'),
+    ('\u{f37}', '\u{f37}'), ('\u{f39}', '\u{f39}'), ('\u{f71}', '\u{f7e}'), ('\u{f80}', '\u{f84}'),
+    ('\u{f86}', '\u{f87}'), ('\u{f8d}', '\u{f97}'), ('\u{f99}', '\u{fbc}'), ('\u{fc6}', '\u{fc6}'),
+    ('\u{102d}', '\u{1030}'), ('\u{1032}', '\u{1037}'), ('\u{103a}', '\u{103a}'),
+    ('\u{103d}', '\u{103e}'), ('\u{1058}', '\u{1059}'), ('\u{105e}', '\u{1060}'),
+    ('\u{1071}', '\u{1074}'), ('\u{1082}', '\u{1082}'), ('\u{1085}', '\u{1086}'),
+    ('\u{108d}', '\u{108d}'), ('\u{109d}', '\u{109d}'), ('\u{135d}', '\u{135f}'),
+    ('\u{1712}', '\u{1715}'), ('\u{1732}', '\u{1734}'), ('\u{1752}', '\u{1753}'),
+    ('\u{1772}', '\u{1773}'), ('\u{17b4}', '\u{17b5}'), ('\u{17b7}', '\u{17bd}'),
+    ('\u{17c6}', '\u{17c6}'), ('\u{17c9}', '\u{17d1}'), ('\u{17d3}', '\u{17d3}'),
+    ('\u{17dd}', '\u{17dd}'), ('\u{180b}', '\u{180d}'), ('\u{180f}', '\u{180f}'),
+    ('\u{1885}', '\u{1886}'), ('\u{18a9}', '\u{18a9}'), ('\u{1920}', '\u{1922}'),
+    ('\u{1927}', '\u{1928}'), ('\u{1932}', '\u{1932}'), ('\u{1939}', '\u{193b}'),
+    ('\u{1a17}', '\u{1a18}'), ('\u{1a1b}', '\u{1a1b}'), ('\u{1a56}', '\u{1a56}'),
+    ('\u{1a58}', '\u{1a5e}'), ('\u{1a62}', '\u{1a62}'), ('\u{1a65}', '\u{1a6c}'),
+    ('\u{1a73}', '\u{1a7c}'), ('\u{1a7f}', '\u{1a7f}'), ('\u{1ab0}', '\u{1add}'),
+    ('\u{1ae0}', '\u{1aeb}'), ('\u{1b00}', '\u{1b03}'), ('\u{1b34}', '\u{1b3d}'),
+    ('\u{1b42}', '\u{1b43}'), ('\u{1b6b}', '\u{1b73}'), ('\u{1b80}', '\u{1b81}'),
+    ('\u{1ba2}', '\u{1ba5}'), ('\u{1ba8}', '\u{1baa}'), ('\u{1bac}', '\u{1bad}'),
+    ('\u{1be6}', '\u{1be6}'), ('\u{1be8}', '\u{1be9}'), ('\u{1bed}', '\u{1bed}'),
+    ('\u{1bef}', '\u{1bf3}'), ('\u{1c2c}', '\u{1c33}'), ('\u{1c36}', '\u{1c37}'),
+    ('\u{1cd0}', '\u{1cd2}'), ('\u{1cd4}', '\u{1ce0}'), ('\u{1ce2}', '\u{1ce8}'),
+    ('\u{1ced}', '\u{1ced}'), ('\u{1cf4}', '\u{1cf4}'), ('\u{1cf8}', '\u{1cf9}'),
+    ('\u{1dc0}', '\u{1dff}'), ('\u{200d}', '\u{200d}'), ('\u{20d0}', '\u{20f0}'),
+    ('\u{2cef}', '\u{2cf1}'), ('\u{2d7f}', '\u{2d7f}'), ('\u{2de0}', '\u{2dff}'),
+    ('\u{302a}', '\u{302f}'), ('\u{3099}', '\u{309a}'), ('\u{a66f}', '\u{a672}'),
+    ('\u{a674}', '\u{a67d}'), ('\u{a69e}', '\u{a69f}'), ('\u{a6f0}', '\u{a6f1}'),
+    ('\u{a802}', '\u{a802}'), ('\u{a806}', '\u{a806}'), ('\u{a80b}', '\u{a80b}'),
+    ('\u{a825}', '\u{a826}'), ('\u{a82c}', '\u{a82c}'), ('\u{a8c4}', '\u{a8c5}'),
+    ('\u{a8e0}', '\u{a8f1}'), ('\u{a8ff}', '\u{a8ff}'), ('\u{a926}', '\u{a92d}'),
+    ('\u{a947}', '\u{a951}'), ('\u{a953}', '\u{a953}'), ('\u{a980}', '\u{a982}'),
+    ('\u{a9b3}', '\u{a9b3}'), ('\u{a9b6}', '\u{a9b9}'), ('\u{a9bc}', '\u{a9bd}'),
+    ('\u{a9e5}', '\u{a9e5}'), ('\u{aa29}', '\u{aa2e}'), ('\u{aa31}', '\u{aa32}'),
+    ('\u{aa35}', '\u{aa36}'), ('\u{aa43}', '\u{aa43}'), ('\u{aa4c}', '\u{aa4c}'),
+    ('\u{aa7c}', '\u{aa7c}'), ('\u{aab0}', '\u{aab0}'), ('\u{aab2}', '\u{aab4}'),
+    ('\u{aab7}', '\u{aab8}'), ('\u{aabe}', '\u{aabf}'), ('\u{aac1}', '\u{aac1}'),
+    ('\u{aaec}', '\u{aaed}'), ('\u{abe5}', '\u{abe5}'), ('\u{abe8}', '\u{abe8}'),
+    ('\u{abed}', '\u{abed}'), ('\u{fb1e}', '\u{fb1e}'), ('\u{fe00}', '\u{fe0f}'),
+    ('\u{fe20}', '\u{fe2f}'), ('\u{ff9e}', '\u{ff9f}'), ('\u{101fd}', '\u{101fd}'),
+    ('\u{102e0}', '\u{102e0}'), ('\u{10376}', '\u{1037a}'), ('\u{10a01}', '\u{10a03}'),
+    ('\u{10a05}', '\u{10a06}'), ('\u{10a0c}', '\u{10a0f}'), ('\u{10a38}', '\u{10a3a}'),
+    ('\u{10ae5}', '\u{10ae6}'), ('\u{10d24}', '\u{10d27}'), ('\u{10d69}', '\u{10d6d}'),
+    ('\u{10eab}', '\u{10eac}'), ('\u{10efa}', '\u{10eff}'), ('\u{10f46}', '\u{10f50}'),
+    ('\u{10f82}', '\u{10f85}'), ('\u{11001}', '\u{11001}'), ('\u{11038}', '\u{11046}'),
+    ('\u{11070}', '\u{11070}'), ('\u{11073}', '\u{11074}'), ('\u{1107f}', '\u{11081}'),
+    ('\u{110b3}', '\u{110b6}'), ('\u{110b9}', '\u{110ba}'), ('\u{110c2}', '\u{110c2}'),
+    ('\u{11100}', '\u{11102}'), ('\u{11127}', '\u{1112b}'), ('\u{1112d}', '\u{11132}'),
+    ('\u{11134}', '\u{11134}'), ('\u{11173}', '\u{11173}'), ('\u{11180}', '\u{11181}'),
+    ('\u{111b6}', '\u{111be}'), ('\u{111c0}', '\u{111c0}'), ('\u{111c9}', '\u{111cc}'),
+    ('\u{111cf}', '\u{111cf}'), ('\u{1122f}', '\u{11231}'), ('\u{11234}', '\u{11237}'),
+    ('\u{1123e}', '\u{1123e}'), ('\u{11241}', '\u{11241}'), ('\u{112df}', '\u{112df}'),
+    ('\u{112e3}', '\u{112ea}'), ('\u{11300}', '\u{11301}'), ('\u{1133b}', '\u{1133c}'),
+    ('\u{1133e}', '\u{1133e}'), ('\u{11340}', '\u{11340}'), ('\u{1134d}', '\u{1134d}'),
+    ('\u{11357}', '\u{11357}'), ('\u{11366}', '\u{1136c}'), ('\u{11370}', '\u{11374}'),
+    ('\u{113b8}', '\u{113b8}'), ('\u{113bb}', '\u{113c0}'), ('\u{113c2}', '\u{113c2}'),
+    ('\u{113c5}', '\u{113c5}'), ('\u{113c7}', '\u{113c9}'), ('\u{113ce}', '\u{113cf}'),
+    ('\u{113d2}', '\u{113d2}'), ('\u{113e1}', '\u{113e2}'), ('\u{11438}', '\u{1143f}'),
+    ('\u{11442}', '\u{11444}'), ('\u{11446}', '\u{11446}'), ('\u{1145e}', '\u{1145e}'),
+    ('\u{114b0}', '\u{114b0}'), ('\u{114b3}', '\u{114b8}'), ('\u{114ba}', '\u{114ba}'),
+    ('\u{114bd}', '\u{114bd}'), ('\u{114bf}', '\u{114c0}'), ('\u{114c2}', '\u{114c3}'),
+    ('\u{115af}', '\u{115af}'), ('\u{115b2}', '\u{115b5}'), ('\u{115bc}', '\u{115bd}'),
+    ('\u{115bf}', '\u{115c0}'), ('\u{115dc}', '\u{115dd}'), ('\u{11633}', '\u{1163a}'),
+    ('\u{1163d}', '\u{1163d}'), ('\u{1163f}', '\u{11640}'), ('\u{116ab}', '\u{116ab}'),
+    ('\u{116ad}', '\u{116ad}'), ('\u{116b0}', '\u{116b7}'), ('\u{1171d}', '\u{1171d}'),
+    ('\u{1171f}', '\u{1171f}'), ('\u{11722}', '\u{11725}'), ('\u{11727}', '\u{1172b}'),
+    ('\u{1182f}', '\u{11837}'), ('\u{11839}', '\u{1183a}'), ('\u{11930}', '\u{11930}'),
+    ('\u{1193b}', '\u{1193d}'), ('\u{11943}', '\u{11943}'), ('\u{119d4}', '\u{119d7}'),
+    ('\u{119da}', '\u{119db}'), ('\u{119e0}', '\u{119e0}'), ('\u{11a01}', '\u{11a0a}'),
+    ('\u{11a33}', '\u{11a38}'), ('\u{11a3b}', '\u{11a3e}'), ('\u{11a51}', '\u{11a56}'),
+    ('\u{11a59}', '\u{11a5b}'), ('\u{11a8a}', '\u{11a96}'), ('\u{11a98}', '\u{11a98}'),
+    ('\u{11b60}', '\u{11b60}'), ('\u{11b62}', '\u{11b64}'), ('\u{11b66}', '\u{11b66}'),
+    ('\u{11c30}', '\u{11c36}'), ('\u{11c38}', '\u{11c3d}'), ('\u{11c3f}', '\u{11c3f}'),
+    ('\u{11c92}', '\u{11ca7}'), ('\u{11caa}', '\u{11cb0}'), ('\u{11cb2}', '\u{11cb3}'),
+    ('\u{11cb5}', '\u{11cb6}'), ('\u{11d31}', '\u{11d36}'), ('\u{11d3a}', '\u{11d3a}'),
+    ('\u{11d3c}', '\u{11d3d}'), ('\u{11d3f}', '\u{11d45}'), ('\u{11d47}', '\u{11d47}'),
+    ('\u{11d90}', '\u{11d91}'), ('\u{11d95}', '\u{11d95}'), ('\u{11d97}', '\u{11d97}'),
+    ('\u{11ef3}', '\u{11ef4}'), ('\u{11f00}', '\u{11f01}'), ('\u{11f36}', '\u{11f3a}'),
+    ('\u{11f40}', '\u{11f41}'), ('\u{11f5a}', '\u{11f5a}'), ('\u{13440}', '\u{13440}'),
+    ('\u{13447}', '\u{13455}'), ('\u{1611e}', '\u{16129}'), ('\u{1612d}', '\u{1612f}'),
+    ('\u{16af0}', '\u{16af4}'), ('\u{16b30}', '\u{16b36}'), ('\u{16f4f}', '\u{16f4f}'),
+    ('\u{16f8f}', '\u{16f92}'), ('\u{16fe4}', '\u{16fe4}'), ('\u{16ff0}', '\u{16ff1}'),
+    ('\u{1bc9d}', '\u{1bc9e}'), ('\u{1cf00}', '\u{1cf2d}'), ('\u{1cf30}', '\u{1cf46}'),
+    ('\u{1d165}', '\u{1d169}'), ('\u{1d16d}', '\u{1d172}'), ('\u{1d17b}', '\u{1d182}'),
+    ('\u{1d185}', '\u{1d18b}'), ('\u{1d1aa}', '\u{1d1ad}'), ('\u{1d242}', '\u{1d244}'),
+    ('\u{1da00}', '\u{1da36}'), ('\u{1da3b}', '\u{1da6c}'), ('\u{1da75}', '\u{1da75}'),
+    ('\u{1da84}', '\u{1da84}'), ('\u{1da9b}', '\u{1da9f}'), ('\u{1daa1}', '\u{1daaf}'),
+    ('\u{1e000}', '\u{1e006}'), ('\u{1e008}', '\u{1e018}'), ('\u{1e01b}', '\u{1e021}'),
+    ('\u{1e023}', '\u{1e024}'), ('\u{1e026}', '\u{1e02a}'), ('\u{1e08f}', '\u{1e08f}'),
+    ('\u{1e130}', '\u{1e136}'), ('\u{1e2ae}', '\u{1e2ae}'), ('\u{1e2ec}', '\u{1e2ef}'),
+    ('\u{1e4ec}', '\u{1e4ef}'), ('\u{1e5ee}', '\u{1e5ef}'), ('\u{1e6e3}', '\u{1e6e3}'),
+    ('\u{1e6e6}', '\u{1e6e6}'), ('\u{1e6ee}', '\u{1e6ef}'), ('\u{1e6f5}', '\u{1e6f5}'),
+    ('\u{1e8d0}', '\u{1e8d6}'), ('\u{1e944}', '\u{1e94a}'), ('\u{1f3fb}', '\u{1f3ff}'),
+    ('\u{e0020}', '\u{e007f}'), ('\u{e0100}', '\u{e01ef}'),
+];
+
+/// Code points with `Indic_Conjunct_Break=Linker` (viramas)
+#[rustfmt::skip]
+pub(super) const INCB_LINKER: &[char] = &[
+    '\u{94d}', '\u{9cd}', '\u{acd}', '\u{b4d}', '\u{c4d}', '\u{d4d}', '\u{1039}', '\u{17d2}',
+    '\u{1a60}', '\u{1b44}', '\u{1bab}', '\u{a9c0}', '\u{aaf6}', '\u{10a3f}', '\u{11133}',
+    '\u{113d0}', '\u{1193e}', '\u{11a47}', '\u{11a99}', '\u{11f42}',
+];
@@ -0,0 +1,205 @@
+//! # Capacity Growth
+//!
+//! Pushing past the capacity of a `String` reallocates, but how much does
+//! it grow? `Vec<u8>` (and so `String`) follows an amortized rule: the new
+//! capacity is the largest of
+//!
+//! - twice the old capacity,
+//! - the capacity actually required, and
+//! - a minimum of 8 bytes for the first allocation.
+//!
+//! Doubling makes a sequence of `n` pushes cost O(n) copying in total.
+//! This module records what really happens, push by push, and compares
+//! every step against that rule. It also covers the calls that take
+//! control of the capacity: `reserve`, `reserve_exact`, `shrink_to` and
+//! `shrink_to_fit`.
+//!
+//! Whether a reallocation *moves* the data is up to the allocator: it may
+//! be able to extend the block in place.
+
+use serde::Serialize;
+
+/// Smallest non-zero capacity std allocates for 1-byte elements
+pub const MIN_NON_ZERO_CAP: usize = 8;
+
+/// The capacity std's amortized growth picks when `required` bytes do not
+/// fit in `capacity`
+pub fn amortized_capacity(capacity: usize, required: usize) -> usize {
+    (capacity * 2).max(required).max(MIN_NON_ZERO_CAP)
+}
+
+/// A push that changed the capacity
+#[derive(Debug, Clone, Serialize)]
+pub struct GrowthEvent {
+    /// Number of the push (starting at 1) that triggered the growth
+    pub push: usize,
+    /// Length after the push
+    pub len: usize,
+    pub old_capacity: usize,
+    pub new_capacity: usize,
+    /// The capacity std's amortized rule predicts
+    pub expected_capacity: usize,
+    /// Whether the data pointer changed
+    pub moved: bool,
+}
+
+impl GrowthEvent {
+    /// Whether the observed capacity is the one the rule predicts
+    pub fn matches_rule(&self) -> bool {
+        self.new_capacity == self.expected_capacity
+    }
+}
+
+/// Every capacity change while pushing `chunk` bytes at a time up to
+/// `target` bytes
+#[derive(Debug, Clone, Serialize)]
+pub struct GrowthCurve {
+    pub chunk: usize,
+    pub target: usize,
+    /// Number of `push_str` calls made
+    pub pushes: usize,
+    /// `(len, capacity)` after every push, for plotting
+    pub samples: Vec<(usize, usize)>,
+    pub events: Vec<GrowthEvent>,
+}
+
+impl GrowthCurve {
+    /// Number of reallocations that moved the data
+    pub fn moves(&self) -> usize {
+        self.events.iter().filter(|e| e.moved).count()
+    }
+
+    /// Whether every capacity change followed std's amortized rule
+    pub fn matches_rule(&self) -> bool {
+        self.events.iter().all(GrowthEvent::matches_rule)
+    }
+
+    /// Capacity after the last push
+    pub fn final_capacity(&self) -> usize {
+        self.samples.last().map_or(0, |&(_, capacity)| capacity)
+    }
+}
+
+/// Pushes `chunk` bytes at a time into an empty `String` until it holds
+/// `target` bytes, recording every capacity change
+pub fn push_curve(target: usize, chunk: usize) -> GrowthCurve {
+    let chunk = chunk.max(1);
+    let bytes = "x".repeat(chunk);
+    let mut s = String::new();
+    let mut samples = Vec::new();
+    let mut events = Vec::new();
+
+    while s.len() < target {
+        let (old_capacity, old_ptr) = (s.capacity(), s.as_ptr());
+        let piece = &bytes[..chunk.min(target - s.len())];
+        s.push_str(piece);
+
+        if s.capacity() != old_capacity {
+            events.push(GrowthEvent {
+                push: samples.len() + 1,
+                len: s.len(),
+                old_capacity,
+                new_capacity: s.capacity(),
+                expected_capacity: amortized_capacity(old_capacity, s.len()),
+                // The empty string's dangling pointer never "moves" data
+                moved: old_capacity > 0 && s.as_ptr() != old_ptr,
+            });
+        }
+        samples.push((s.len(), s.capacity()));
+    }
+
+    GrowthCurve {
+        chunk,
+        target,
+        pushes: samples.len(),
+        samples,
+        events,
+    }
+}
+
+/// An explicit capacity operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityOp {
+    PushStr(usize),
+    Reserve(usize),
+    ReserveExact(usize),
+    ShrinkTo(usize),
+    ShrinkToFit,
+}
+
+impl CapacityOp {
+    /// The capacity std's documented behavior leads to
+    pub fn expected_capacity(&self, len: usize, capacity: usize) -> usize {
+        let fits = |extra: usize| capacity - len >= extra;
+        match *self {
+            CapacityOp::PushStr(n) | CapacityOp::Reserve(n) if fits(n) => capacity,
+            CapacityOp::PushStr(n) | CapacityOp::Reserve(n) => {
+                amortized_capacity(capacity, len + n)
+            }
+            CapacityOp::ReserveExact(n) if fits(n) => capacity,
+            CapacityOp::ReserveExact(n) => len + n,
+            CapacityOp::ShrinkTo(min) => capacity.min(len.max(min)),
+            CapacityOp::ShrinkToFit => len,
+        }
+    }
+
+    fn apply(&self, s: &mut String) {
+        match *self {
+            CapacityOp::PushStr(n) => s.push_str(&"x".repeat(n)),
+            CapacityOp::Reserve(n) => s.reserve(n),
+            CapacityOp::ReserveExact(n) => s.reserve_exact(n),
+            CapacityOp::ShrinkTo(min) => s.shrink_to(min),
+            CapacityOp::ShrinkToFit => s.shrink_to_fit(),
+        }
+    }
+}
+
+impl std::fmt::Display for CapacityOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CapacityOp::PushStr(n) => write!(f, "push_str({} bytes)", n),
+            CapacityOp::Reserve(n) => write!(f, "reserve({})", n),
+            CapacityOp::ReserveExact(n) => write!(f, "reserve_exact({})", n),
+            CapacityOp::ShrinkTo(min) => write!(f, "shrink_to({})", min),
+            CapacityOp::ShrinkToFit => write!(f, "shrink_to_fit()"),
+        }
+    }
+}
+
+/// One explicit capacity operation and its effect
+#[derive(Debug, Clone, Serialize)]
+pub struct CapacityStep {
+    pub op: CapacityOp,
+    /// Length after the operation
+    pub len: usize,
+    pub old_capacity: usize,
+    pub new_capacity: usize,
+    pub expected_capacity: usize,
+    pub moved: bool,
+}
+
+impl CapacityStep {
+    pub fn matches_rule(&self) -> bool {
+        self.new_capacity == self.expected_capacity
+    }
+}
+
+/// Applies `ops` in order to an empty `String`, recording each effect
+pub fn capacity_steps(ops: &[CapacityOp]) -> Vec<CapacityStep> {
+    let mut s = String::new();
+    ops.iter()
+        .map(|op| {
+            let (len, old_capacity, old_ptr) = (s.len(), s.capacity(), s.as_ptr());
+            op.apply(&mut s);
+            CapacityStep {
+                op: *op,
+                len: s.len(),
+                old_capacity,
+                new_capacity: s.capacity(),
+                expected_capacity: op.expected_capacity(len, old_capacity),
+                moved: old_capacity > 0 && s.capacity() > 0 && s.as_ptr() != old_ptr,
+            }
+        })
+        .collect()
+}
@@ -4,47 +4,105 @@
 //! It reveals the hidden memory layout, allocation details, and internal
 //! representation of various string types.
 
+use crate::allocator::{self, AllocStats};
+use crate::grapheme;
+use crate::region::{MemoryMap, MemoryRegion};
+use crate::theme;
 use colored::Colorize;
+use serde::Serialize;
 use std::borrow::Cow;
+use std::ffi::{CString, OsString};
 use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
 
 /// Represents detailed memory information about a string
-#[derive(Debug, Clone)]
+///
+/// Every string type is a *handle* - the `String` struct, the `&str` fat
+/// pointer, the `Box` or `Rc` - pointing at its *data*. The two live in
+/// different places: a local handle is on the stack frame that declared it,
+/// while the data may be on the heap, in the binary, or anywhere else.
+#[derive(Debug, Clone, Serialize)]
 pub struct StringMemoryInfo {
-    /// The memory address where the string data lives (heap pointer)
+    /// Where the handle itself lives
+    pub handle_ptr: usize,
+    /// Size of the handle in bytes, e.g. 24 for `String`
+    pub handle_size: usize,
+    /// The memory region the handle lives in
+    pub handle_region: MemoryRegion,
+    /// Where the string data (the pointee) lives
     pub data_ptr: usize,
-    /// The memory address of the String/&str object itself (stack location)
-    pub object_ptr: usize,
+    /// The memory region the data pointer points into
+    pub data_region: MemoryRegion,
     /// Current length in bytes
     pub length: usize,
-    /// Allocated capacity in bytes (heap allocation size)
+    /// Capacity in bytes: what the type asked the allocator for
     pub capacity: usize,
-    /// Whether this string is heap-allocated
-    pub is_heap_allocated: bool,
+    /// Usable size of the heap block the allocator really reserved, which
+    /// may be more than the capacity; `None` when the handle owns no heap
+    /// buffer or the platform cannot tell
+    pub block_size: Option<usize>,
     /// Human-readable description
     pub description: String,
 }
 
+impl StringMemoryInfo {
+    /// Bytes reserved but not holding string data: spare capacity, plus the
+    /// allocator's rounding when the block size is known
+    pub fn wasted_bytes(&self) -> usize {
+        self.block_size
+            .unwrap_or(self.capacity)
+            .saturating_sub(self.length)
+    }
+
+    fn wasted_space(&self) -> String {
+        let spare = self.capacity.saturating_sub(self.length);
+        match self.block_size {
+            Some(block) if block > self.capacity => format!(
+                "{} bytes ({} spare capacity + {} allocator rounding)",
+                self.wasted_bytes(),
+                spare,
+                block - self.capacity
+            ),
+            _ => format!("{} bytes", spare),
+        }
+    }
+}
+
 impl fmt::Display for StringMemoryInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let single = theme::glyphs().single;
+        let (top, side, bottom) = (
+            format!("{}{}", single.top_left, single.horizontal),
+            single.vertical,
+            format!("{}{}", single.bottom_left, single.horizontal),
+        );
         write!(
             f,
             "{}",
             format!(
-                "┌─ String Memory Layout\n\
-                 │ Object Location (stack): {:#x}\n\
-                 │ Data Location   (heap):  {:#x}\n\
-                 │ Length:                  {} bytes\n\
-                 │ Capacity:                {} bytes\n\
-                 │ Heap Allocated:          {}\n\
-                 │ Wasted Space:            {} bytes\n\
-                 └─ {}",
-                self.object_ptr,
+                "{top} String Memory Layout\n\
+                 {side} Handle Location:         {:#x}\n\
+                 {side} Handle Region:           {} ({} bytes)\n\
+                 {side} Data Location:           {:#x}\n\
+                 {side} Data Region:             {}\n\
+                 {side} Length:                  {} bytes\n\
+                 {side} Capacity:                {} bytes\n\
+                 {}\
+                 {side} Wasted Space:            {}\n\
+                 {bottom} {}",
+                self.handle_ptr,
+                self.handle_region,
+                self.handle_size,
                 self.data_ptr,
+                self.data_region,
                 self.length,
                 self.capacity,
-                if self.is_heap_allocated { "Yes ✓" } else { "No ✗" },
-                self.capacity.saturating_sub(self.length),
+                self.block_size
+                    .map(|size| format!("{side} Allocator Block:         {} bytes\n", size))
+                    .unwrap_or_default(),
+                self.wasted_space(),
                 self.description
             )
             .cyan()
@@ -52,175 +110,653 @@ impl fmt::Display for StringMemoryInfo {
     }
 }
 
-/// Inspects a `String` and returns detailed memory information
+/// A string-like value whose memory layout can be inspected
 ///
-/// # How it works:
-/// - `String` in Rust is a struct with three fields: ptr, len, cap
-/// - The object itself lives on the stack (24 bytes on 64-bit systems)
-/// - The actual character data lives on the heap
-/// - We use raw pointers to examine the memory layout
-pub fn inspect_string(s: &String, description: &str) -> StringMemoryInfo {
-    StringMemoryInfo {
-        // Pointer to the heap-allocated buffer
-        data_ptr: s.as_ptr() as usize,
-        // Pointer to the String object on the stack
-        object_ptr: s as *const String as usize,
-        length: s.len(),
-        capacity: s.capacity(),
-        is_heap_allocated: true,
-        description: description.to_string(),
-    }
-}
-
-/// Inspects a string slice `&str`
+/// Implementors describe where their bytes live; the provided
+/// [`inspect`](Inspect::inspect) method turns that into a
+/// [`StringMemoryInfo`]. Adding support for a new type means implementing
+/// this trait - everything built on top (comparisons, demos) works with it
+/// automatically.
 ///
-/// # How it works:
-/// - `&str` is a "fat pointer": contains both data pointer and length
-/// - It's 16 bytes on 64-bit systems (8 bytes ptr + 8 bytes len)
-/// - The data it points to could be anywhere: stack, heap, or static memory
-/// - String literals live in the binary's read-only data section
-pub fn inspect_str(s: &str, description: &str) -> StringMemoryInfo {
-    // Determine if this is a string literal (in static memory) or elsewhere
-    let is_static = is_static_str(s);
-
-    StringMemoryInfo {
-        data_ptr: s.as_ptr() as usize,
-        object_ptr: s as *const str as *const () as usize,
-        length: s.len(),
-        capacity: s.len(), // &str has no separate capacity
-        is_heap_allocated: !is_static,
-        description: format!(
-            "{} | Location: {}",
+/// # Safety
+///
+/// [`heap_allocation`](Inspect::heap_allocation) must return `None`, or the
+/// start of a live allocation made by the global allocator that stays live
+/// while `self` is borrowed: [`inspect`](Inspect::inspect) asks the
+/// allocator for the size of that block.
+pub unsafe trait Inspect {
+    /// Name of the inspected type, e.g. `Box<str>`
+    fn type_name(&self) -> &'static str;
+
+    /// Pointer to the first byte of the string data
+    fn data_ptr(&self) -> *const u8;
+
+    /// Length of the string data in bytes
+    fn byte_len(&self) -> usize;
+
+    /// Bytes available without reallocating (defaults to the length)
+    fn byte_capacity(&self) -> usize {
+        self.byte_len()
+    }
+
+    /// Extra type-specific detail appended to the description
+    fn detail(&self) -> Option<String> {
+        None
+    }
+
+    /// Start of the heap buffer this handle owns, if it owns one
+    ///
+    /// See the trait's safety contract.
+    fn heap_allocation(&self) -> Option<*const u8> {
+        None
+    }
+
+    /// Inspects the value and returns detailed memory information
+    ///
+    /// # How it works:
+    /// - `self` is the handle itself (e.g. the 24-byte `String` on the stack)
+    /// - `data_ptr()` is where the character data lives
+    /// - We use raw pointers to examine the memory layout, and the process's
+    ///   memory map to classify the regions the handle and the data live in
+    ///
+    /// Call it on the handle you mean: `text.inspect(..)` on a local `&str`
+    /// reports that local, while `s.as_str().inspect(..)` reports a
+    /// temporary fat pointer.
+    fn inspect(&self, description: &str) -> StringMemoryInfo {
+        let mut description = format!("{} | Type: {}", description, self.type_name());
+        if let Some(detail) = self.detail() {
+            description.push_str(" | ");
+            description.push_str(&detail);
+        }
+
+        let handle_ptr = self as *const Self as *const () as usize;
+        let data_ptr = self.data_ptr() as usize;
+        let map = MemoryMap::current();
+        StringMemoryInfo {
+            handle_ptr,
+            handle_size: std::mem::size_of_val(self),
+            handle_region: map.classify(handle_ptr),
+            data_ptr,
+            data_region: map.classify(data_ptr),
+            length: self.byte_len(),
+            capacity: self.byte_capacity(),
+            // SAFETY: the trait's contract - `heap_allocation` returns only
+            // live allocations from the global allocator
+            block_size: self
+                .heap_allocation()
+                .and_then(|ptr| unsafe { allocator::usable_size(ptr) }),
             description,
-            if is_static { "Static (Binary)" } else { "Dynamic" }
-        ),
+        }
     }
 }
 
-/// Inspects a `Box<str>` - heap-allocated, immutable string
+/// A captured snapshot inspects as itself
 ///
-/// # How it works:
-/// - `Box<str>` is like `&str` but owns its data on the heap
-/// - No extra capacity (unlike String) - exactly sized
-/// - More memory efficient than String when size is fixed
-pub fn inspect_boxed_str(s: &Box<str>, description: &str) -> StringMemoryInfo {
-    StringMemoryInfo {
-        data_ptr: s.as_ptr() as usize,
-        object_ptr: s.as_ref() as *const str as *const () as usize,
-        length: s.len(),
-        capacity: s.len(),
-        is_heap_allocated: true,
-        description: format!("{} | Type: Box<str> (immutable)", description),
-    }
-}
-
-/// Inspects a `Cow<str>` - Clone on Write smart pointer
+/// This lets comparisons mix live values with snapshots taken before a
+/// mutation (the only way to compare a value against its former self).
+unsafe impl Inspect for StringMemoryInfo {
+    fn type_name(&self) -> &'static str {
+        "StringMemoryInfo"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.data_ptr as *const u8
+    }
+
+    fn byte_len(&self) -> usize {
+        self.length
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn inspect(&self, _description: &str) -> StringMemoryInfo {
+        self.clone()
+    }
+}
+
+/// `String` in Rust is a struct with three fields: ptr, len, cap
+///
+/// The handle itself lives on the stack (24 bytes on 64-bit systems) and
+/// the actual character data lives on the heap.
+unsafe impl Inspect for String {
+    fn type_name(&self) -> &'static str {
+        "String"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        (self.capacity() > 0).then(|| self.data_ptr())
+    }
+}
+
+/// `&str` is a "fat pointer": contains both data pointer and length
 ///
-/// # How it works:
-/// - Cow can be either Borrowed or Owned
-/// - Borrowed: just wraps a &str (no allocation)
-/// - Owned: wraps a String (heap allocated)
-/// - Delays allocation until mutation is needed
-pub fn inspect_cow(s: &Cow<str>, description: &str) -> StringMemoryInfo {
-    let (is_owned, data_ptr, capacity) = match s {
-        Cow::Borrowed(str_ref) => {
-            (false, str_ref.as_ptr() as usize, str_ref.len())
+/// It's 16 bytes on 64-bit systems (8 bytes ptr + 8 bytes len). The data it
+/// points to could be anywhere: stack, heap, or static memory. String
+/// literals live in the binary's read-only data section.
+unsafe impl Inspect for &str {
+    fn type_name(&self) -> &'static str {
+        "&str"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// `Box<str>` is like `&str` but owns its data on the heap
+///
+/// No extra capacity (unlike String) - exactly sized, so it is more memory
+/// efficient than String when size is fixed.
+unsafe impl Inspect for Box<str> {
+    fn type_name(&self) -> &'static str {
+        "Box<str>"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some("immutable".to_string())
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        // An empty box holds a dangling pointer, not an allocation
+        (!self.is_empty()).then(|| self.data_ptr())
+    }
+}
+
+/// `Cow<str>` - Clone on Write smart pointer
+///
+/// Cow can be either Borrowed (just wraps a &str, no allocation) or Owned
+/// (wraps a String, heap allocated). It delays allocation until mutation is
+/// needed.
+unsafe impl Inspect for Cow<'_, str> {
+    fn type_name(&self) -> &'static str {
+        "Cow<str>"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        match self {
+            Cow::Borrowed(str_ref) => str_ref.len(),
+            Cow::Owned(string) => string.capacity(),
         }
-        Cow::Owned(string) => {
-            (true, string.as_ptr() as usize, string.capacity())
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some(format!(
+            "Cow: {}",
+            if matches!(self, Cow::Owned(_)) {
+                "Owned"
+            } else {
+                "Borrowed"
+            }
+        ))
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        match self {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(string) => string.heap_allocation(),
         }
-    };
-
-    StringMemoryInfo {
-        data_ptr,
-        object_ptr: s as *const Cow<str> as *const () as usize,
-        length: s.len(),
-        capacity,
-        is_heap_allocated: is_owned,
-        description: format!(
-            "{} | Cow: {}",
-            description,
-            if is_owned { "Owned" } else { "Borrowed" }
-        ),
     }
 }
 
-/// Attempts to determine if a &str points to static memory
+/// `Rc<str>` - single-threaded shared ownership
 ///
-/// This is a heuristic - we check if the pointer is in a "reasonable"
-/// static range. This works for string literals in the binary.
-fn is_static_str(s: &str) -> bool {
-    let ptr = s.as_ptr() as usize;
-    // String literals typically live in low memory addresses
-    // This is platform-dependent but works for demonstration
-    ptr < 0x600000000000
+/// The bytes live in one heap allocation right after the reference counts.
+unsafe impl Inspect for Rc<str> {
+    fn type_name(&self) -> &'static str {
+        "Rc<str>"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some(format!(
+            "strong: {}, weak: {}",
+            Rc::strong_count(self),
+            Rc::weak_count(self)
+        ))
+    }
+}
+
+/// `Arc<str>` - thread-safe shared ownership with atomic reference counts
+unsafe impl Inspect for Arc<str> {
+    fn type_name(&self) -> &'static str {
+        "Arc<str>"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some(format!(
+            "strong: {}, weak: {}",
+            Arc::strong_count(self),
+            Arc::weak_count(self)
+        ))
+    }
+}
+
+/// `Vec<u8>` - the growable byte buffer that `String` is built on
+unsafe impl Inspect for Vec<u8> {
+    fn type_name(&self) -> &'static str {
+        "Vec<u8>"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn detail(&self) -> Option<String> {
+        std::str::from_utf8(self)
+            .err()
+            .map(|e| format!("not UTF-8 after {} bytes", e.valid_up_to()))
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        (self.capacity() > 0).then(|| self.data_ptr())
+    }
+}
+
+/// `CString` - an owned, nul-terminated string for C interop
+///
+/// The length excludes the trailing nul, but the allocation includes it.
+unsafe impl Inspect for CString {
+    fn type_name(&self) -> &'static str {
+        "CString"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_ptr() as *const u8
+    }
+
+    fn byte_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.as_bytes_with_nul().len()
+    }
+
+    fn detail(&self) -> Option<String> {
+        Some("+1 byte nul terminator".to_string())
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        // Even an empty `CString` allocates its nul terminator
+        Some(self.data_ptr())
+    }
+}
+
+/// `OsString` - an owned string in the platform's native encoding
+unsafe impl Inspect for OsString {
+    fn type_name(&self) -> &'static str {
+        "OsString"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_encoded_bytes().as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn detail(&self) -> Option<String> {
+        self.to_str()
+            .is_none()
+            .then(|| "not valid Unicode".to_string())
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        (self.capacity() > 0).then(|| self.data_ptr())
+    }
+}
+
+/// `PathBuf` - an owned filesystem path, backed by an `OsString`
+unsafe impl Inspect for PathBuf {
+    fn type_name(&self) -> &'static str {
+        "PathBuf"
+    }
+
+    fn data_ptr(&self) -> *const u8 {
+        self.as_os_str().as_encoded_bytes().as_ptr()
+    }
+
+    fn byte_len(&self) -> usize {
+        self.as_os_str().len()
+    }
+
+    fn byte_capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn heap_allocation(&self) -> Option<*const u8> {
+        (self.capacity() > 0).then(|| self.data_ptr())
+    }
+}
+
+/// The result of comparing the memory layout of a string before and after
+/// an operation
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutComparison {
+    /// Name of the operation that was performed
+    pub operation: String,
+    /// Memory layout before the operation
+    pub before: StringMemoryInfo,
+    /// Memory layout after the operation
+    pub after: StringMemoryInfo,
+    /// Whether the data pointer moved to a new location
+    pub ptr_changed: bool,
+    /// Whether the handle is at a different location
+    pub handle_changed: bool,
+    /// Whether the capacity changed
+    pub capacity_changed: bool,
+    /// Whether the length changed
+    pub length_changed: bool,
+    /// Allocator activity during the operation, if it was tracked
+    pub allocations: Option<AllocStats>,
+}
+
+impl LayoutComparison {
+    /// Analyzes the difference between two memory layouts
+    ///
+    /// Either side may be a live value or a previously captured
+    /// [`StringMemoryInfo`] snapshot. Nothing is tracked here, so the
+    /// comparison has no allocator activity until
+    /// [`with_allocations`](Self::with_allocations) attaches it; use
+    /// [`measure`](Self::measure) to run and track the operation as well.
+    pub fn between<A, B>(before: &A, after: &B, operation: &str) -> Self
+    where
+        A: Inspect + ?Sized,
+        B: Inspect + ?Sized,
+    {
+        let before = before.inspect("Before");
+        let after = after.inspect("After");
+        Self {
+            operation: operation.to_string(),
+            ptr_changed: before.data_ptr != after.data_ptr,
+            handle_changed: before.handle_ptr != after.handle_ptr,
+            capacity_changed: before.capacity != after.capacity,
+            length_changed: before.length != after.length,
+            before,
+            after,
+            allocations: None,
+        }
+    }
+
+    /// Runs `f` on `value`, comparing its layout before and after along with
+    /// the allocator activity `f` caused
+    ///
+    /// Both snapshots are inspected with `description`. Only `f` is tracked,
+    /// not the inspections around it.
+    pub fn measure<T, R>(
+        value: &mut T,
+        description: &str,
+        operation: &str,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> (R, Self)
+    where
+        T: Inspect + ?Sized,
+    {
+        let before = value.inspect(description);
+        let (result, allocations) = allocator::track_if_installed(|| f(value));
+        let after = value.inspect(description);
+        let comparison = Self::between(&before, &after, operation).with_allocations(allocations);
+        (result, comparison)
+    }
+
+    /// Attaches the allocator activity measured during the operation
+    pub fn with_allocations(mut self, allocations: Option<AllocStats>) -> Self {
+        self.allocations = allocations;
+        self
+    }
+
+    /// Signed change in length, in bytes
+    pub fn length_delta(&self) -> i64 {
+        self.after.length as i64 - self.before.length as i64
+    }
+
+    /// Prints a beautiful before/after comparison with analysis
+    pub fn print(&self) {
+        let (info1, info2) = (&self.before, &self.after);
+        let glyphs = theme::glyphs();
+        let (double, arrow, pointer) = (glyphs.double, glyphs.arrow, glyphs.pointer);
+        let rule = double.horizontal.repeat(3);
+
+        println!(
+            "\n{}",
+            format!(
+                "{}{} {} {}{}",
+                double.top_left, rule, self.operation, rule, double.top_right
+            )
+            .bright_yellow()
+            .bold()
+        );
+
+        println!("\n{}", "BEFORE:".bright_green().bold());
+        println!("{}", info1);
+
+        println!("\n{}", "AFTER:".bright_magenta().bold());
+        println!("{}", info2);
+
+        println!("\n{}", "ANALYSIS:".bright_blue().bold());
+
+        if self.ptr_changed {
+            println!(
+                "  {} Data was {} - NEW heap allocation!",
+                pointer.bright_red(),
+                "MOVED".bright_red().bold()
+            );
+            println!("    Old address: {:#x}", info1.data_ptr);
+            println!("    New address: {:#x}", info2.data_ptr);
+        } else {
+            println!(
+                "  {} Data pointer {} - modified in-place",
+                pointer.bright_green(),
+                "UNCHANGED".bright_green().bold()
+            );
+        }
+
+        if self.handle_changed {
+            println!(
+                "  {} Handle at a new location: {:#x} {} {:#x}",
+                pointer.bright_cyan(),
+                info1.handle_ptr,
+                arrow,
+                info2.handle_ptr
+            );
+        }
+
+        if self.capacity_changed {
+            println!(
+                "  {} Capacity changed: {} {} {} bytes",
+                pointer.bright_yellow(),
+                info1.capacity,
+                arrow,
+                info2.capacity
+            );
+            if info2.capacity > info1.capacity {
+                println!(
+                    "    Reallocation occurred (grew by {} bytes)",
+                    info2.capacity - info1.capacity
+                );
+            }
+        }
+
+        if self.length_changed {
+            println!(
+                "  {} Length changed: {} {} {} bytes ({:+})",
+                pointer.bright_cyan(),
+                info1.length,
+                arrow,
+                info2.length,
+                self.length_delta()
+            );
+        }
+
+        if let Some(allocations) = &self.allocations {
+            println!("  {} Allocator: {}", pointer.bright_magenta(), allocations);
+        }
+
+        println!(
+            "{}",
+            format!(
+                "{}{}{}",
+                double.bottom_left,
+                double.horizontal.repeat(27),
+                double.bottom_right
+            )
+            .bright_yellow()
+            .bold()
+        );
+    }
 }
 
 /// Prints a beautiful comparison of memory layout between two strings
-pub fn compare_memory_layout(
-    info1: &StringMemoryInfo,
-    info2: &StringMemoryInfo,
-    operation: &str,
-) {
-    println!("\n{}", format!("╔═══ {} ═══╗", operation).bright_yellow().bold());
-
-    println!("\n{}", "BEFORE:".bright_green().bold());
-    println!("{}", info1);
-
-    println!("\n{}", "AFTER:".bright_magenta().bold());
-    println!("{}", info2);
-
-    // Analysis
-    let ptr_changed = info1.data_ptr != info2.data_ptr;
-    let capacity_changed = info1.capacity != info2.capacity;
-    let length_changed = info1.length != info2.length;
-
-    println!("\n{}", "ANALYSIS:".bright_blue().bold());
-
-    if ptr_changed {
-        println!("  {} Data was {} - NEW heap allocation!",
-            "➜".bright_red(),
-            "MOVED".bright_red().bold());
-        println!("    Old address: {:#x}", info1.data_ptr);
-        println!("    New address: {:#x}", info2.data_ptr);
-    } else {
-        println!("  {} Data pointer {} - modified in-place",
-            "➜".bright_green(),
-            "UNCHANGED".bright_green().bold());
-    }
-
-    if capacity_changed {
-        println!("  {} Capacity changed: {} → {} bytes",
-            "➜".bright_yellow(),
-            info1.capacity,
-            info2.capacity);
-        if info2.capacity > info1.capacity {
-            println!("    Reallocation occurred (grew by {} bytes)",
-                info2.capacity - info1.capacity);
+///
+/// Works with any pair of inspectable values or snapshots. Allocator
+/// activity is not tracked; print a [`LayoutComparison::measure`]d
+/// comparison to show it.
+pub fn compare_memory_layout<A, B>(info1: &A, info2: &B, operation: &str)
+where
+    A: Inspect + ?Sized,
+    B: Inspect + ?Sized,
+{
+    LayoutComparison::between(info1, info2, operation).print();
+}
+
+/// The byte-level representation of a string
+#[derive(Debug, Clone, Serialize)]
+pub struct ByteReport {
+    /// Label describing the string
+    pub label: String,
+    /// The raw UTF-8 bytes
+    pub bytes: Vec<u8>,
+    /// The decoded Unicode scalar values
+    pub chars: Vec<char>,
+    /// The extended grapheme clusters (user-perceived characters)
+    pub graphemes: Vec<String>,
+}
+
+impl ByteReport {
+    /// Captures the byte-level representation of `s`
+    pub fn new(s: &str, label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            bytes: s.as_bytes().to_vec(),
+            chars: s.chars().collect(),
+            graphemes: grapheme::graphemes(s).map(str::to_string).collect(),
         }
     }
 
-    if length_changed {
-        println!("  {} Length changed: {} → {} bytes (Δ {})",
-            "➜".bright_cyan(),
-            info1.length,
-            info2.length,
-            info2.length as i64 - info1.length as i64);
+    /// Number of UTF-8 bytes
+    pub fn byte_count(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Number of Unicode scalar values
+    pub fn char_count(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Number of extended grapheme clusters
+    pub fn grapheme_count(&self) -> usize {
+        self.graphemes.len()
     }
 
-    println!("{}", "╚═══════════════════════════╝".bright_yellow().bold());
+    /// Whether any character needs more than one byte
+    pub fn has_multibyte(&self) -> bool {
+        self.byte_count() != self.char_count()
+    }
+
+    /// Prints the byte-level representation
+    pub fn print(&self) {
+        let warning = theme::glyphs().warning;
+        println!(
+            "\n{} {}",
+            theme::icon("📊").bright_yellow(),
+            self.label.bright_white().bold()
+        );
+        println!("  UTF-8 bytes: {:?}", self.bytes);
+        println!("  Characters:  {:?}", self.chars);
+        println!("  Byte count:  {}", self.byte_count());
+        println!("  Char count:  {}", self.char_count());
+        println!("  Graphemes:   {:?}", self.graphemes);
+        println!("  Grapheme count: {}", self.grapheme_count());
+
+        if self.has_multibyte() {
+            println!(
+                "  {} Multi-byte UTF-8 characters detected!",
+                warning.bright_yellow()
+            );
+        }
+
+        if self.grapheme_count() != self.char_count() {
+            println!(
+                "  {} Some characters are built from several code points!",
+                warning.bright_yellow()
+            );
+        }
+    }
 }
 
 /// Displays the byte-level representation of a string
 pub fn display_bytes(s: &str, label: &str) {
-    println!("\n{} {}", "📊".bright_yellow(), label.bright_white().bold());
-    println!("  UTF-8 bytes: {:?}", s.as_bytes());
-    println!("  Characters:  {:?}", s.chars().collect::<Vec<_>>());
-    println!("  Byte count:  {}", s.len());
-    println!("  Char count:  {}", s.chars().count());
-
-    if s.len() != s.chars().count() {
-        println!("  {} Multi-byte UTF-8 characters detected!",
-            "⚠".bright_yellow());
-    }
+    ByteReport::new(s, label).print();
 }
@@ -0,0 +1,42 @@
+//! # Additional Lessons
+//!
+//! Demos registered after the built-in ones. A lesson is a type
+//! implementing [`Demo`](crate::registry::Demo); registering it here is all
+//! it takes for `lab list` to show it and `lab run NAME` or `--tag` to run
+//! it:
+//!
+//! ```ignore
+//! struct SmallStrings;
+//!
+//! impl Demo for SmallStrings {
+//!     fn name(&self) -> &'static str {
+//!         "small-strings"
+//!     }
+//!
+//!     fn description(&self) -> &'static str {
+//!         "Why an empty String never allocates"
+//!     }
+//!
+//!     fn tags(&self) -> &'static [&'static str] {
+//!         &["memory"]
+//!     }
+//!
+//!     fn run<'a>(&'a self, cx: &'a Context<'_>) -> LocalBoxFuture<'a, ()> {
+//!         Box::pin(async move {
+//!             let (empty, _stats) = cx.track(String::new);
+//!             cx.render.layout(&empty.inspect("String::new()"));
+//!         })
+//!     }
+//! }
+//!
+//! pub fn register(registry: &mut Registry) {
+//!     registry.register(SmallStrings);
+//! }
+//! ```
+//!
+//! Lessons can live in modules of their own below this one.
+
+use crate::registry::Registry;
+
+/// Adds the lessons to `registry`, after the built-in demos
+pub fn register(_registry: &mut Registry) {}
@@ -0,0 +1,82 @@
+//! # The Introspective String Laboratory
+//!
+//! A library for performing string manipulations while providing deep
+//! introspection into memory layout, ownership, and async behavior.
+//!
+//! ## Key Concepts Demonstrated:
+//! - `String` vs `&str` vs `Box<str>` vs `Cow<str>`
+//! - Stack vs heap allocation
+//! - Ownership, borrowing, and lifetimes
+//! - Capacity management and reallocation
+//! - Async/await with Tokio
+//! - Structured logging with tracing
+//! - Unicode and UTF-8 handling
+//!
+//! ## Architecture:
+//! - `allocator`: Opt-in counting global allocator with scoped tracking
+//! - `batch`: Concurrent batches with a concurrency limit, timeouts and cancellation
+//! - `case`: Full case mapping: folding, titlecase, context and tailoring
+//! - `grapheme`: UAX #29 extended grapheme cluster segmentation
+//! - `growth`: Capacity growth curves against std's amortized rule
+//! - `inspector`: Low-level memory introspection utilities
+//! - `lessons`: Lessons registered after the built-in demos
+//! - `lifetime`: Tracked strings recording construction, clones, moves and drops
+//! - `logging`: Log filter, format, destination and span events for the log layer
+//! - `normalize`: Unicode normalization forms with a quick-check fast path
+//! - `pipeline`: Channel pipelines of tasks tracing each string's buffer
+//! - `raw`: Raw header bytes and heap buffer hexdumps with a detected field order
+//! - `registry`: The `Demo` trait and the registry of demos `lab` runs
+//! - `region`: Classifies pointers using the process's memory map
+//! - `repl`: Interactive sessions applying `String` statements step by step
+//! - `span_tree`: Tracing layer merging spans into a tree with call counts and timings
+//! - `theme`: Color/plain and Unicode/ASCII rendering themes detected from the terminal
+//! - `trace_event`: Tracing layer exporting spans as Chrome trace events
+//! - `transformer`: Async string transformation operations
+//! - `word`: UAX #29 word boundaries
+//! - `workload`: File, TCP echo and `spawn_blocking` workloads reporting their copies
+//! - `bench`: Statistical benchmarking with warmup and outlier rejection
+//! - `demos`: The laboratory demonstrations, returning structured reports
+//! - `render`: Presentation of the demonstration reports as text or JSON records
+//!
+//! The `lab` binary is a thin command-line layer on top of this crate that
+//! runs demos, inspections, single transformations and the REPL and renders
+//! their reports.
+
+pub mod allocator;
+pub mod batch;
+pub mod bench;
+pub mod case;
+pub mod demos;
+pub mod grapheme;
+pub mod growth;
+pub mod inspector;
+pub mod lessons;
+pub mod lifetime;
+pub mod logging;
+pub mod normalize;
+pub mod pipeline;
+pub mod raw;
+pub mod region;
+pub mod registry;
+pub mod render;
+pub mod repl;
+pub mod span_tree;
+pub mod theme;
+pub mod trace_event;
+pub mod transformer;
+pub mod word;
+pub mod workload;
+
+pub use allocator::{track, AllocStats, CountingAllocator};
+pub use bench::{BenchConfig, Distribution};
+pub use case::{CaseLocale, CaseMapping};
+pub use inspector::{
+    compare_memory_layout, display_bytes, ByteReport, Inspect, LayoutComparison, StringMemoryInfo,
+};
+pub use lifetime::{Timeline, Tracked};
+pub use normalize::NormalizationForm;
+pub use region::MemoryRegion;
+pub use registry::{Demo, Registry};
+pub use render::{OutputFormat, Renderer};
+pub use theme::Theme;
+pub use transformer::{Operation, StringManipulator, TimedResult};
@@ -0,0 +1,376 @@
+//! # Lifetime Tracing
+//!
+//! The ownership demo says a move invalidates the old binding, but nothing
+//! shows when memory is actually freed. [`Tracked`] wraps a `String` and
+//! records its life as events: construction, clones, moves and the drop,
+//! each with the addresses of the handle and of the heap buffer.
+//!
+//! Rust has no hook for moves - a move is a plain copy of the handle's
+//! bytes. A `Tracked` notices one the next time it is used (or dropped)
+//! from a different address than before, so several moves in a row show up
+//! as one, and a move the compiler turns into a no-op does not show up at
+//! all.
+//!
+//! Events are logged through `tracing`. While a [`Timeline`] exists they are
+//! also kept in a process-wide log until it takes them, grouped into named
+//! scenes; without one nothing is kept, so tracking values costs no memory
+//! when nobody looks at their lives:
+//!
+//! ```ignore
+//! let mut timeline = Timeline::new();
+//! let original = Tracked::new("original", "Hello");
+//! let moved = original;
+//! println!("{}", moved.len()); // notices the move
+//! drop(moved);
+//! timeline.record("ownership");
+//! ```
+
+use crate::allocator;
+use serde::Serialize;
+use std::cell::Cell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tracing::debug;
+
+/// What happened to a tracked value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    /// Constructed from a `String`
+    Created,
+    /// Constructed by cloning the value with id `from`
+    Cloned { from: u64 },
+    /// The handle was found at a new address; it used to be at `from`
+    Moved { from: usize },
+    /// Dropped, freeing its buffer
+    Dropped,
+}
+
+/// One event in the life of a tracked value
+#[derive(Debug, Clone, Serialize)]
+pub struct LifetimeEvent {
+    /// Position among all recorded events
+    pub seq: u64,
+    /// The value's id, unique for the process
+    pub id: u64,
+    pub name: String,
+    #[serde(flatten)]
+    pub action: Action,
+    /// Address of the `String` handle; `None` until the value is first used
+    /// where it lives, since construction returns it by moving
+    pub handle: Option<usize>,
+    /// Address of the heap buffer
+    pub buffer: usize,
+}
+
+impl fmt::Display for LifetimeEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {} ", self.id, self.name)?;
+        match self.action {
+            Action::Created => write!(f, "created")?,
+            Action::Cloned { from } => write!(f, "cloned from #{}", from)?,
+            Action::Moved { from } => write!(f, "moved from {:#x}", from)?,
+            Action::Dropped => write!(f, "dropped")?,
+        }
+        match self.handle {
+            Some(handle) => write!(f, " | handle {:#x}", handle)?,
+            None => write!(f, " | handle -")?,
+        }
+        write!(f, " | buffer {:#x}", self.buffer)
+    }
+}
+
+/// Events not yet taken by a [`Timeline`]
+static EVENTS: Mutex<Vec<LifetimeEvent>> = Mutex::new(Vec::new());
+
+/// Number of live [`Timeline`]s; events are only kept while there is one
+static TIMELINES: AtomicUsize = AtomicUsize::new(0);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+fn record(id: u64, name: &str, action: Action, handle: Option<usize>, buffer: usize) {
+    // Recording is not part of whatever operation is being measured
+    allocator::untracked(|| {
+        debug!(
+            id,
+            name,
+            action = ?action,
+            handle = ?handle.map(|h| format!("{:#x}", h)),
+            buffer = format!("{:#x}", buffer),
+            "lifetime event"
+        );
+        let seq = NEXT_SEQ.fetch_add(1, Ordering::Relaxed);
+        if TIMELINES.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let event = LifetimeEvent {
+            seq,
+            id,
+            name: name.to_string(),
+            action,
+            handle,
+            buffer,
+        };
+        EVENTS.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+    });
+}
+
+/// Takes every event recorded so far, in order
+pub fn take_events() -> Vec<LifetimeEvent> {
+    let mut events = EVENTS.lock().unwrap_or_else(|e| e.into_inner());
+    std::mem::take(&mut *events)
+}
+
+/// A `String` that records its construction, clones, moves and drop
+///
+/// Derefs to the `String`, so it can be read, mutated and inspected like
+/// one. Every access checks whether the handle has moved.
+pub struct Tracked {
+    value: String,
+    id: u64,
+    name: String,
+    /// Where the handle was last seen
+    handle: Cell<Option<usize>>,
+}
+
+impl Tracked {
+    /// Starts tracking `value` under `name`
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        let tracked = Self {
+            value: value.into(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name: name.into(),
+            handle: Cell::new(None),
+        };
+        record(
+            tracked.id,
+            &tracked.name,
+            Action::Created,
+            None,
+            tracked.buffer(),
+        );
+        tracked
+    }
+
+    /// The value's id, unique for the process
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn buffer(&self) -> usize {
+        self.value.as_ptr() as usize
+    }
+
+    /// Records a move if the handle is not where it was last seen
+    fn observe(&self) -> usize {
+        let here = &self.value as *const String as usize;
+        match self.handle.replace(Some(here)) {
+            Some(from) if from != here => record(
+                self.id,
+                &self.name,
+                Action::Moved { from },
+                Some(here),
+                self.buffer(),
+            ),
+            _ => {}
+        }
+        here
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        self.observe();
+        let cloned = Self {
+            value: self.value.clone(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name: allocator::untracked(|| self.name.clone()),
+            handle: Cell::new(None),
+        };
+        record(
+            cloned.id,
+            &cloned.name,
+            Action::Cloned { from: self.id },
+            None,
+            cloned.buffer(),
+        );
+        cloned
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        let here = self.observe();
+        record(
+            self.id,
+            &self.name,
+            Action::Dropped,
+            Some(here),
+            self.buffer(),
+        );
+    }
+}
+
+impl Deref for Tracked {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        self.observe();
+        &self.value
+    }
+}
+
+impl DerefMut for Tracked {
+    fn deref_mut(&mut self) -> &mut String {
+        self.observe();
+        &mut self.value
+    }
+}
+
+impl AsRef<str> for Tracked {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl fmt::Display for Tracked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl fmt::Debug for Tracked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracked")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// The events recorded while one scene (such as a demo) ran
+#[derive(Debug, Clone, Serialize)]
+pub struct Scene {
+    pub name: String,
+    pub events: Vec<LifetimeEvent>,
+}
+
+/// A value's life from its first to its last event
+#[derive(Debug, Clone, Serialize)]
+pub struct Lifetime {
+    pub id: u64,
+    pub name: String,
+    /// Id of the value this one was cloned from
+    pub cloned_from: Option<u64>,
+    /// Scene the value was created in
+    pub born: String,
+    /// Scene the value was dropped in; `None` while it is alive
+    pub died: Option<String>,
+    /// Number of moves noticed
+    pub moves: usize,
+}
+
+/// Keeps events recorded for as long as the timeline holding it lives
+#[derive(Debug)]
+struct Recording;
+
+impl Recording {
+    fn start() -> Self {
+        TIMELINES.fetch_add(1, Ordering::Relaxed);
+        Recording
+    }
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
+impl Clone for Recording {
+    fn clone(&self) -> Self {
+        Self::start()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if TIMELINES.fetch_sub(1, Ordering::Relaxed) == 1 {
+            // No timeline is left to take them
+            *EVENTS.lock().unwrap_or_else(|e| e.into_inner()) = Vec::new();
+        }
+    }
+}
+
+/// Tracked values' events, grouped into scenes in the order they ran
+///
+/// Events are kept from the moment the first timeline is created until the
+/// last one is dropped.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Timeline {
+    pub scenes: Vec<Scene>,
+    #[serde(skip)]
+    _recording: Recording,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the events recorded since the last scene as the scene `name`
+    ///
+    /// A scene without events is left out.
+    pub fn record(&mut self, name: impl Into<String>) {
+        let events = take_events();
+        if !events.is_empty() {
+            self.scenes.push(Scene {
+                name: name.into(),
+                events,
+            });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Every value seen, in order of creation
+    pub fn lifetimes(&self) -> Vec<Lifetime> {
+        let mut lifetimes: Vec<Lifetime> = Vec::new();
+        for scene in &self.scenes {
+            for event in &scene.events {
+                let index = match lifetimes.iter().position(|l| l.id == event.id) {
+                    Some(index) => index,
+                    None => {
+                        lifetimes.push(Lifetime {
+                            id: event.id,
+                            name: event.name.clone(),
+                            cloned_from: None,
+                            born: scene.name.clone(),
+                            died: None,
+                            moves: 0,
+                        });
+                        lifetimes.len() - 1
+                    }
+                };
+                let lifetime = &mut lifetimes[index];
+                match event.action {
+                    Action::Created => {}
+                    Action::Cloned { from } => lifetime.cloned_from = Some(from),
+                    Action::Moved { .. } => lifetime.moves += 1,
+                    Action::Dropped => lifetime.died = Some(scene.name.clone()),
+                }
+            }
+        }
+        lifetimes
+    }
+}
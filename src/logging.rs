@@ -0,0 +1,191 @@
+//! # Logging Configuration
+//!
+//! Builds the `tracing_subscriber` layer that writes log lines, from a
+//! [`LogConfig`]:
+//!
+//! - **filter** - `EnvFilter` directives such as `info,introspective_strings::pipeline=debug`,
+//!   taken from the config, else from `RUST_LOG`, else `info`;
+//! - **format** - compact lines, multi-line pretty output or JSON objects;
+//! - **destination** - stderr by default, so logs never interleave with the
+//!   lab's own output on stdout; stdout, a file, or nowhere on request;
+//! - **span events** - which span lifecycle events are logged as lines of
+//!   their own. None by default: the span tree summarizes them instead;
+//! - **color** - ANSI styling for a terminal destination, following the
+//!   same `NO_COLOR` and `TERM` rules as the [`theme`](crate::theme).
+//!
+//! The filter applies to the log layer only, so layers added next to it -
+//! the span tree, the trace export - still see every span.
+//!
+//! ```ignore
+//! let config = LogConfig { format: LogFormat::Json, ..LogConfig::default() };
+//! tracing_subscriber::registry().with(config.layer()?).init();
+//! ```
+
+use crate::theme::{ColorChoice, Terminal};
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::Subscriber;
+use tracing_subscriber::filter::{EnvFilter, LevelFilter, ParseError};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Level logged when neither the config nor `RUST_LOG` sets directives
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// How log lines are formatted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One short line per event
+    #[default]
+    Compact,
+    /// Multi-line, indented events with their source locations
+    Pretty,
+    /// One JSON object per line
+    Json,
+}
+
+/// Where log lines go
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LogDestination {
+    /// Standard error, apart from the lab's output
+    #[default]
+    Stderr,
+    /// Standard output, interleaved with the lab's output
+    Stdout,
+    /// A file, created or truncated when logging starts
+    File(PathBuf),
+    /// Nowhere
+    Off,
+}
+
+/// Span lifecycle events logged as lines of their own
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpanEvents {
+    #[default]
+    None,
+    /// When a span is created
+    New,
+    /// When a span closes, with its busy and idle time
+    Close,
+    /// Every enter and exit
+    Active,
+    /// All of the above
+    Full,
+}
+
+impl SpanEvents {
+    fn fmt_span(self) -> FmtSpan {
+        match self {
+            SpanEvents::None => FmtSpan::NONE,
+            SpanEvents::New => FmtSpan::NEW,
+            SpanEvents::Close => FmtSpan::CLOSE,
+            SpanEvents::Active => FmtSpan::ACTIVE,
+            SpanEvents::Full => FmtSpan::FULL,
+        }
+    }
+}
+
+/// Why the log layer could not be built
+#[derive(Debug)]
+pub enum LogError {
+    /// The filter directives do not parse
+    Filter(ParseError),
+    /// The log file could not be created
+    File { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogError::Filter(error) => write!(f, "invalid log filter: {}", error),
+            LogError::File { path, source } => {
+                write!(f, "cannot create log file {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LogError::Filter(error) => Some(error),
+            LogError::File { source, .. } => Some(source),
+        }
+    }
+}
+
+/// How the lab logs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogConfig {
+    /// `EnvFilter` directives; `RUST_LOG` is used when `None`
+    pub directives: Option<String>,
+    pub format: LogFormat,
+    pub destination: LogDestination,
+    pub span_events: SpanEvents,
+    /// Whether log lines are styled; never in a file
+    pub color: ColorChoice,
+}
+
+impl LogConfig {
+    /// The filter for log lines: the directives, else `RUST_LOG`, with
+    /// [`DEFAULT_LEVEL`] for everything they leave out
+    pub fn filter(&self) -> Result<EnvFilter, LogError> {
+        let directives = match &self.directives {
+            Some(directives) => directives.clone(),
+            None => std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_default(),
+        };
+        EnvFilter::builder()
+            .with_default_directive(DEFAULT_LEVEL.into())
+            .parse(&directives)
+            .map_err(LogError::Filter)
+    }
+
+    /// Builds the filtered layer writing log lines, or `None` when logging
+    /// is off
+    ///
+    /// The directives are checked first, whatever the destination.
+    pub fn layer<S>(&self) -> Result<Option<Box<dyn Layer<S> + Send + Sync>>, LogError>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        // Parsed before the destination is opened, so a bad directive
+        // leaves an existing log file alone
+        let filter = self.filter()?;
+        let (writer, ansi) = match &self.destination {
+            LogDestination::Off => return Ok(None),
+            LogDestination::Stderr => (
+                BoxMakeWriter::new(io::stderr),
+                Terminal::of(&io::stderr()).color(self.color),
+            ),
+            LogDestination::Stdout => (
+                BoxMakeWriter::new(io::stdout),
+                Terminal::of(&io::stdout()).color(self.color),
+            ),
+            LogDestination::File(path) => {
+                let file = File::create(path).map_err(|source| LogError::File {
+                    path: path.clone(),
+                    source,
+                })?;
+                (BoxMakeWriter::new(Mutex::new(file)), false)
+            }
+        };
+
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(ansi)
+            .with_span_events(self.span_events.fmt_span())
+            .with_target(false)
+            .with_thread_ids(true)
+            .with_line_number(true);
+        Ok(Some(match self.format {
+            LogFormat::Compact => layer.compact().with_filter(filter).boxed(),
+            LogFormat::Pretty => layer.pretty().with_filter(filter).boxed(),
+            LogFormat::Json => layer.json().with_filter(filter).boxed(),
+        }))
+    }
+}
@@ -1,457 +1,617 @@
 //! # The Introspective String Laboratory
 //!
-//! An educational Rust program that performs string manipulations while
-//! providing deep introspection into memory layout, ownership, and async behavior.
+//! The `lab` binary: sets up structured logging, parses the command line and
+//! runs demonstrations, inspections or single transformations from the
+//! `introspective_strings` library, rendering their reports.
 //!
-//! ## Key Concepts Demonstrated:
-//! - String vs &str vs Box<str> vs Cow<str>
-//! - Stack vs heap allocation
-//! - Ownership, borrowing, and lifetimes
-//! - Capacity management and reallocation
-//! - Async/await with Tokio
-//! - Structured logging with tracing
-//! - Unicode and UTF-8 handling
-//!
-//! ## Architecture:
-//! - `inspector`: Low-level memory introspection utilities
-//! - `transformer`: Async string transformation operations
-//! - `main`: Orchestrates demonstrations with rich logging
-
-mod inspector;
-mod transformer;
-
-use colored::Colorize;
-use inspector::*;
-use std::borrow::Cow;
-use tokio::task;
-use tracing::{debug, error, info, warn, Level};
-use tracing_subscriber::fmt::format::FmtSpan;
-use transformer::*;
-
-/// Prints a fancy section header
-fn print_section(title: &str) {
-    println!("\n\n");
-    println!(
-        "{}",
-        format!("╔═══════════════════════════════════════════════════════════╗")
-            .bright_cyan()
-            .bold()
-    );
-    println!(
-        "{}",
-        format!("║  {:<56} ║", title)
-            .bright_cyan()
-            .bold()
-    );
-    println!(
-        "{}",
-        format!("╚═══════════════════════════════════════════════════════════╝")
-            .bright_cyan()
-            .bold()
-    );
+//! Without a subcommand it runs every demonstration, like `lab run`.
+
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use introspective_strings::demos::{self, DemoInputs};
+use introspective_strings::lifetime::Timeline;
+use introspective_strings::logging::{LogConfig, LogDestination, LogFormat, SpanEvents};
+use introspective_strings::pipeline::Stage;
+use introspective_strings::registry::{Context, Demo, Registry};
+use introspective_strings::render::{OutputFormat, Renderer};
+use introspective_strings::repl::Session;
+use introspective_strings::span_tree::{SpanTree, SpanTreeLayer};
+use introspective_strings::theme::{Charset, ColorChoice, Theme};
+use introspective_strings::trace_event::TraceEventLayer;
+use introspective_strings::{
+    BenchConfig, CaseLocale, CaseMapping, CountingAllocator, NormalizationForm, Operation,
+};
+use serde_json::json;
+use std::alloc::System;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::info;
+use tracing_subscriber::prelude::*;
+
+/// Counts every allocation so demos can report what each operation cost
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+#[derive(Parser)]
+#[command(name = "lab", version, about = "The Introspective String Laboratory")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// How reports are written (and logs, unless --log-format says otherwise)
+    #[arg(long, value_enum, global = true, default_value_t = Format::Text)]
+    format: Format,
+    /// Write the spans of the run to FILE as Chrome trace events, for
+    /// chrome://tracing or ui.perfetto.dev
+    #[arg(long, global = true, value_name = "FILE")]
+    trace: Option<PathBuf>,
+    /// When to color text output and logs [auto: a terminal, unless NO_COLOR
+    /// is set or TERM is dumb]
+    #[arg(long, value_enum, global = true, default_value_t = ColorArg::Auto)]
+    color: ColorArg,
+    /// Characters text output is decorated with [auto: Unicode on a terminal,
+    /// unless TERM is dumb]
+    #[arg(long, value_enum, global = true, default_value_t = CharsetArg::Auto)]
+    charset: CharsetArg,
+    #[command(flatten)]
+    log: LogArgs,
 }
 
-/// Demonstrates basic string types and their memory layout
-#[tracing::instrument]
-async fn demo_string_types() {
-    print_section("1. STRING TYPES AND MEMORY LAYOUT");
-
-    info!("Creating various string types...");
-
-    // String literal - stored in binary's read-only data section
-    let literal: &'static str = "Rust";
-    let info_literal = inspect_str(literal, "String literal (&'static str)");
-    println!("\n{}", info_literal);
-
-    // Owned String - heap allocated with capacity for growth
-    let owned = String::from("Rustacean");
-    let info_owned = inspect_string(&owned, "Owned String (heap-allocated)");
-    println!("\n{}", info_owned);
-
-    // Box<str> - heap allocated but immutable, no extra capacity
-    let boxed: Box<str> = "Ferris".into();
-    let info_boxed = inspect_boxed_str(&boxed, "Boxed str");
-    println!("\n{}", info_boxed);
-
-    // Demonstrate size differences
-    println!("\n{}", "📏 SIZE ANALYSIS:".bright_yellow().bold());
-    println!(
-        "  &str:      {} bytes (fat pointer: ptr + len)",
-        std::mem::size_of::<&str>()
-    );
-    println!(
-        "  String:    {} bytes (ptr + len + cap)",
-        std::mem::size_of::<String>()
-    );
-    println!(
-        "  Box<str>:  {} bytes (fat pointer: ptr + len)",
-        std::mem::size_of::<Box<str>>()
-    );
-    println!(
-        "  Cow<str>:  {} bytes (enum: tag + fat pointer)",
-        std::mem::size_of::<Cow<str>>()
-    );
-
-    display_bytes(literal, "Byte representation of 'Rust'");
+/// Values of `--color`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorArg {
+    Auto,
+    Always,
+    Never,
 }
 
-/// Demonstrates ownership, moves, and clones
-#[tracing::instrument]
-async fn demo_ownership() {
-    print_section("2. OWNERSHIP, MOVES, AND CLONES");
-
-    info!("Demonstrating ownership mechanics...");
-
-    let original = String::from("Hello, Rust!");
-    let info_original = inspect_string(&original, "Original String");
-    println!("\n{}", info_original);
-
-    // Move - transfers ownership, no copy
-    info!("⚠ About to MOVE the string...");
-    let moved = original;
-    // Note: `original` is now invalid - compiler prevents use
-    let info_moved = inspect_string(&moved, "After MOVE");
-
-    compare_memory_layout(&info_original, &info_moved, "MOVE Operation");
-
-    println!(
-        "\n{} The pointer addresses are identical!",
-        "💡 INSIGHT:".bright_green().bold()
-    );
-    println!("   Move is zero-cost - just transfers ownership.");
-    println!("   No data was copied, no new allocation happened.");
-
-    // Clone - creates a new heap allocation and copies data
-    info!("🔄 About to CLONE the string...");
-    let cloned = moved.clone();
-    let info_cloned = inspect_string(&cloned, "After CLONE");
-
-    compare_memory_layout(
-        &info_moved,
-        &info_cloned,
-        "CLONE Operation (allocation + copy)",
-    );
-
-    println!(
-        "\n{} The data pointers are different!",
-        "💡 INSIGHT:".bright_green().bold()
-    );
-    println!("   Clone created a NEW heap allocation.");
-    println!("   All bytes were copied to the new location.");
-    println!(
-        "   Cost: {} bytes allocated + {} bytes copied",
-        cloned.capacity(),
-        cloned.len()
-    );
+impl From<ColorArg> for ColorChoice {
+    fn from(color: ColorArg) -> Self {
+        match color {
+            ColorArg::Auto => ColorChoice::Auto,
+            ColorArg::Always => ColorChoice::Always,
+            ColorArg::Never => ColorChoice::Never,
+        }
+    }
 }
 
-/// Demonstrates capacity and reallocation
-#[tracing::instrument]
-async fn demo_capacity_and_growth() {
-    print_section("3. CAPACITY MANAGEMENT AND REALLOCATION");
-
-    info!("Exploring how String manages capacity...");
-
-    // Create with exact capacity
-    let mut s = String::with_capacity(8);
-    println!(
-        "\n{} Created String::with_capacity(8)",
-        "✓".bright_green()
-    );
-
-    let info_empty = inspect_string(&s, "Empty String with capacity 8");
-    println!("{}", info_empty);
-
-    // Add data within capacity
-    s.push_str("Rust");
-    let info_rust = inspect_string(&s, "After adding 'Rust' (4 bytes)");
-
-    compare_memory_layout(&info_empty, &info_rust, "Within Capacity Push");
-
-    // Exceed capacity - forces reallocation
-    warn!("⚠ About to exceed capacity - reallocation will occur!");
-
-    s.push_str("!!!!!"); // 5 more bytes = 9 total (exceeds 8)
-
-    let info_reallocated = inspect_string(&s, "After exceeding capacity");
-
-    compare_memory_layout(&info_rust, &info_reallocated, "Reallocation Triggered");
-
-    println!(
-        "\n{} Reallocation details:",
-        "💡 INSIGHT:".bright_green().bold()
-    );
-    println!("   Old capacity: {} bytes", info_rust.capacity);
-    println!("   New capacity: {} bytes", info_reallocated.capacity);
-    println!(
-        "   Growth strategy: typically doubles capacity"
-    );
-    println!("   Performance cost: O(n) copy of all existing data");
+/// Values of `--charset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CharsetArg {
+    Auto,
+    /// Emoji, arrows and box drawing
+    Unicode,
+    /// Plain ASCII only
+    Ascii,
 }
 
-/// Demonstrates Clone-on-Write (Cow) optimization
-#[tracing::instrument]
-async fn demo_clone_on_write() {
-    print_section("4. CLONE-ON-WRITE (COW) OPTIMIZATION");
-
-    info!("Demonstrating Cow<str> for efficient conditional ownership...");
-
-    let static_str = "Ferris the Crab";
-
-    // Cow starts borrowed - zero cost
-    let cow_borrowed: Cow<str> = Cow::Borrowed(static_str);
-    let info_borrowed = inspect_cow(&cow_borrowed, "Cow::Borrowed (zero-cost)");
-    println!("\n{}", info_borrowed);
+impl From<CharsetArg> for Charset {
+    fn from(charset: CharsetArg) -> Self {
+        match charset {
+            CharsetArg::Auto => Charset::Auto,
+            CharsetArg::Unicode => Charset::Unicode,
+            CharsetArg::Ascii => Charset::Ascii,
+        }
+    }
+}
 
-    println!(
-        "\n{} Cow::Borrowed points to the original string",
-        "✓".bright_green()
-    );
-    println!("   No allocation, no copy - just a reference");
+/// Logging options, shared by every subcommand
+#[derive(Args)]
+struct LogArgs {
+    /// Log filter directives, e.g. `debug` or `info,introspective_strings::pipeline=debug`
+    /// [default: $RUST_LOG, else info]
+    #[arg(long = "log", global = true, value_name = "DIRECTIVES")]
+    directives: Option<String>,
+    /// How log lines are formatted [default: json with --format json, else compact]
+    #[arg(long, value_enum, global = true)]
+    log_format: Option<LogFormatArg>,
+    /// Where log lines go; lab output always goes to stdout
+    #[arg(long, value_enum, global = true, default_value_t = LogTo::Stderr)]
+    log_to: LogTo,
+    /// Write log lines to FILE instead
+    #[arg(long, global = true, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+    /// Span lifecycle events to log as lines of their own
+    #[arg(long, value_enum, global = true, default_value_t = SpanEventsArg::None)]
+    span_events: SpanEventsArg,
+}
 
-    // Convert to owned when needed
-    let mut cow_owned = cow_borrowed.clone();
-    cow_owned.to_mut().push_str(" 🦀");
+/// Values of `--log-format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormatArg {
+    Compact,
+    Pretty,
+    Json,
+}
 
-    let info_owned = inspect_cow(&cow_owned, "Cow::Owned (after mutation)");
+/// Values of `--log-to`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogTo {
+    Stderr,
+    Stdout,
+    /// Do not log
+    Off,
+}
 
-    compare_memory_layout(
-        &info_borrowed,
-        &info_owned,
-        "Cow: Borrowed → Owned (lazy allocation)",
-    );
+/// Values of `--span-events`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SpanEventsArg {
+    None,
+    /// When a span is created
+    New,
+    /// When a span closes, with its busy and idle time
+    Close,
+    /// Every enter and exit
+    Active,
+    /// All of the above
+    Full,
+}
 
-    println!(
-        "\n{} Cow delayed allocation until mutation!",
-        "💡 INSIGHT:".bright_green().bold()
-    );
-    println!("   Use case: API that might or might not modify data");
-    println!("   Benefit: Zero cost when no modification needed");
+impl LogArgs {
+    fn config(self, format: Format, color: ColorChoice) -> LogConfig {
+        let format = match (self.log_format, format) {
+            (Some(LogFormatArg::Compact), _) | (None, Format::Text) => LogFormat::Compact,
+            (Some(LogFormatArg::Pretty), _) => LogFormat::Pretty,
+            (Some(LogFormatArg::Json), _) | (None, Format::Json) => LogFormat::Json,
+        };
+        let destination = match (self.log_file, self.log_to) {
+            (Some(path), _) => LogDestination::File(path),
+            (None, LogTo::Stderr) => LogDestination::Stderr,
+            (None, LogTo::Stdout) => LogDestination::Stdout,
+            (None, LogTo::Off) => LogDestination::Off,
+        };
+        let span_events = match self.span_events {
+            SpanEventsArg::None => SpanEvents::None,
+            SpanEventsArg::New => SpanEvents::New,
+            SpanEventsArg::Close => SpanEvents::Close,
+            SpanEventsArg::Active => SpanEvents::Active,
+            SpanEventsArg::Full => SpanEvents::Full,
+        };
+        LogConfig {
+            directives: self.directives,
+            format,
+            destination,
+            span_events,
+            color,
+        }
+    }
 }
 
-/// Demonstrates async string processing
-#[tracing::instrument]
-async fn demo_async_operations() {
-    print_section("5. ASYNCHRONOUS STRING PROCESSING");
+/// Values of `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Colorful text for the terminal
+    Text,
+    /// One JSON record per line, for post-processing
+    Json,
+}
 
-    info!("Spawning multiple async tasks...");
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Text => OutputFormat::Text,
+            Format::Json => OutputFormat::Json,
+        }
+    }
+}
 
-    let input = String::from("Async");
+#[derive(Subcommand)]
+enum Command {
+    /// List the available demos
+    List {
+        /// Only list demos with one of these tags; repeat for several
+        #[arg(long = "tag", value_parser = demo_tags())]
+        tags: Vec<String>,
+    },
+    /// Run demos in the given order (all of them when none is named)
+    Run {
+        #[arg(value_parser = demo_names())]
+        demos: Vec<String>,
+        /// Only run demos with one of these tags; repeat for several
+        #[arg(long = "tag", value_parser = demo_tags())]
+        tags: Vec<String>,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// Show how a string is laid out in memory by each string type
+    Inspect {
+        text: String,
+        /// Create the `String` with this capacity
+        #[arg(long)]
+        capacity: Option<usize>,
+    },
+    /// Experiment interactively: declare strings, mutate them and watch
+    /// their memory after every step
+    Repl {
+        /// Replay a saved session before reading new statements
+        script: Option<PathBuf>,
+    },
+    /// Apply one operation and show its result, cost and memory effect
+    Transform {
+        #[arg(value_enum)]
+        op: Transform,
+        text: String,
+        /// Second string for the interleave operations
+        #[arg(long, default_value = "")]
+        with: String,
+        /// Repeat count for `repeat`
+        #[arg(long, default_value_t = DemoInputs::default().repeat)]
+        repeat: usize,
+        /// Language whose casing rules apply, e.g. `tr` or `lt`
+        #[arg(long, default_value = "und")]
+        locale: String,
+        /// Benchmark with warmup and many samples instead of timing one run
+        #[arg(long)]
+        bench: bool,
+    },
+}
 
-    // Spawn multiple async tasks concurrently
-    let task1 = task::spawn(async_process_string(
-        input.clone(),
-        "Task 1: Database Fetch",
-    ));
+/// Inputs for `lab run`
+#[derive(Args)]
+struct InputArgs {
+    /// Sample text replacing the demos' own defaults
+    #[arg(long)]
+    text: Option<String>,
+    /// Second string for the interleave operations
+    #[arg(long)]
+    with: Option<String>,
+    /// Initial capacity in the capacity demo
+    #[arg(long, default_value_t = DemoInputs::default().capacity)]
+    capacity: usize,
+    /// Repeat count in the transformations demo
+    #[arg(long, default_value_t = DemoInputs::default().repeat)]
+    repeat: usize,
+    /// Length the growth demo pushes up to
+    #[arg(long, default_value_t = DemoInputs::default().growth_target)]
+    up_to: usize,
+    /// Bytes per push in the growth demo; repeat for several curves
+    #[arg(long = "chunk", default_values_t = DemoInputs::default().chunks)]
+    chunks: Vec<usize>,
+    /// Inputs the concurrency demo processes at once
+    #[arg(long, default_value_t = DemoInputs::default().max_concurrent)]
+    max_concurrent: usize,
+    /// Per-input timeout in the concurrency demo, in milliseconds
+    #[arg(long, default_value_t = DemoInputs::default().timeout_ms)]
+    timeout_ms: u64,
+    /// Cancel the rest of the concurrency demo's batch after this many milliseconds
+    #[arg(long, default_value_t = DemoInputs::default().cancel_after_ms)]
+    cancel_after_ms: u64,
+    /// Stages of the pipeline demo, in order; repeat for several
+    #[arg(long = "stage", value_enum)]
+    stages: Vec<PipelineStage>,
+}
 
-    let task2 = task::spawn(async_process_string(
-        input.clone(),
-        "Task 2: API Call",
-    ));
+impl From<InputArgs> for DemoInputs {
+    fn from(args: InputArgs) -> Self {
+        DemoInputs {
+            text: args.text,
+            other: args.with,
+            capacity: args.capacity,
+            repeat: args.repeat,
+            growth_target: args.up_to,
+            chunks: args.chunks,
+            max_concurrent: args.max_concurrent,
+            timeout_ms: args.timeout_ms,
+            cancel_after_ms: args.cancel_after_ms,
+            stages: if args.stages.is_empty() {
+                DemoInputs::default().stages
+            } else {
+                args.stages
+                    .iter()
+                    .map(|stage| stage.stage(args.repeat))
+                    .collect()
+            },
+        }
+    }
+}
 
-    let task3 = task::spawn(async_process_string(
-        input.clone(),
-        "Task 3: File Read",
-    ));
+/// Accepts the name of a registered demo, with its description as help
+fn demo_names() -> PossibleValuesParser {
+    let names: Vec<PossibleValue> = Registry::builtin()
+        .iter()
+        .map(|demo| PossibleValue::new(demo.name()).help(demo.description()))
+        .collect();
+    PossibleValuesParser::new(names)
+}
 
-    println!("\n{} Launched 3 concurrent tasks", "🚀".bright_yellow());
-    println!("   Each task is running independently on the Tokio runtime");
+/// Accepts a tag of some registered demo
+fn demo_tags() -> PossibleValuesParser {
+    let mut tags: Vec<&'static str> = Registry::builtin()
+        .iter()
+        .flat_map(|demo| demo.tags().iter().copied())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    PossibleValuesParser::new(tags)
+}
 
-    // Await all tasks
-    let results = tokio::join!(task1, task2, task3);
+/// Whether the demo carries one of `tags`, or `tags` is empty
+fn has_any_tag(demo: &dyn Demo, tags: &[String]) -> bool {
+    tags.is_empty() || tags.iter().any(|tag| demo.tags().contains(&tag.as_str()))
+}
 
-    println!("\n{} All tasks completed!", "✓".bright_green().bold());
+/// Stages for the pipeline demo's `--stage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PipelineStage {
+    /// Send the string on unchanged
+    Forward,
+    /// Append "!" in place
+    Push,
+    Nfc,
+    Nfd,
+    Reverse,
+    ReverseGraphemes,
+    Upper,
+    Lower,
+    Fold,
+    /// Repeat `--repeat` times
+    Repeat,
+}
 
-    match results {
-        (Ok(r1), Ok(r2), Ok(r3)) => {
-            println!("   Task 1: {}", r1);
-            println!("   Task 2: {}", r2);
-            println!("   Task 3: {}", r3);
+impl PipelineStage {
+    fn stage(self, repeat: usize) -> Stage {
+        match self {
+            PipelineStage::Forward => Stage::Forward,
+            PipelineStage::Push => Stage::Push("!".to_string()),
+            PipelineStage::Nfc => Stage::Normalize(NormalizationForm::Nfc),
+            PipelineStage::Nfd => Stage::Normalize(NormalizationForm::Nfd),
+            PipelineStage::Reverse => Stage::Reverse,
+            PipelineStage::ReverseGraphemes => Stage::ReverseGraphemes,
+            PipelineStage::Upper => Stage::CaseMap(CaseMapping::Upper, CaseLocale::Root),
+            PipelineStage::Lower => Stage::CaseMap(CaseMapping::Lower, CaseLocale::Root),
+            PipelineStage::Fold => Stage::CaseMap(CaseMapping::Fold, CaseLocale::Root),
+            PipelineStage::Repeat => Stage::Repeat(repeat),
         }
-        _ => error!("Some tasks failed!"),
     }
+}
 
-    println!(
-        "\n{} Async runtime details:",
-        "💡 INSIGHT:".bright_green().bold()
-    );
-    println!("   Runtime: Tokio (work-stealing scheduler)");
-    println!("   Tasks are lightweight (not OS threads)");
-    println!("   Concurrent execution without blocking");
+/// Operations for `lab transform`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Transform {
+    Reverse,
+    ReverseGraphemes,
+    Upper,
+    Lower,
+    Title,
+    Fold,
+    Repeat,
+    Interleave,
+    InterleaveGraphemes,
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
 }
 
-/// Demonstrates string transformations with timing
-#[tracing::instrument]
-async fn demo_transformations() {
-    print_section("6. STRING TRANSFORMATIONS WITH TIMING");
-
-    info!("Performing various string transformations...");
-
-    let mut manipulator = StringManipulator::new();
-
-    // Reverse
-    let input = "Hello, World!";
-    let result = manipulator.reverse(input);
-    println!("\n{} REVERSE", "🔄".bright_cyan());
-    println!("   Input:  '{}'", input);
-    println!("   Output: '{}'", result.value);
-    result.display_timing();
-
-    display_bytes(&result.value, "Reversed bytes");
-
-    // Uppercase (demonstrates Unicode case mapping)
-    let unicode_input = "Straße"; // German street
-    let result = manipulator.to_upper(unicode_input);
-    println!("\n{} UPPERCASE (Unicode-aware)", "🔤".bright_cyan());
-    println!("   Input:  '{}' ({} bytes)", unicode_input, unicode_input.len());
-    println!(
-        "   Output: '{}' ({} bytes)",
-        result.value,
-        result.value.len()
-    );
-    result.display_timing();
-
-    if result.value.len() != unicode_input.len() {
-        println!(
-            "   {} Case conversion changed byte length!",
-            "⚠".bright_yellow()
-        );
-        println!("      'ß' (1 char, 2 bytes) → 'SS' (2 chars, 2 bytes)");
+impl Transform {
+    fn operation<'a>(
+        self,
+        text: &'a str,
+        with: &'a str,
+        repeat: usize,
+        locale: CaseLocale,
+    ) -> Operation<'a> {
+        match self {
+            Transform::Reverse => Operation::Reverse(text),
+            Transform::ReverseGraphemes => Operation::ReverseGraphemes(text),
+            Transform::Upper if locale == CaseLocale::Root => Operation::ToUpper(text),
+            Transform::Upper => Operation::CaseMap(text, CaseMapping::Upper, locale),
+            Transform::Lower => Operation::CaseMap(text, CaseMapping::Lower, locale),
+            Transform::Title => Operation::CaseMap(text, CaseMapping::Title, locale),
+            Transform::Fold => Operation::CaseMap(text, CaseMapping::Fold, locale),
+            Transform::Repeat => Operation::Repeat(text, repeat),
+            Transform::Interleave => Operation::Interleave(text, with),
+            Transform::InterleaveGraphemes => Operation::InterleaveGraphemes(text, with),
+            Transform::Nfc => Operation::Normalize(text, NormalizationForm::Nfc),
+            Transform::Nfd => Operation::Normalize(text, NormalizationForm::Nfd),
+            Transform::Nfkc => Operation::Normalize(text, NormalizationForm::Nfkc),
+            Transform::Nfkd => Operation::Normalize(text, NormalizationForm::Nfkd),
+        }
     }
-
-    // Repeat
-    let result = manipulator.repeat("Rust ", 5);
-    println!("\n{} REPEAT", "🔁".bright_cyan());
-    println!("   Pattern: 'Rust '");
-    println!("   Count:   5");
-    println!("   Output:  '{}'", result.value);
-    println!(
-        "   Capacity: {} bytes (pre-allocated, no reallocation)",
-        result.value.capacity()
-    );
-    result.display_timing();
-
-    // Interleave
-    let result = manipulator.interleave("RUST", "rust");
-    println!("\n{} INTERLEAVE", "🔀".bright_cyan());
-    println!("   String 1: 'RUST'");
-    println!("   String 2: 'rust'");
-    println!("   Output:   '{}'", result.value);
-    result.display_timing();
-
-    println!(
-        "\n{} Total operations: {}",
-        "📊".bright_yellow(),
-        manipulator.operations_count.to_string().bright_green().bold()
-    );
 }
 
-/// Demonstrates UTF-8 and Unicode handling
-#[tracing::instrument]
-async fn demo_unicode() {
-    print_section("7. UNICODE AND UTF-8 HANDLING");
+/// Runs a laboratory session; the takeaways close a session of every demo
+async fn run(
+    registry: &Registry,
+    names: Vec<String>,
+    tags: Vec<String>,
+    inputs: DemoInputs,
+    render: &Renderer,
+    spans: &SpanTree,
+) {
+    let all = names.is_empty() && tags.is_empty();
+    let selected: Vec<&dyn Demo> = if names.is_empty() {
+        registry.iter().collect()
+    } else {
+        names.iter().filter_map(|name| registry.get(name)).collect()
+    };
+
+    render.print_banner();
 
-    info!("Exploring UTF-8 encoding...");
+    info!("Starting introspective string laboratory...");
 
-    // ASCII - 1 byte per character
-    let ascii = "Rust";
-    display_bytes(ascii, "ASCII string (1 byte/char)");
+    let cx = Context::new(render, &inputs);
+    let mut timeline = Timeline::new();
+    for demo in selected
+        .into_iter()
+        .filter(|demo| has_any_tag(*demo, &tags))
+    {
+        demo.run(&cx).await;
+        timeline.record(demo.name());
+    }
 
-    // Multi-byte UTF-8
-    let emoji = "🦀🚀";
-    display_bytes(emoji, "Emoji (4 bytes/char)");
+    if !timeline.is_empty() {
+        render.timeline(&timeline);
+    }
+    render.span_tree(&spans.snapshot());
+
+    if all {
+        render.takeaways();
+    }
 
-    // Mixed
-    let mixed = "Rust 🦀";
-    display_bytes(mixed, "Mixed ASCII + Emoji");
+    info!("Laboratory session complete!");
+}
 
-    // Demonstrate the danger of byte indexing
-    println!("\n{} BYTE vs CHAR indexing:", "⚠".bright_yellow().bold());
-    println!("   String: '{}'", mixed);
-    println!("   Length in bytes: {}", mixed.len());
-    println!("   Length in chars: {}", mixed.chars().count());
+const REPL_HELP: &str = "\
+Statements:
+  let s = String::new() | String::with_capacity(n) | String::from(\"..\") | \"..\".to_string()
+  let t = s.clone()     let u = t     drop(u)
+  s.push_str(\"..\")  s.push('c')  s.insert(i, 'c')  s.insert_str(i, \"..\")
+  s.truncate(n)  s.pop()  s.clear()  s.reserve(n)  s.reserve_exact(n)
+  s.shrink_to_fit()  s.shrink_to(n)
+Commands:
+  :vars  :show NAME  :history  :save FILE  :help  :quit";
+
+/// Runs the interactive REPL until `:quit` or end of input
+fn repl(script: Option<&Path>, render: &Renderer) -> io::Result<()> {
+    let mut session = Session::new();
+    let interactive = render.format() == OutputFormat::Text;
+
+    if let Some(path) = script {
+        for result in session.replay(&std::fs::read_to_string(path)?) {
+            match result {
+                Ok(step) => render.repl_step(&step),
+                Err(error) => render.repl_error(&error),
+            }
+        }
+    }
 
-    println!("\n   Character iteration:");
-    for (i, ch) in mixed.chars().enumerate() {
-        println!("      chars[{}] = '{}' ({} bytes)", i, ch, ch.len_utf8());
+    if interactive {
+        println!("String REPL - type :help for the statements it understands");
     }
 
-    warn!("   ⚠ mixed[5] would PANIC! Use .chars().nth(n) instead");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("lab> ");
+            io::stdout().flush()?;
+        }
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+
+        let line = line.trim();
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "" => {}
+            ":quit" | ":exit" | ":q" => break,
+            ":help" => println!("{}", REPL_HELP),
+            ":history" => render.repl_history(session.history()),
+            ":vars" => {
+                for layout in session.layouts() {
+                    render.layout(&layout);
+                }
+            }
+            ":show" => match session.layout(argument.trim()) {
+                Ok(layout) => render.layout(&layout),
+                Err(error) => render.repl_error(&error),
+            },
+            ":save" => {
+                let path = Path::new(argument.trim());
+                match session.save(path) {
+                    Ok(()) if interactive => println!("Saved to {}", path.display()),
+                    Ok(()) => {}
+                    Err(error) => eprintln!("could not save {}: {}", path.display(), error),
+                }
+            }
+            _ => match session.execute(line) {
+                Ok(step) => render.repl_step(&step),
+                Err(error) => render.repl_error(&error),
+            },
+        }
+    }
+
+    Ok(())
 }
 
-/// Main entry point - sets up logging and runs all demonstrations
+/// Main entry point - sets up logging and dispatches the subcommand
 #[tokio::main]
 async fn main() {
-    // Initialize tracing subscriber for structured logging
-    tracing_subscriber::fmt()
-        .with_max_level(Level::DEBUG)
-        .with_span_events(FmtSpan::ENTER | FmtSpan::CLOSE)
-        .with_target(false)
-        .with_thread_ids(true)
-        .with_line_number(true)
+    // SAFETY: GLOBAL is this binary's #[global_allocator]
+    unsafe { GLOBAL.assume_global() };
+    let cli = Cli::parse();
+    let render = Renderer::new(cli.format.into());
+    Theme::detect(cli.color.into(), cli.charset.into()).install();
+
+    // Initialize tracing for structured logging; spans are collected into a
+    // tree shown after a session rather than logged on every enter and exit
+    let span_layer = SpanTreeLayer::new();
+    let spans = span_layer.tree();
+    let log_layer = match cli.log.config(cli.format, cli.color.into()).layer() {
+        Ok(layer) => layer,
+        Err(error) => {
+            eprintln!("lab: {}", error);
+            std::process::exit(2);
+        }
+    };
+    let trace_layer = cli.trace.as_ref().map(|_| TraceEventLayer::new());
+    let trace = trace_layer.as_ref().map(TraceEventLayer::trace);
+    tracing_subscriber::registry()
+        .with(log_layer)
+        .with(span_layer)
+        .with(trace_layer)
         .init();
 
-    println!("{}", "╔═══════════════════════════════════════════════════════════════════╗".bright_magenta().bold());
-    println!(
-        "{}",
-        "║                                                                   ║"
-            .bright_magenta()
-            .bold()
-    );
-    println!(
-        "{}",
-        "║        🦀  THE INTROSPECTIVE STRING LABORATORY  🦀                ║"
-            .bright_magenta()
-            .bold()
-    );
-    println!(
-        "{}",
-        "║                                                                   ║"
-            .bright_magenta()
-            .bold()
-    );
-    println!(
-        "{}",
-        "║  A Self-Aware Rust Program Teaching String Internals             ║"
-            .bright_magenta()
-            .bold()
-    );
-    println!(
-        "{}",
-        "║                                                                   ║"
-            .bright_magenta()
-            .bold()
-    );
-    println!("{}", "╚═══════════════════════════════════════════════════════════════════╝".bright_magenta().bold());
-
-    info!("Starting introspective string laboratory...");
-
-    // Run all demonstrations
-    demo_string_types().await;
-    demo_ownership().await;
-    demo_capacity_and_growth().await;
-    demo_clone_on_write().await;
-    demo_async_operations().await;
-    demo_transformations().await;
-    demo_unicode().await;
-
-    print_section("✨ LABORATORY SESSION COMPLETE ✨");
-
-    println!("\n{} Key Takeaways:", "📚".bright_green().bold());
-    println!("   1. String is heap-allocated, growable, and owned");
-    println!("   2. &str is a borrowed slice, can point to stack, heap, or static memory");
-    println!("   3. Moves are zero-cost, clones allocate and copy");
-    println!("   4. Capacity management affects performance (reallocation is O(n))");
-    println!("   5. Cow<str> delays allocation until mutation");
-    println!("   6. Rust is UTF-8 aware - chars ≠ bytes");
-    println!("   7. Async operations are lightweight and concurrent");
-
-    println!("\n{} Rust guarantees:", "🛡️".bright_blue().bold());
-    println!("   ✓ Memory safety without garbage collection");
-    println!("   ✓ Thread safety enforced at compile time");
-    println!("   ✓ Zero-cost abstractions");
-    println!("   ✓ No null pointer exceptions");
-    println!("   ✓ No data races");
+    let registry = Registry::builtin();
+    match cli.command {
+        None => {
+            let inputs = DemoInputs::default();
+            run(&registry, Vec::new(), Vec::new(), inputs, &render, &spans).await
+        }
+        Some(Command::List { tags }) => {
+            for demo in registry.iter().filter(|demo| has_any_tag(*demo, &tags)) {
+                match render.format() {
+                    OutputFormat::Text => println!(
+                        "{:<16} {:<66} {}",
+                        demo.name(),
+                        demo.description(),
+                        demo.tags().join(", ")
+                    ),
+                    OutputFormat::Json => render.emit(
+                        "demo",
+                        &json!({
+                            "name": demo.name(),
+                            "description": demo.description(),
+                            "tags": demo.tags(),
+                        }),
+                    ),
+                }
+            }
+        }
+        Some(Command::Run {
+            demos,
+            tags,
+            inputs,
+        }) => run(&registry, demos, tags, inputs.into(), &render, &spans).await,
+        Some(Command::Inspect { text, capacity }) => {
+            render.inspection(&demos::inspection(&text, capacity));
+        }
+        Some(Command::Repl { script }) => {
+            if let Err(error) = repl(script.as_deref(), &render) {
+                eprintln!("repl: {}", error);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Transform {
+            op,
+            text,
+            with,
+            repeat,
+            locale,
+            bench,
+        }) => {
+            let locale = CaseLocale::from_language(&locale);
+            let config = BenchConfig::default();
+            let operation = op.operation(&text, &with, repeat, locale);
+            render.transform(&demos::transform(operation, bench.then_some(&config)));
+        }
+    }
 
-    info!("Laboratory session complete!");
+    if let (Some(path), Some(trace)) = (&cli.trace, trace) {
+        if let Err(error) = trace.write(path) {
+            eprintln!("could not write {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+        info!("Wrote the trace to {}", path.display());
+    }
 }
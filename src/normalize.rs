@@ -0,0 +1,132 @@
+//! # Unicode Normalization
+//!
+//! "é" can be stored as one code point (U+00E9) or as `e` followed by
+//! U+0301 COMBINING ACUTE ACCENT. Both render identically, but their bytes
+//! differ, so `==` says they are different strings. Normalization maps
+//! canonically equivalent text to a single representation:
+//!
+//! - **NFC** - canonical composition (the usual choice for storage)
+//! - **NFD** - canonical decomposition
+//! - **NFKC** / **NFKD** - the same, but also folding compatibility
+//!   characters such as the "ﬁ" ligature into "fi"
+//!
+//! Most text is already normalized, so [`normalize`] first runs the
+//! Unicode quick-check algorithm and borrows the input when it can prove
+//! nothing would change.
+
+use std::borrow::Cow;
+use std::fmt;
+use unicode_normalization::{
+    is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick, IsNormalized, UnicodeNormalization,
+};
+
+/// One of the four Unicode normalization forms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NormalizationForm {
+    /// Canonical decomposition followed by canonical composition
+    Nfc,
+    /// Canonical decomposition
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition
+    Nfkc,
+    /// Compatibility decomposition
+    Nfkd,
+}
+
+impl NormalizationForm {
+    /// Every form, in the order they are usually listed
+    pub const ALL: [NormalizationForm; 4] = [
+        NormalizationForm::Nfc,
+        NormalizationForm::Nfd,
+        NormalizationForm::Nfkc,
+        NormalizationForm::Nfkd,
+    ];
+
+    /// Short name, e.g. `NFC`
+    pub fn name(&self) -> &'static str {
+        match self {
+            NormalizationForm::Nfc => "NFC",
+            NormalizationForm::Nfd => "NFD",
+            NormalizationForm::Nfkc => "NFKC",
+            NormalizationForm::Nfkd => "NFKD",
+        }
+    }
+
+    /// Whether this form also folds compatibility characters
+    pub fn is_compatibility(&self) -> bool {
+        matches!(self, NormalizationForm::Nfkc | NormalizationForm::Nfkd)
+    }
+}
+
+impl fmt::Display for NormalizationForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Answer of the Unicode quick-check algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickCheck {
+    /// Definitely already normalized
+    Yes,
+    /// Definitely not normalized
+    No,
+    /// Undecided without running the full algorithm
+    Maybe,
+}
+
+impl From<IsNormalized> for QuickCheck {
+    fn from(answer: IsNormalized) -> Self {
+        match answer {
+            IsNormalized::Yes => QuickCheck::Yes,
+            IsNormalized::No => QuickCheck::No,
+            IsNormalized::Maybe => QuickCheck::Maybe,
+        }
+    }
+}
+
+impl fmt::Display for QuickCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QuickCheck::Yes => "yes",
+            QuickCheck::No => "no",
+            QuickCheck::Maybe => "maybe",
+        })
+    }
+}
+
+/// Runs the quick-check algorithm for `form` over `s`
+///
+/// This is a single pass over the code points that never allocates.
+pub fn quick_check(s: &str, form: NormalizationForm) -> QuickCheck {
+    let chars = s.chars();
+    match form {
+        NormalizationForm::Nfc => is_nfc_quick(chars),
+        NormalizationForm::Nfd => is_nfd_quick(chars),
+        NormalizationForm::Nfkc => is_nfkc_quick(chars),
+        NormalizationForm::Nfkd => is_nfkd_quick(chars),
+    }
+    .into()
+}
+
+/// Normalizes `s` to `form`
+///
+/// Borrows `s` when the quick check proves it is already normalized, so
+/// the common case costs no allocation.
+pub fn normalize(s: &str, form: NormalizationForm) -> Cow<'_, str> {
+    if quick_check(s, form) == QuickCheck::Yes {
+        return Cow::Borrowed(s);
+    }
+
+    Cow::Owned(match form {
+        NormalizationForm::Nfc => s.nfc().collect(),
+        NormalizationForm::Nfd => s.nfd().collect(),
+        NormalizationForm::Nfkc => s.nfkc().collect(),
+        NormalizationForm::Nfkd => s.nfkd().collect(),
+    })
+}
+
+/// Whether `a` and `b` are equal once both are normalized to `form`
+pub fn eq_normalized(a: &str, b: &str, form: NormalizationForm) -> bool {
+    a == b || normalize(a, form) == normalize(b, form)
+}
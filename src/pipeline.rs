@@ -0,0 +1,271 @@
+//! # Channel Pipelines
+//!
+//! Strings flow through a chain of tokio tasks connected by bounded `mpsc`
+//! channels, each stage applying one transformation. Stages run on the
+//! blocking pool, one thread each for as long as the pipeline runs, so every
+//! hop hands the string to another thread. Every stage records
+//! the buffer's `data_ptr` as the string arrives and as it leaves, so a
+//! [`Journey`] shows which hops only moved the 24-byte handle and which
+//! transformations put the bytes in a new buffer:
+//!
+//! - sending a `String` through a channel moves the handle; the heap buffer
+//!   stays where it is, whichever thread receives it;
+//! - `push_str` within the capacity, and a normalization that passes the
+//!   quick check, keep the buffer;
+//! - the other [`StringManipulator`] operations build a new `String`.
+
+use crate::allocator;
+use crate::case::{CaseLocale, CaseMapping};
+use crate::inspector::Inspect;
+use crate::normalize::NormalizationForm;
+use crate::transformer::{StringManipulator, TimedResult};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt;
+use std::iter;
+use std::thread;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio::task;
+use tracing::{debug, error, info_span, instrument};
+
+/// One transformation in a pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stage {
+    /// Send the string on unchanged
+    Forward,
+    /// Append text with `push_str`, in place while the capacity lasts
+    Push(String),
+    /// Convert to a normalization form, keeping the string if it already is
+    Normalize(NormalizationForm),
+    /// Reverse by characters
+    Reverse,
+    /// Reverse by grapheme clusters
+    ReverseGraphemes,
+    /// Full case mapping with a language tailoring
+    CaseMap(CaseMapping, CaseLocale),
+    /// Repeat the string n times
+    Repeat(usize),
+}
+
+impl Stage {
+    /// Applies the stage to a string it owns
+    fn apply(&self, manipulator: &mut StringManipulator, mut input: String) -> TimedResult<String> {
+        match self {
+            Stage::Forward => in_place("forward", || input),
+            Stage::Push(text) => in_place("push_str", || {
+                input.push_str(text);
+                input
+            }),
+            Stage::Normalize(form) => {
+                let TimedResult {
+                    value,
+                    duration_nanos,
+                    operation,
+                    allocations,
+                    distribution,
+                } = manipulator.normalize(&input, *form);
+                // Already-normalized text comes back borrowed: keep the input
+                let normalized = match value {
+                    Cow::Owned(normalized) => Some(normalized),
+                    Cow::Borrowed(_) => None,
+                };
+                TimedResult {
+                    value: normalized.unwrap_or(input),
+                    duration_nanos,
+                    operation,
+                    allocations,
+                    distribution,
+                }
+            }
+            Stage::Reverse => manipulator.reverse(&input),
+            Stage::ReverseGraphemes => manipulator.reverse_graphemes(&input),
+            Stage::CaseMap(mapping, locale) => manipulator.case_map(&input, *mapping, *locale),
+            Stage::Repeat(count) => manipulator.repeat(&input, *count),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Forward => write!(f, "forward"),
+            Stage::Push(text) => write!(f, "push_str({:?})", text),
+            Stage::Normalize(form) => write!(f, "normalize({})", form),
+            Stage::Reverse => write!(f, "reverse"),
+            Stage::ReverseGraphemes => write!(f, "reverse_graphemes"),
+            Stage::CaseMap(mapping, CaseLocale::Root) => write!(f, "{}", mapping),
+            Stage::CaseMap(mapping, locale) => write!(f, "{}[{}]", mapping, locale),
+            Stage::Repeat(count) => write!(f, "repeat({})", count),
+        }
+    }
+}
+
+/// Times an operation that works on the string it owns
+fn in_place(operation: &str, f: impl FnOnce() -> String) -> TimedResult<String> {
+    let start = Instant::now();
+    let (value, allocations) = allocator::track_if_installed(f);
+    TimedResult {
+        value,
+        duration_nanos: start.elapsed().as_nanos(),
+        operation: operation.to_string(),
+        allocations,
+        distribution: None,
+    }
+}
+
+/// What one stage did to a string
+#[derive(Debug, Clone, Serialize)]
+pub struct StageRecord {
+    pub stage: String,
+    /// Thread the stage's task ran on while it handled the string
+    pub thread: String,
+    /// `data_ptr` of the string as it came out of the channel
+    pub received: usize,
+    /// `data_ptr` of the string the stage sent on
+    pub sent: usize,
+    pub len: usize,
+    pub capacity: usize,
+    pub duration_nanos: u128,
+    pub allocations: Option<allocator::AllocStats>,
+}
+
+impl StageRecord {
+    /// Whether the stage sent the bytes on in a different buffer
+    pub fn reallocated(&self) -> bool {
+        self.sent != self.received
+    }
+}
+
+/// A string's way through the pipeline
+#[derive(Debug, Clone, Serialize)]
+pub struct Journey {
+    pub input: String,
+    /// `data_ptr` of the string the source sent
+    pub source: usize,
+    pub stages: Vec<StageRecord>,
+    pub output: String,
+    /// `data_ptr` of the string as the sink received it
+    pub delivered: usize,
+}
+
+impl Journey {
+    /// The buffer sent into and the buffer received from each channel, in
+    /// order
+    pub fn hops(&self) -> Vec<(usize, usize)> {
+        let sent = iter::once(self.source).chain(self.stages.iter().map(|s| s.sent));
+        let received = self
+            .stages
+            .iter()
+            .map(|s| s.received)
+            .chain(iter::once(self.delivered));
+        sent.zip(received).collect()
+    }
+
+    /// Whether every channel delivered the buffer it was sent
+    pub fn zero_copy_hops(&self) -> bool {
+        self.hops().iter().all(|(sent, received)| sent == received)
+    }
+
+    /// Number of stages that moved the bytes to a new buffer
+    pub fn reallocations(&self) -> usize {
+        self.stages.iter().filter(|s| s.reallocated()).count()
+    }
+}
+
+/// A string in flight, with the records of the stages it passed
+struct Parcel {
+    input: String,
+    value: String,
+    source: usize,
+    stages: Vec<StageRecord>,
+}
+
+/// Sends every input through `stages`, one task per stage, connected by
+/// channels holding up to `channel_capacity` strings
+///
+/// Returns the journeys in the order the strings came out of the last
+/// stage. A stage that panics ends the pipeline early: the strings it held
+/// are missing from the result.
+#[instrument(skip(inputs, stages), fields(stages = stages.len()))]
+pub async fn run<I>(inputs: I, stages: &[Stage], channel_capacity: usize) -> Vec<Journey>
+where
+    I: IntoIterator<Item = String>,
+{
+    let capacity = channel_capacity.max(1);
+    let (source, mut rx) = mpsc::channel::<Parcel>(capacity);
+
+    let mut handles = Vec::with_capacity(stages.len() + 1);
+    for (position, stage) in stages.iter().cloned().enumerate() {
+        let (tx, next) = mpsc::channel(capacity);
+        let mut stage_rx = std::mem::replace(&mut rx, next);
+        let span = info_span!("pipeline_stage", position, stage = %stage);
+        // A woken async task tends to run on the worker that woke it, so
+        // the stages hold blocking threads to be sure the strings move
+        handles.push(task::spawn_blocking(move || {
+            let _entered = span.enter();
+            let mut manipulator = StringManipulator::new();
+            while let Some(mut parcel) = stage_rx.blocking_recv() {
+                let received = parcel.value.data_ptr() as usize;
+                let result = stage.apply(&mut manipulator, parcel.value);
+                let record = StageRecord {
+                    stage: stage.to_string(),
+                    thread: format!("{:?}", thread::current().id()),
+                    received,
+                    sent: result.value.data_ptr() as usize,
+                    len: result.value.len(),
+                    capacity: result.value.capacity(),
+                    duration_nanos: result.duration_nanos,
+                    allocations: result.allocations,
+                };
+                debug!(
+                    received = format_args!("{:#x}", record.received),
+                    sent = format_args!("{:#x}", record.sent),
+                    thread = %record.thread,
+                    "{}",
+                    if record.reallocated() { "New buffer" } else { "Kept the buffer" }
+                );
+                parcel.stages.push(record);
+                parcel.value = result.value;
+                if tx.blocking_send(parcel).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    // Feed the pipeline from its own task, so the sink drains it meanwhile
+    let inputs: Vec<String> = inputs.into_iter().collect();
+    handles.push(task::spawn(async move {
+        for value in inputs {
+            let parcel = Parcel {
+                input: value.clone(),
+                source: value.data_ptr() as usize,
+                value,
+                stages: Vec::new(),
+            };
+            if source.send(parcel).await.is_err() {
+                break;
+            }
+        }
+    }));
+
+    let mut journeys = Vec::new();
+    while let Some(parcel) = rx.recv().await {
+        journeys.push(Journey {
+            input: parcel.input,
+            source: parcel.source,
+            stages: parcel.stages,
+            delivered: parcel.value.data_ptr() as usize,
+            output: parcel.value,
+        });
+    }
+
+    for handle in handles {
+        if let Err(e) = handle.await {
+            error!("Pipeline stage panicked or was cancelled: {}", e);
+        }
+    }
+
+    journeys
+}
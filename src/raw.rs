@@ -0,0 +1,550 @@
+//! # Raw Object Bytes
+//!
+//! [`Inspect`](crate::inspector::Inspect) reports a string's pointer, length
+//! and capacity through the public API. This module reads the bytes of the
+//! object itself instead: the 24-byte header of a `String` and the 16-byte
+//! fat pointer of a `&str` or `Box<str>`.
+//!
+//! Rust does not guarantee the order of those fields - rustc is free to
+//! reorder struct fields, and has done so between releases. So nothing here
+//! assumes an order: a probe value with a distinct pointer, length and
+//! capacity is built first, and whichever word holds which value gives the
+//! [`FieldOrder`] used to decode the real one.
+//!
+//! The heap buffer is dumped too, with UTF-8 character boundaries marked.
+//! Spare capacity is shown but never read - it is uninitialized memory.
+//!
+//! `Rc<str>` and `Arc<str>` keep their reference counts in the same heap
+//! allocation as the bytes, right before them. [`SharedAllocation`] reads
+//! that counts header, detecting which word is the strong and which the
+//! weak count with a probe too.
+
+use crate::allocator;
+use crate::theme;
+use colored::Colorize;
+use serde::Serialize;
+use std::fmt;
+use std::mem::size_of;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const WORD: usize = size_of::<usize>();
+
+/// Bytes per hexdump row
+const ROW: usize = 16;
+
+/// What a word of a string header holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordRole {
+    Pointer,
+    Length,
+    Capacity,
+    /// Number of strong references (`Rc`/`Arc` clones)
+    Strong,
+    /// Number of weak references, plus one held by all the strong ones
+    Weak,
+}
+
+impl fmt::Display for WordRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordRole::Pointer => write!(f, "ptr"),
+            WordRole::Length => write!(f, "len"),
+            WordRole::Capacity => write!(f, "cap"),
+            WordRole::Strong => write!(f, "strong"),
+            WordRole::Weak => write!(f, "weak"),
+        }
+    }
+}
+
+/// The role of every word of a header, in memory order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldOrder(pub Vec<WordRole>);
+
+impl FieldOrder {
+    /// Works out which of `words` holds the pointer, length and capacity of
+    /// a probe whose three values are all different
+    ///
+    /// Returns `None` when a value is missing or found twice.
+    fn detect(words: &[usize], ptr: usize, len: usize, capacity: Option<usize>) -> Option<Self> {
+        Self::detect_roles(
+            words,
+            &[
+                (WordRole::Pointer, Some(ptr)),
+                (WordRole::Length, Some(len)),
+                (WordRole::Capacity, capacity),
+            ],
+        )
+    }
+
+    /// Gives every word the role whose expected value it holds
+    fn detect_roles(words: &[usize], expected: &[(WordRole, Option<usize>)]) -> Option<Self> {
+        let roles = words
+            .iter()
+            .map(|&word| {
+                let mut matching = expected.iter().filter(|(_, value)| *value == Some(word));
+                match (matching.next(), matching.next()) {
+                    (Some(&(role, _)), None) => Some(role),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(FieldOrder(roles))
+    }
+
+    /// Index of the word holding `role`
+    fn position(&self, role: WordRole) -> Option<usize> {
+        self.0.iter().position(|&r| r == role)
+    }
+}
+
+impl fmt::Display for FieldOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, role) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", role)?;
+        }
+        Ok(())
+    }
+}
+
+/// One machine word of a header
+#[derive(Debug, Clone, Serialize)]
+pub struct HeaderWord {
+    /// Byte offset from the start of the object
+    pub offset: usize,
+    /// The word's bytes, in memory order
+    pub bytes: Vec<u8>,
+    /// The bytes read as a native-endian `usize`
+    pub value: usize,
+    pub role: Option<WordRole>,
+}
+
+/// The raw bytes of a string object (or of the counts header of a shared
+/// allocation), decoded with a detected field order
+#[derive(Debug, Clone, Serialize)]
+pub struct RawHeader {
+    pub type_name: &'static str,
+    /// Where the object itself lives
+    pub address: usize,
+    /// `size_of` the object
+    pub size: usize,
+    /// Field order detected with a probe, `None` if detection failed
+    pub order: Option<FieldOrder>,
+    pub words: Vec<HeaderWord>,
+    /// Whether the decoded words agree with the public API
+    pub verified: bool,
+}
+
+impl RawHeader {
+    /// The decoded value of the word holding `role`
+    pub fn field(&self, role: WordRole) -> Option<usize> {
+        let position = self.order.as_ref()?.position(role)?;
+        Some(self.words[position].value)
+    }
+
+    /// Prints the header word by word
+    pub fn print(&self) {
+        let order = match &self.order {
+            Some(order) => format!("field order (detected): {}", order),
+            None => "field order could not be detected".to_string(),
+        };
+        let glyphs = theme::glyphs();
+        let check = if self.verified {
+            format!("{} matches the API", glyphs.check).bright_green()
+        } else {
+            format!("{} does not match the API", glyphs.cross).bright_red()
+        };
+        println!(
+            "\n{} {} header @ {:#x}: {} bytes, {} {}",
+            theme::icon("🔬").bright_cyan(),
+            self.type_name.bright_white().bold(),
+            self.address,
+            self.size,
+            order,
+            check
+        );
+        for word in &self.words {
+            let hex: Vec<String> = word.bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            let decoded = match word.role {
+                Some(WordRole::Pointer) => format!("ptr  {:#x}", word.value).bright_magenta(),
+                Some(role) => format!("{}  {}", role, word.value).bright_yellow(),
+                None => "?".dimmed(),
+            };
+            println!("   +{:#04x}  {}  {}", word.offset, hex.join(" "), decoded);
+        }
+    }
+
+    fn read<T>(value: &T, type_name: &'static str, order: Option<FieldOrder>) -> Self {
+        Self::from_words(
+            words_of(value),
+            value as *const T as usize,
+            type_name,
+            order,
+        )
+    }
+
+    fn from_words(
+        words: Vec<usize>,
+        address: usize,
+        type_name: &'static str,
+        order: Option<FieldOrder>,
+    ) -> Self {
+        let roles = order.as_ref().map(|order| order.0.clone());
+        RawHeader {
+            type_name,
+            address,
+            size: words.len() * WORD,
+            words: words
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| HeaderWord {
+                    offset: i * WORD,
+                    bytes: value.to_ne_bytes().to_vec(),
+                    value,
+                    role: roles.as_ref().map(|roles| roles[i]),
+                })
+                .collect(),
+            order,
+            verified: false,
+        }
+    }
+
+    fn verify(mut self, ptr: *const u8, len: usize, capacity: Option<usize>) -> Self {
+        self.verified = self.field(WordRole::Pointer) == Some(ptr as usize)
+            && self.field(WordRole::Length) == Some(len)
+            && self.field(WordRole::Capacity) == capacity;
+        self
+    }
+}
+
+/// Copies the words of `value`
+///
+/// Only used on types made entirely of pointer-sized fields (`String`,
+/// `&str`, `Box<str>`), so every byte read is initialized - there is no
+/// padding.
+fn words_of<T>(value: &T) -> Vec<usize> {
+    debug_assert_eq!(size_of::<T>() % WORD, 0);
+    let ptr = value as *const T as *const usize;
+    (0..size_of::<T>() / WORD)
+        // SAFETY: `ptr` points to a live `T` whose size is a whole number of
+        // words and which has no padding; `read_unaligned` has no alignment
+        // requirement
+        .map(|i| unsafe { ptr.add(i).read_unaligned() })
+        .collect()
+}
+
+/// Field order of `String`, detected with a probe
+pub fn string_field_order() -> Option<FieldOrder> {
+    let mut probe = String::with_capacity(23);
+    probe.push_str("probe");
+    FieldOrder::detect(
+        &words_of(&probe),
+        probe.as_ptr() as usize,
+        probe.len(),
+        Some(probe.capacity()),
+    )
+}
+
+/// Field order of a `&str` fat pointer, detected with a probe
+pub fn str_field_order() -> Option<FieldOrder> {
+    let probe: &str = "probe";
+    FieldOrder::detect(
+        &words_of(&probe),
+        probe.as_ptr() as usize,
+        probe.len(),
+        None,
+    )
+}
+
+/// Field order of a `Box<str>` fat pointer, detected with a probe
+pub fn boxed_str_field_order() -> Option<FieldOrder> {
+    let probe: Box<str> = "probe".into();
+    FieldOrder::detect(
+        &words_of(&probe),
+        probe.as_ptr() as usize,
+        probe.len(),
+        None,
+    )
+}
+
+/// The 24 raw bytes of a `String`: pointer, length and capacity
+///
+/// Takes `&String` rather than `&str`: the object read is the `String`.
+#[allow(clippy::ptr_arg)]
+pub fn string_header(s: &String) -> RawHeader {
+    RawHeader::read(s, "String", string_field_order()).verify(
+        s.as_ptr(),
+        s.len(),
+        Some(s.capacity()),
+    )
+}
+
+/// The 16 raw bytes of a `&str`: pointer and length
+pub fn str_header(s: &&str) -> RawHeader {
+    RawHeader::read(s, "&str", str_field_order()).verify(s.as_ptr(), s.len(), None)
+}
+
+/// The 16 raw bytes of a `Box<str>`: pointer and length
+///
+/// Takes `&Box<str>` rather than `&str`: the object read is the box.
+#[allow(clippy::borrowed_box)]
+pub fn boxed_str_header(s: &Box<str>) -> RawHeader {
+    RawHeader::read(s, "Box<str>", boxed_str_field_order()).verify(s.as_ptr(), s.len(), None)
+}
+
+/// Size of the counts header std puts before the bytes of an `Rc<str>` or
+/// `Arc<str>`: the strong count, then the weak count
+const COUNTS_HEADER: usize = 2 * WORD;
+
+/// Loads the counts header in front of the bytes of a shared string
+///
+/// Other threads may update the counts of an `Arc<str>` at any time, so
+/// each word is loaded atomically rather than copied; for an `Rc<str>`,
+/// whose counts only this thread touches, the loads are plain reads.
+///
+/// # Safety
+///
+/// `data` must be the data pointer of a live `Rc<str>` or `Arc<str>`. std
+/// lays their allocation out as two `usize` counts directly followed by the
+/// bytes (`str` needs no padding), so the header is initialized, aligned
+/// memory in the same allocation.
+unsafe fn counts_of(data: *const u8) -> Vec<usize> {
+    let start = data.sub(COUNTS_HEADER) as *const AtomicUsize;
+    (0..COUNTS_HEADER / WORD)
+        .map(|i| (*start.add(i)).load(Ordering::Relaxed))
+        .collect()
+}
+
+/// Order of the strong and weak counts, detected with a probe
+///
+/// The probe has three strong and one weak reference, so its weak word
+/// (counting the one weak reference held by the strong ones) is 2. `Arc`
+/// lays out its counts like `Rc`; [`SharedAllocation`] verifies both.
+pub fn counts_order() -> Option<FieldOrder> {
+    let probe: Rc<str> = Rc::from("probe");
+    let _clones = [Rc::clone(&probe), Rc::clone(&probe)];
+    let _weak: Weak<str> = Rc::downgrade(&probe);
+    // SAFETY: `probe` is a live `Rc<str>`
+    let words = unsafe { counts_of(probe.as_ptr()) };
+    FieldOrder::detect_roles(
+        &words,
+        &[
+            (WordRole::Strong, Some(Rc::strong_count(&probe))),
+            (WordRole::Weak, Some(Rc::weak_count(&probe) + 1)),
+        ],
+    )
+}
+
+/// The one heap allocation behind every clone of an `Rc<str>` or
+/// `Arc<str>`: the counts header followed by the bytes
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedAllocation {
+    pub type_name: &'static str,
+    /// Strong references, as the API reports them
+    pub strong: usize,
+    /// Weak references, as the API reports them
+    pub weak: usize,
+    /// The counts header, at the start of the allocation
+    pub header: RawHeader,
+    /// The bytes, right after the header
+    pub data: HeapDump,
+    /// Usable size of the allocation's block, when the platform can tell
+    pub block_size: Option<usize>,
+}
+
+impl SharedAllocation {
+    fn read(type_name: &'static str, s: &str, strong: usize, weak: usize) -> Self {
+        let data = s.as_ptr();
+        // SAFETY: callers pass the bytes of a live `Rc<str>` or `Arc<str>`
+        let words = unsafe { counts_of(data) };
+        let address = data as usize - COUNTS_HEADER;
+        let mut header = RawHeader::from_words(words, address, type_name, counts_order());
+        header.verified = header.field(WordRole::Strong) == Some(strong)
+            && header.field(WordRole::Weak) == Some(weak + 1);
+        SharedAllocation {
+            type_name,
+            strong,
+            weak,
+            // SAFETY: the header starts the allocation, made by the global
+            // allocator
+            block_size: unsafe { allocator::usable_size(address as *const u8) },
+            header,
+            data: HeapDump::new(type_name, s, s.len()),
+        }
+    }
+
+    /// Bytes requested for the allocation: the header plus the bytes
+    pub fn size(&self) -> usize {
+        self.header.size + self.data.length
+    }
+
+    /// Prints the allocation: its header word by word, then the bytes
+    pub fn print(&self) {
+        let block = match self.block_size {
+            Some(block) => format!(" (allocator block: {} bytes)", block),
+            None => String::new(),
+        };
+        println!(
+            "\n{} {} allocation @ {:#x}: {}-byte counts header + {} bytes = {} bytes{}",
+            theme::icon("🔗").bright_cyan(),
+            self.type_name.bright_white().bold(),
+            self.header.address,
+            self.header.size,
+            self.data.length,
+            self.size(),
+            block
+        );
+        println!(
+            "   strong: {}, weak: {} (the weak word also counts one for all the strong references)",
+            self.strong.to_string().bright_yellow(),
+            self.weak.to_string().bright_yellow()
+        );
+        self.header.print();
+        self.data.print();
+    }
+}
+
+/// The counts header and bytes shared by the clones of `s`
+pub fn rc_str_allocation(s: &Rc<str>) -> SharedAllocation {
+    SharedAllocation::read("Rc<str>", s, Rc::strong_count(s), Rc::weak_count(s))
+}
+
+/// The counts header and bytes shared by the clones of `s`
+///
+/// Other threads may change the counts while they are read.
+pub fn arc_str_allocation(s: &Arc<str>) -> SharedAllocation {
+    SharedAllocation::read("Arc<str>", s, Arc::strong_count(s), Arc::weak_count(s))
+}
+
+/// What a byte of a string buffer is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteKind {
+    /// A single-byte (ASCII) character
+    Ascii,
+    /// The first byte of a character `width` bytes long
+    Lead { width: usize },
+    /// A continuation byte inside a multi-byte character
+    Continuation,
+    /// Allocated but unused - never read
+    Spare,
+}
+
+impl ByteKind {
+    /// Whether a character starts at this byte
+    pub fn is_boundary(&self) -> bool {
+        matches!(self, ByteKind::Ascii | ByteKind::Lead { .. })
+    }
+}
+
+/// One byte of a heap buffer
+#[derive(Debug, Clone, Serialize)]
+pub struct ByteCell {
+    /// The byte, or `None` for spare capacity
+    pub byte: Option<u8>,
+    pub kind: ByteKind,
+    /// The character starting at this byte
+    pub ch: Option<char>,
+}
+
+/// A string's heap buffer, byte by byte, up to its capacity
+#[derive(Debug, Clone, Serialize)]
+pub struct HeapDump {
+    pub type_name: &'static str,
+    pub address: usize,
+    pub length: usize,
+    pub capacity: usize,
+    pub cells: Vec<ByteCell>,
+}
+
+impl HeapDump {
+    /// Dumps the initialized bytes of `s` followed by `capacity - len`
+    /// cells of spare capacity
+    pub fn new(type_name: &'static str, s: &str, capacity: usize) -> Self {
+        let mut cells = Vec::with_capacity(capacity.max(s.len()));
+        for ch in s.chars() {
+            let width = ch.len_utf8();
+            let mut buf = [0; 4];
+            for (i, &byte) in ch.encode_utf8(&mut buf).as_bytes().iter().enumerate() {
+                let kind = match (i, width) {
+                    (0, 1) => ByteKind::Ascii,
+                    (0, _) => ByteKind::Lead { width },
+                    _ => ByteKind::Continuation,
+                };
+                cells.push(ByteCell {
+                    byte: Some(byte),
+                    kind,
+                    ch: (i == 0).then_some(ch),
+                });
+            }
+        }
+        cells.extend((s.len()..capacity).map(|_| ByteCell {
+            byte: None,
+            kind: ByteKind::Spare,
+            ch: None,
+        }));
+
+        HeapDump {
+            type_name,
+            address: s.as_ptr() as usize,
+            length: s.len(),
+            capacity,
+            cells,
+        }
+    }
+
+    /// Dumps a `String`'s buffer including its spare capacity
+    pub fn of_string(s: &String) -> Self {
+        Self::new("String", s, s.capacity())
+    }
+
+    /// Number of allocated but unused bytes
+    pub fn spare(&self) -> usize {
+        self.capacity.saturating_sub(self.length)
+    }
+
+    /// Prints the buffer as a hexdump
+    ///
+    /// Under every row, `^` marks the byte a character starts at and `-`
+    /// its continuation bytes; spare capacity shows as `..`.
+    pub fn print(&self) {
+        println!(
+            "\n{} {} heap buffer @ {:#x}: {} bytes used, {} spare",
+            theme::icon("🧱").bright_cyan(),
+            self.type_name.bright_white().bold(),
+            self.address,
+            self.length,
+            self.spare()
+        );
+        let unprintable = theme::glyphs().unprintable;
+        for (row, cells) in self.cells.chunks(ROW).enumerate() {
+            let mut hex = String::new();
+            let mut marks = String::new();
+            let mut text = String::new();
+            for cell in cells {
+                let (byte, mark) = match (cell.byte, cell.kind) {
+                    (Some(b), ByteKind::Ascii) => (format!("{:02x}", b).normal(), "^  "),
+                    (Some(b), ByteKind::Lead { .. }) => {
+                        (format!("{:02x}", b).bright_yellow(), "^--")
+                    }
+                    (Some(b), _) => (format!("{:02x}", b).yellow(), "---"),
+                    (None, _) => ("..".dimmed(), "   "),
+                };
+                hex.push_str(&format!("{} ", byte));
+                marks.push_str(mark);
+                if let Some(ch) = cell.ch {
+                    text.push(if ch.is_control() { unprintable } else { ch });
+                }
+            }
+            // Pad the last row so the text column lines up
+            let padding = "   ".repeat(ROW - cells.len());
+            println!("   {:04x}  {}{}  {}", row * ROW, hex, padding, text);
+            println!("         {}", marks.trim_end());
+        }
+    }
+}
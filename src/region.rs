@@ -0,0 +1,319 @@
+//! # Memory Region Classifier
+//!
+//! This module answers "where does this pointer actually point?" by reading
+//! the process's own memory map from `/proc/self/maps` and combining it with
+//! the executable bounds the linker records in the binary (`__ehdr_start`,
+//! `edata` and `end`).
+//!
+//! Unlike guessing from the numeric value of an address, this is correct
+//! under ASLR: the kernel tells us exactly which mapping contains the
+//! address and what it is backed by.
+//!
+//! The map only names the main thread's stack. Every thread that classifies
+//! an address therefore records where its own stack lies, so addresses on
+//! the stacks of those threads are recognised from any other thread too.
+
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// The kind of memory a pointer points into
+///
+/// `/proc/self/maps` names the main thread's stack but not the ones other
+/// threads run on, which are plain anonymous mappings. A pointer into the
+/// stack of another thread is an [`OtherThreadStack`](Self::OtherThreadStack)
+/// if that thread has classified an address itself, and an
+/// [`AnonymousMmap`](Self::AnonymousMmap) otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryRegion {
+    /// Executable code of this binary
+    Text,
+    /// Read-only data of this binary (string literals live here)
+    Rodata,
+    /// Initialized, writable statics of this binary
+    Data,
+    /// Zero-initialized statics of this binary
+    Bss,
+    /// The `brk` heap used by the allocator's main arena
+    Heap,
+    /// The stack of the calling thread, or the main thread's `[stack]`
+    Stack,
+    /// The stack of another live thread that has classified an address
+    OtherThreadStack,
+    /// An anonymous mapping: other allocator arenas, large allocations, and
+    /// the stacks of threads never seen classifying
+    AnonymousMmap,
+    /// A mapping backed by a file other than this binary
+    FileMapping(String),
+    /// Not mapped at all, e.g. the dangling pointer of an empty `String`
+    Unmapped,
+    /// The memory map could not be read on this platform
+    Unknown,
+}
+
+impl MemoryRegion {
+    /// Whether the region is one the allocator hands out memory from
+    pub fn is_heap(&self) -> bool {
+        matches!(self, MemoryRegion::Heap | MemoryRegion::AnonymousMmap)
+    }
+
+    /// Whether the region is part of the binary image itself
+    pub fn is_static(&self) -> bool {
+        matches!(
+            self,
+            MemoryRegion::Text | MemoryRegion::Rodata | MemoryRegion::Data | MemoryRegion::Bss
+        )
+    }
+}
+
+impl fmt::Display for MemoryRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryRegion::Text => write!(f, "text (binary code)"),
+            MemoryRegion::Rodata => write!(f, "rodata (binary, read-only)"),
+            MemoryRegion::Data => write!(f, "data (binary, writable statics)"),
+            MemoryRegion::Bss => write!(f, "bss (binary, zeroed statics)"),
+            MemoryRegion::Heap => write!(f, "heap"),
+            MemoryRegion::Stack => write!(f, "thread stack"),
+            MemoryRegion::OtherThreadStack => write!(f, "another thread's stack"),
+            MemoryRegion::AnonymousMmap => write!(f, "anonymous mmap"),
+            MemoryRegion::FileMapping(path) => write!(f, "file mmap ({})", path),
+            MemoryRegion::Unmapped => write!(f, "unmapped"),
+            MemoryRegion::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// One line of `/proc/self/maps`
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    pub start: usize,
+    pub end: usize,
+    /// Permission string, e.g. `r-xp`
+    pub perms: String,
+    /// Backing file, or a pseudo-name like `[heap]`; empty if anonymous
+    pub pathname: String,
+}
+
+impl Mapping {
+    /// Parses a single `/proc/self/maps` line
+    ///
+    /// Format: `start-end perms offset dev inode pathname`
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let (start, end) = fields.next()?.split_once('-')?;
+        let perms = fields.next()?.to_string();
+        // offset, dev, inode
+        for _ in 0..3 {
+            fields.next()?;
+        }
+        // The pathname may itself contain spaces
+        let pathname = fields.collect::<Vec<_>>().join(" ");
+
+        Some(Self {
+            start: usize::from_str_radix(start, 16).ok()?,
+            end: usize::from_str_radix(end, 16).ok()?,
+            perms,
+            pathname,
+        })
+    }
+
+    /// Whether `addr` falls inside this mapping
+    pub fn contains(&self, addr: usize) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+
+    fn is_writable(&self) -> bool {
+        self.perms.as_bytes().get(1) == Some(&b'w')
+    }
+
+    fn is_executable(&self) -> bool {
+        self.perms.as_bytes().get(2) == Some(&b'x')
+    }
+}
+
+/// Stacks of the live threads that have classified an address
+static THREAD_STACKS: Mutex<Vec<Range<usize>>> = Mutex::new(Vec::new());
+
+/// This thread's entry in [`THREAD_STACKS`], removed when the thread exits
+/// and its stack may be unmapped or reused
+struct ThreadStack(Option<Range<usize>>);
+
+impl ThreadStack {
+    fn register() -> Self {
+        let stack = current_stack();
+        if let Some(stack) = &stack {
+            let mut stacks = THREAD_STACKS.lock().unwrap_or_else(|e| e.into_inner());
+            stacks.push(stack.clone());
+        }
+        Self(stack)
+    }
+}
+
+impl Drop for ThreadStack {
+    fn drop(&mut self) {
+        if let Some(stack) = &self.0 {
+            let mut stacks = THREAD_STACKS.lock().unwrap_or_else(|e| e.into_inner());
+            stacks.retain(|s| s != stack);
+        }
+    }
+}
+
+thread_local! {
+    static THIS_STACK: ThreadStack = ThreadStack::register();
+}
+
+/// Bounds of the calling thread's stack
+#[cfg(target_os = "linux")]
+fn current_stack() -> Option<Range<usize>> {
+    // SAFETY: the attributes are initialized by `pthread_getattr_np` before
+    // they are read, and destroyed once
+    unsafe {
+        let mut attr: libc::pthread_attr_t = std::mem::zeroed();
+        if libc::pthread_getattr_np(libc::pthread_self(), &mut attr) != 0 {
+            return None;
+        }
+        let (mut addr, mut size) = (std::ptr::null_mut(), 0);
+        let found = libc::pthread_attr_getstack(&attr, &mut addr, &mut size) == 0;
+        libc::pthread_attr_destroy(&mut attr);
+        found.then(|| addr as usize..addr as usize + size)
+    }
+}
+
+/// Other threads' stacks are not looked up outside Linux
+#[cfg(not(target_os = "linux"))]
+fn current_stack() -> Option<Range<usize>> {
+    None
+}
+
+/// Whether `addr` lies on a registered stack other than `own`
+fn on_other_thread_stack(addr: usize, own: Option<&Range<usize>>) -> bool {
+    let stacks = THREAD_STACKS.lock().unwrap_or_else(|e| e.into_inner());
+    stacks
+        .iter()
+        .any(|stack| Some(stack) != own && stack.contains(&addr))
+}
+
+/// Bounds of the executable image, as recorded by the linker
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutableBounds {
+    /// Start of the ELF header (first byte of the image)
+    pub start: usize,
+    /// End of initialized data (`.data`); `.bss` starts here
+    pub edata: usize,
+    /// End of `.bss` (last byte of the image)
+    pub end: usize,
+}
+
+impl ExecutableBounds {
+    /// Reads the linker-provided symbols of the running binary
+    #[cfg(target_os = "linux")]
+    pub fn current() -> Self {
+        extern "C" {
+            static __ehdr_start: u8;
+            static edata: u8;
+            static end: u8;
+        }
+
+        // Only the addresses of these linker symbols matter; they are never
+        // read through
+        Self {
+            start: std::ptr::addr_of!(__ehdr_start) as usize,
+            edata: std::ptr::addr_of!(edata) as usize,
+            end: std::ptr::addr_of!(end) as usize,
+        }
+    }
+
+    /// Whether `addr` lies inside the executable image
+    pub fn contains(&self, addr: usize) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+}
+
+/// A snapshot of the process's memory map
+#[derive(Debug, Clone)]
+pub struct MemoryMap {
+    pub mappings: Vec<Mapping>,
+    pub executable: Option<ExecutableBounds>,
+}
+
+impl MemoryMap {
+    /// Reads the current memory map of this process
+    ///
+    /// The map changes as the heap grows and threads start, so take a fresh
+    /// snapshot when classifying newly allocated memory.
+    #[cfg(target_os = "linux")]
+    pub fn current() -> Self {
+        let mappings = std::fs::read_to_string("/proc/self/maps")
+            .map(|maps| maps.lines().filter_map(Mapping::parse).collect())
+            .unwrap_or_default();
+
+        Self {
+            mappings,
+            executable: Some(ExecutableBounds::current()),
+        }
+    }
+
+    /// There is no `/proc/self/maps` outside Linux
+    #[cfg(not(target_os = "linux"))]
+    pub fn current() -> Self {
+        Self {
+            mappings: Vec::new(),
+            executable: None,
+        }
+    }
+
+    /// Finds the mapping that contains `addr`
+    pub fn mapping_for(&self, addr: usize) -> Option<&Mapping> {
+        self.mappings.iter().find(|m| m.contains(addr))
+    }
+
+    /// Classifies the memory region `addr` points into
+    pub fn classify(&self, addr: usize) -> MemoryRegion {
+        if self.mappings.is_empty() {
+            return MemoryRegion::Unknown;
+        }
+
+        // Makes this thread's stack known to the others; `try_with` fails
+        // only while the thread is being torn down
+        let own_stack = THIS_STACK.try_with(|stack| stack.0.clone()).ok().flatten();
+
+        let Some(mapping) = self.mapping_for(addr) else {
+            return MemoryRegion::Unmapped;
+        };
+
+        if let Some(exe) = self.executable.filter(|exe| exe.contains(addr)) {
+            return if mapping.is_executable() {
+                MemoryRegion::Text
+            } else if !mapping.is_writable() {
+                MemoryRegion::Rodata
+            } else if addr < exe.edata {
+                MemoryRegion::Data
+            } else {
+                MemoryRegion::Bss
+            };
+        }
+
+        // A local variable lives on this thread's stack, so whichever
+        // mapping contains it is the current thread's stack
+        let marker = 0u8;
+        let stack_marker = std::hint::black_box(&marker) as *const u8 as usize;
+
+        match mapping.pathname.as_str() {
+            "[heap]" => MemoryRegion::Heap,
+            "[stack]" => MemoryRegion::Stack,
+            _ if mapping.contains(stack_marker) => MemoryRegion::Stack,
+            "" if on_other_thread_stack(addr, own_stack.as_ref()) => MemoryRegion::OtherThreadStack,
+            "" => MemoryRegion::AnonymousMmap,
+            path => MemoryRegion::FileMapping(path.to_string()),
+        }
+    }
+}
+
+/// Classifies the memory region `addr` points into, using a fresh snapshot
+/// of the memory map
+pub fn classify(addr: usize) -> MemoryRegion {
+    MemoryMap::current().classify(addr)
+}
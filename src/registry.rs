@@ -0,0 +1,290 @@
+//! # Demo Registry
+//!
+//! Every lesson the lab can run is a [`Demo`]: a name for the command line,
+//! a one-line description, tags to select lessons by topic, and an async
+//! [`run`](Demo::run) that produces a report and renders it. A [`Registry`]
+//! holds them in session order.
+//!
+//! [`Registry::builtin`] registers the lab's own demonstrations, then the
+//! [`lessons`] module's, so a new lesson needs no change to the `lab` binary:
+//! `lab list` shows it and `lab run NAME` runs it.
+//!
+//! ```ignore
+//! let registry = Registry::builtin();
+//! let cx = Context::new(&renderer, &DemoInputs::default());
+//! for demo in registry.tagged("unicode") {
+//!     demo.run(&cx).await;
+//! }
+//! ```
+
+use crate::allocator::{self, AllocStats};
+use crate::demos::{self, DemoInputs};
+use crate::lessons;
+use crate::render::Renderer;
+use futures::future::LocalBoxFuture;
+
+/// What a demo gets to work with
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    /// Presents the demo's report as text or JSON records
+    pub render: &'a Renderer,
+    /// The inputs of the session, from `lab run`'s flags
+    pub inputs: &'a DemoInputs,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(render: &'a Renderer, inputs: &'a DemoInputs) -> Self {
+        Self { render, inputs }
+    }
+
+    /// Runs `f`, counting its allocations on this thread when the counting
+    /// allocator is installed
+    pub fn track<R>(&self, f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+        allocator::track_if_installed(f)
+    }
+}
+
+/// A lesson the lab can run
+pub trait Demo {
+    /// Name on the command line, in kebab-case
+    fn name(&self) -> &'static str;
+
+    /// One line for `lab list` and `--help`
+    fn description(&self) -> &'static str;
+
+    /// Topics for selecting demos with `--tag`
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Runs the demonstration and renders its report
+    fn run<'a>(&'a self, cx: &'a Context<'_>) -> LocalBoxFuture<'a, ()>;
+}
+
+/// Demos in session order
+#[derive(Default)]
+pub struct Registry {
+    demos: Vec<Box<dyn Demo>>,
+}
+
+impl Registry {
+    /// A registry without any demo
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The lab's demonstrations, followed by the [`lessons`] module's
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register(StringTypes)
+            .register(Ownership)
+            .register(Capacity)
+            .register(Cow)
+            .register(Async)
+            .register(Transformations)
+            .register(Unicode)
+            .register(Normalization)
+            .register(Case)
+            .register(Growth)
+            .register(SizeClasses)
+            .register(Shared)
+            .register(Concurrency)
+            .register(Pipeline);
+        lessons::register(&mut registry);
+        registry
+    }
+
+    /// Adds a demo after the ones registered so far
+    ///
+    /// # Panics
+    ///
+    /// If a demo with the same name is already registered.
+    pub fn register(&mut self, demo: impl Demo + 'static) -> &mut Self {
+        assert!(
+            self.get(demo.name()).is_none(),
+            "a demo named {:?} is already registered",
+            demo.name()
+        );
+        self.demos.push(Box::new(demo));
+        self
+    }
+
+    /// The demo with the given name
+    pub fn get(&self, name: &str) -> Option<&dyn Demo> {
+        self.iter().find(|demo| demo.name() == name)
+    }
+
+    /// Every demo, in session order
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Demo> {
+        self.demos.iter().map(Box::as_ref)
+    }
+
+    /// The demos carrying `tag`, in session order
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a dyn Demo> {
+        self.iter().filter(move |demo| demo.tags().contains(&tag))
+    }
+
+    pub fn len(&self) -> usize {
+        self.demos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.demos.is_empty()
+    }
+}
+
+/// Declares a built-in demo: a unit struct whose `run` renders one report
+macro_rules! builtin {
+    ($demo:ident, $name:literal, $description:literal, [$($tag:literal),*], |$cx:ident| $body:expr) => {
+        struct $demo;
+
+        impl Demo for $demo {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn description(&self) -> &'static str {
+                $description
+            }
+
+            fn tags(&self) -> &'static [&'static str] {
+                &[$($tag),*]
+            }
+
+            fn run<'a>(&'a self, $cx: &'a Context<'_>) -> LocalBoxFuture<'a, ()> {
+                Box::pin(async move { $body })
+            }
+        }
+    };
+}
+
+builtin!(
+    StringTypes,
+    "string-types",
+    "String types and their memory layout",
+    ["memory", "types"],
+    |cx| cx
+        .render
+        .string_types(&demos::string_types(cx.inputs).await)
+);
+
+builtin!(
+    Ownership,
+    "ownership",
+    "Zero-cost moves vs allocating clones",
+    ["memory", "ownership"],
+    |cx| cx.render.ownership(&demos::ownership(cx.inputs).await)
+);
+
+builtin!(
+    Capacity,
+    "capacity",
+    "Capacity management and reallocation",
+    ["memory", "capacity"],
+    |cx| cx
+        .render
+        .capacity_and_growth(&demos::capacity_and_growth(cx.inputs).await)
+);
+
+builtin!(
+    Cow,
+    "cow",
+    "Clone-on-write delays allocation until mutation",
+    ["memory", "ownership", "types"],
+    |cx| cx
+        .render
+        .clone_on_write(&demos::clone_on_write(cx.inputs).await)
+);
+
+builtin!(
+    Async,
+    "async",
+    "Concurrent string processing on Tokio",
+    ["async", "io"],
+    |cx| cx
+        .render
+        .async_operations(&demos::async_operations(cx.inputs).await)
+);
+
+builtin!(
+    Transformations,
+    "transformations",
+    "Timed and benchmarked string operations",
+    ["timing", "unicode"],
+    |cx| cx
+        .render
+        .transformations(&demos::transformations(cx.inputs).await)
+);
+
+builtin!(
+    Unicode,
+    "unicode",
+    "UTF-8 bytes, chars and grapheme clusters",
+    ["unicode"],
+    |cx| cx.render.unicode(&demos::unicode(cx.inputs).await)
+);
+
+builtin!(
+    Normalization,
+    "normalization",
+    "Equal-looking strings and normalization forms",
+    ["unicode"],
+    |cx| cx.render.normalization(&demos::normalization().await)
+);
+
+builtin!(
+    Case,
+    "case",
+    "Case folding, titlecase, final sigma and locale tailoring",
+    ["unicode"],
+    |cx| cx
+        .render
+        .case_mapping(&demos::case_mapping(cx.inputs).await)
+);
+
+builtin!(
+    Growth,
+    "growth",
+    "Capacity growth curve, reserve and shrink_to",
+    ["memory", "capacity", "allocator"],
+    |cx| cx
+        .render
+        .capacity_growth(&demos::capacity_growth(cx.inputs).await)
+);
+
+builtin!(
+    SizeClasses,
+    "size-classes",
+    "Allocator size classes behind requested capacities",
+    ["memory", "allocator"],
+    |cx| cx
+        .render
+        .size_classes(&demos::size_classes(cx.inputs).await)
+);
+
+builtin!(
+    Shared,
+    "shared",
+    "Rc<str> and Arc<str>: reference counts and one shared allocation",
+    ["memory", "ownership", "async"],
+    |cx| cx
+        .render
+        .shared_ownership(&demos::shared_ownership(cx.inputs).await)
+);
+
+builtin!(
+    Concurrency,
+    "concurrency",
+    "Concurrency limit, timeouts and cancellation for a batch of tasks",
+    ["async"],
+    |cx| cx.render.concurrency(&demos::concurrency(cx.inputs).await)
+);
+
+builtin!(
+    Pipeline,
+    "pipeline",
+    "Strings moving between tasks through channels, buffer by buffer",
+    ["async", "threads", "ownership"],
+    |cx| cx.render.pipeline(&demos::pipeline(cx.inputs).await)
+);
@@ -0,0 +1,1617 @@
+//! # Report Rendering
+//!
+//! Presentation for the reports produced by the [`demos`](crate::demos)
+//! module, in one of two [`OutputFormat`]s:
+//!
+//! - **Text** - colorful section headers, memory layouts, timing and insights
+//!   for a human at a terminal
+//! - **JSON** - one JSON object per line for every event worth
+//!   post-processing: section starts, memory layouts, timings, layout
+//!   comparisons and byte reports. Each record has a `kind` field naming the
+//!   event; the prose around them is left out.
+
+use crate::batch::Outcome;
+use crate::bench;
+use crate::demos::*;
+use crate::growth::GrowthCurve;
+use crate::inspector::{ByteReport, LayoutComparison, StringMemoryInfo};
+use crate::lifetime::{Action, LifetimeEvent, Timeline};
+use crate::raw::{HeapDump, RawHeader, SharedAllocation};
+use crate::repl::{ReplError, Step};
+use crate::span_tree::SpanNode;
+use crate::theme;
+use crate::transformer::TimedResult;
+use colored::Colorize;
+use serde::Serialize;
+
+/// How reports are presented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable terminal output
+    #[default]
+    Text,
+    /// JSON records, one per line
+    Json,
+}
+
+/// A JSON output line: the event's fields, tagged with its kind
+#[derive(Serialize)]
+struct Record<'a, T: Serialize + ?Sized> {
+    kind: &'static str,
+    #[serde(flatten)]
+    event: &'a T,
+}
+
+/// `println!` for text output only; JSON output carries just the records
+macro_rules! say {
+    ($renderer:expr) => {
+        if $renderer.is_text() {
+            println!();
+        }
+    };
+    ($renderer:expr, $($arg:tt)*) => {
+        if $renderer.is_text() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Presents demonstration reports in an [`OutputFormat`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Renderer {
+    format: OutputFormat,
+}
+
+impl Renderer {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    /// Prints one JSON record of the given kind
+    pub fn emit<T: Serialize + ?Sized>(&self, kind: &'static str, event: &T) {
+        let record = Record { kind, event };
+        println!(
+            "{}",
+            serde_json::to_string(&record).expect("records serialize to JSON")
+        );
+    }
+
+    /// Presents a memory layout
+    pub fn layout(&self, info: &StringMemoryInfo) {
+        match self.format {
+            OutputFormat::Text => println!("\n{}", info),
+            OutputFormat::Json => self.emit("memory_layout", info),
+        }
+    }
+
+    /// Presents the timing (and allocations) of an operation
+    pub fn timing<T: Serialize>(&self, result: &TimedResult<T>) {
+        match self.format {
+            OutputFormat::Text => result.display_timing(),
+            OutputFormat::Json => self.emit("timing", result),
+        }
+    }
+
+    /// Presents a before/after layout comparison with its analysis
+    pub fn comparison(&self, comparison: &LayoutComparison) {
+        match self.format {
+            OutputFormat::Text => comparison.print(),
+            OutputFormat::Json => self.emit("layout_comparison", comparison),
+        }
+    }
+
+    /// Presents the byte-level representation of a string
+    pub fn bytes(&self, report: &ByteReport) {
+        match self.format {
+            OutputFormat::Text => report.print(),
+            OutputFormat::Json => self.emit("bytes", report),
+        }
+    }
+
+    /// Presents the raw bytes of a string object
+    pub fn raw_header(&self, header: &RawHeader) {
+        match self.format {
+            OutputFormat::Text => header.print(),
+            OutputFormat::Json => self.emit("raw_header", header),
+        }
+    }
+
+    /// Presents a string's heap buffer as a hexdump
+    pub fn heap_dump(&self, dump: &HeapDump) {
+        match self.format {
+            OutputFormat::Text => dump.print(),
+            OutputFormat::Json => self.emit("heap_dump", dump),
+        }
+    }
+
+    /// Presents the counts header and bytes of a shared allocation
+    pub fn shared_allocation(&self, allocation: &SharedAllocation) {
+        match self.format {
+            OutputFormat::Text => allocation.print(),
+            OutputFormat::Json => self.emit("shared_allocation", allocation),
+        }
+    }
+
+    /// Prints a fancy section header
+    pub fn print_section(&self, title: &str) {
+        if !self.is_text() {
+            #[derive(Serialize)]
+            struct Section<'a> {
+                title: &'a str,
+            }
+            return self.emit("section", &Section { title });
+        }
+
+        let double = theme::glyphs().double;
+        let rule = double.horizontal.repeat(59);
+        println!("\n\n");
+        println!(
+            "{}",
+            format!("{}{}{}", double.top_left, rule, double.top_right)
+                .bright_cyan()
+                .bold()
+        );
+        println!(
+            "{}",
+            format!("{}  {:<56} {}", double.vertical, title, double.vertical)
+                .bright_cyan()
+                .bold()
+        );
+        println!(
+            "{}",
+            format!("{}{}{}", double.bottom_left, rule, double.bottom_right)
+                .bright_cyan()
+                .bold()
+        );
+    }
+
+    /// Prints the laboratory banner
+    pub fn print_banner(&self) {
+        if !self.is_text() {
+            return;
+        }
+
+        let double = theme::glyphs().double;
+        let crab = theme::icon("🦀");
+        // Each emoji is one char but two columns wide
+        let wide = if crab.len() > 1 { 2 } else { 0 };
+        let lines = [
+            (String::new(), 0),
+            (
+                format!(
+                    "       {}  THE INTROSPECTIVE STRING LABORATORY  {}",
+                    crab, crab
+                ),
+                wide,
+            ),
+            (String::new(), 0),
+            (
+                " A Self-Aware Rust Program Teaching String Internals".to_string(),
+                0,
+            ),
+            (String::new(), 0),
+        ];
+        let rule = double.horizontal.repeat(67);
+        println!(
+            "{}",
+            format!("{}{}{}", double.top_left, rule, double.top_right)
+                .bright_magenta()
+                .bold()
+        );
+        for (line, wide) in lines {
+            let width = 66 - wide;
+            println!(
+                "{}",
+                format!("{} {:<width$}{}", double.vertical, line, double.vertical)
+                    .bright_magenta()
+                    .bold()
+            );
+        }
+        println!(
+            "{}",
+            format!("{}{}{}", double.bottom_left, rule, double.bottom_right)
+                .bright_magenta()
+                .bold()
+        );
+    }
+
+    /// Prints an insight heading
+    fn print_insight(&self, text: &str) {
+        say!(
+            self,
+            "\n{} {}",
+            format!("{} INSIGHT:", theme::icon("💡"))
+                .bright_green()
+                .bold(),
+            text
+        );
+    }
+
+    /// Renders the string types report
+    pub fn string_types(&self, report: &StringTypesReport) {
+        self.print_section("1. STRING TYPES AND MEMORY LAYOUT");
+
+        self.layout(&report.literal);
+        self.layout(&report.owned);
+        self.layout(&report.boxed);
+
+        say!(
+            self,
+            "\n{}",
+            format!("{} SIZE ANALYSIS:", theme::icon("📏"))
+                .bright_yellow()
+                .bold()
+        );
+        for size in &report.sizes {
+            say!(
+                self,
+                "  {:<10} {} bytes ({})",
+                format!("{}:", size.type_name),
+                size.size,
+                size.layout
+            );
+        }
+
+        self.bytes(&report.literal_bytes);
+    }
+
+    /// Renders the ownership report
+    pub fn ownership(&self, report: &OwnershipReport) {
+        self.print_section("2. OWNERSHIP, MOVES, AND CLONES");
+
+        self.layout(&report.original);
+
+        self.comparison(&report.moved);
+
+        self.print_insight("The data pointers are identical!");
+        say!(
+            self,
+            "   Only the {}-byte handle was copied, to {:#x}.",
+            report.moved.after.handle_size,
+            report.moved.after.handle_ptr
+        );
+        say!(self, "   Move is zero-cost - just transfers ownership.");
+        say!(self, "   No data was copied, no new allocation happened.");
+
+        self.comparison(&report.cloned);
+
+        self.print_insight("The data pointers are different!");
+        say!(self, "   Clone created a NEW heap allocation.");
+        say!(self, "   All bytes were copied to the new location.");
+        say!(
+            self,
+            "   Cost: {} bytes allocated + {} bytes copied",
+            report.clone_allocated,
+            report.clone_copied
+        );
+    }
+
+    /// Renders the capacity report
+    pub fn capacity_and_growth(&self, report: &CapacityReport) {
+        self.print_section("3. CAPACITY MANAGEMENT AND REALLOCATION");
+
+        say!(
+            self,
+            "\n{} Created String::with_capacity({})",
+            theme::glyphs().check.bright_green(),
+            report.empty.capacity
+        );
+        self.layout(&report.empty);
+
+        self.comparison(&report.within_capacity);
+        self.comparison(&report.reallocation);
+
+        self.print_insight("Reallocation details:");
+        say!(
+            self,
+            "   Old capacity: {} bytes",
+            report.reallocation.before.capacity
+        );
+        say!(
+            self,
+            "   New capacity: {} bytes",
+            report.reallocation.after.capacity
+        );
+        say!(
+            self,
+            "   Growth strategy: max(2 {} capacity, required, 8) - see demo 10",
+            theme::glyphs().times
+        );
+        say!(self, "   Performance cost: O(n) copy of all existing data");
+    }
+
+    /// Renders the clone-on-write report
+    pub fn clone_on_write(&self, report: &CowReport) {
+        self.print_section("4. CLONE-ON-WRITE (COW) OPTIMIZATION");
+
+        self.layout(&report.borrowed);
+
+        say!(
+            self,
+            "\n{} Cow::Borrowed points to the original string",
+            theme::glyphs().check.bright_green()
+        );
+        say!(self, "   No allocation, no copy - just a reference");
+
+        match self.format {
+            // The report spells the label's arrow `->`, whatever the theme
+            OutputFormat::Text => LayoutComparison {
+                operation: theme::arrows(&report.to_owned.operation).into_owned(),
+                ..report.to_owned.clone()
+            }
+            .print(),
+            OutputFormat::Json => self.comparison(&report.to_owned),
+        }
+
+        self.print_insight("Cow delayed allocation until mutation!");
+        say!(self, "   Use case: API that might or might not modify data");
+        say!(self, "   Benefit: Zero cost when no modification needed");
+    }
+
+    /// Renders the async operations report
+    pub fn async_operations(&self, report: &AsyncReport) {
+        self.print_section("5. ASYNCHRONOUS STRING PROCESSING");
+
+        say!(
+            self,
+            "\n{} Launched {} concurrent tasks",
+            theme::icon("🚀").bright_yellow(),
+            report.tasks.len()
+        );
+        say!(
+            self,
+            "   Each task is running independently on the Tokio runtime"
+        );
+
+        if report.all_succeeded() {
+            say!(
+                self,
+                "\n{} All tasks completed!",
+                theme::glyphs().check.bright_green().bold()
+            );
+        }
+
+        for task in &report.tasks {
+            self.task(task);
+        }
+
+        self.print_insight("Async runtime details:");
+        say!(self, "   Runtime: Tokio (work-stealing scheduler)");
+        say!(self, "   Tasks are lightweight (not OS threads)");
+        say!(self, "   Concurrent execution without blocking");
+        say!(
+            self,
+            "   I/O copies bytes at every boundary: into the kernel, out of it,"
+        );
+        say!(
+            self,
+            "   and into buffers a blocking thread can own. Moves and"
+        );
+        say!(
+            self,
+            "   String::from_utf8 hand a buffer over without copying."
+        );
+    }
+
+    /// Presents a spawned task's output and the copies it made
+    fn task(&self, task: &TaskReport) {
+        if !self.is_text() {
+            return self.emit("task", task);
+        }
+
+        let arrow = theme::glyphs().arrow;
+        match task.output() {
+            Some(output) => println!("\n   {} {} {}", task.name.bright_cyan(), arrow, output),
+            None => println!(
+                "\n   {} {} {}",
+                task.name.bright_cyan(),
+                arrow,
+                task.result
+                    .outcome
+                    .to_string()
+                    .to_uppercase()
+                    .bright_red()
+                    .bold()
+            ),
+        }
+        for step in task.steps() {
+            let buffer = match step.buffer {
+                Some(buffer) => format!("@ {:#x}", buffer),
+                None => String::new(),
+            };
+            let copied = match step.copied {
+                0 => "no copy".bright_green(),
+                n => format!("copied {} B", n).bright_yellow(),
+            };
+            let allocated = match step.allocated {
+                0 => String::new(),
+                n => format!(", allocated {} B", n),
+            };
+            let line = format!(
+                "      {:<72} {}{} {}",
+                theme::arrows(&step.step),
+                copied,
+                allocated,
+                buffer.dimmed()
+            );
+            println!("{}", line.trim_end());
+        }
+        if !task.steps().is_empty() {
+            let copied: usize = task.steps().iter().map(|s| s.copied).sum();
+            let allocated: usize = task.steps().iter().map(|s| s.allocated).sum();
+            println!(
+                "      {}",
+                format!("total: {} B copied, {} B allocated", copied, allocated).bold()
+            );
+        }
+    }
+
+    /// Renders the shared ownership report
+    pub fn shared_ownership(&self, report: &SharedReport) {
+        let arrow = theme::glyphs().arrow;
+        self.print_section("12. SHARED OWNERSHIP: Rc<str> AND Arc<str>");
+
+        self.layout(&report.rc);
+        self.shared_allocation(&report.rc_allocation);
+
+        say!(
+            self,
+            "\n{} Handing the text to {} spawned tasks:",
+            theme::icon("🚀").bright_yellow(),
+            report.approaches.first().map_or(0, |a| a.tasks.len())
+        );
+        for approach in &report.approaches {
+            if !self.is_text() {
+                self.emit("sharing_approach", approach);
+                continue;
+            }
+
+            let allocations = match &approach.allocations {
+                Some(stats) => stats.to_string().bright_yellow(),
+                None => "not counted (no counting allocator)".dimmed(),
+            };
+            println!("   {:<30} {}", approach.name, allocations);
+            for task in &approach.tasks {
+                match task.output() {
+                    Some(output) => println!("      {} {} {}", task.name, arrow, output),
+                    None => println!(
+                        "      {} {} {}",
+                        task.name,
+                        arrow,
+                        task.result
+                            .outcome
+                            .to_string()
+                            .to_uppercase()
+                            .bright_red()
+                            .bold()
+                    ),
+                }
+            }
+        }
+
+        say!(
+            self,
+            "\n   The Arc<str>, read while holding one clone per task:"
+        );
+        self.shared_allocation(&report.arc_allocation);
+
+        self.print_insight("Rc/Arc clones share one allocation: counts, then bytes.");
+        say!(
+            self,
+            "   Cloning bumps the strong count instead of copying the bytes, so"
+        );
+        say!(
+            self,
+            "   both approaches pay for spawning but only String pays per copy."
+        );
+        say!(
+            self,
+            "   The bytes are freed when the strong count drops to zero; the"
+        );
+        say!(
+            self,
+            "   counts live on until the last weak reference is gone too."
+        );
+    }
+
+    /// Renders the concurrency controls report
+    pub fn concurrency(&self, report: &ConcurrencyReport) {
+        self.print_section("13. CONCURRENCY: LIMITS, TIMEOUTS, CANCELLATION");
+
+        say!(
+            self,
+            "\n{} {} inputs, at most {} at once, {} ms each, cancelled after {} ms:",
+            theme::icon("🚦").bright_yellow(),
+            report.tasks.len(),
+            report.max_concurrent,
+            report.timeout_ms,
+            report.cancel_after_ms
+        );
+        say!(
+            self,
+            "   {:>3}  {:<12} {:>9} {:>9}  {}",
+            "#",
+            "input",
+            "queued",
+            "ran",
+            "outcome"
+        );
+        for task in &report.tasks {
+            if !self.is_text() {
+                self.emit("batch_task", task);
+                continue;
+            }
+
+            let arrow = theme::glyphs().arrow;
+            let millis = |nanos: Option<u128>| match nanos {
+                Some(nanos) => format!("{:.1} ms", nanos as f64 / 1e6),
+                None => "-".to_string(),
+            };
+            let outcome = match &task.result.outcome {
+                Outcome::Ok(output) => format!("ok {} {}", arrow, output).bright_green(),
+                Outcome::TimedOut | Outcome::Cancelled => {
+                    task.result.outcome.to_string().bright_yellow()
+                }
+                other => other.to_string().bright_red(),
+            };
+            let join_error = match task.result.join_error {
+                Some(kind) => format!("JoinError::{:?}", kind).dimmed().to_string(),
+                None => String::new(),
+            };
+            let line = format!(
+                "   {:>3}  {:<12} {:>9} {:>9}  {} {}",
+                task.result.index,
+                task.input,
+                millis(task.result.queued_nanos),
+                millis(task.result.run_nanos),
+                outcome,
+                join_error
+            );
+            println!("{}", line.trim_end());
+        }
+        say!(
+            self,
+            "\n   {} ok, {} failed, {} timed out, {} cancelled, {} panicked",
+            report.count(Outcome::is_ok),
+            report.count(|o| matches!(o, Outcome::Failed(_))),
+            report.count(|o| matches!(o, Outcome::TimedOut)),
+            report.count(|o| matches!(o, Outcome::Cancelled)),
+            report.count(|o| matches!(o, Outcome::Panicked(_)))
+        );
+
+        self.print_insight("One bad task does not take the batch down with it.");
+        say!(
+            self,
+            "   A semaphore keeps the rest queued until a permit frees up, and"
+        );
+        say!(
+            self,
+            "   the timeout starts with the permit, not with the spawn. A panic"
+        );
+        say!(
+            self,
+            "   stays in its task and comes back as a JoinError; cancelling the"
+        );
+        say!(self, "   token stops queued tasks before they start.");
+    }
+
+    /// Renders the channel pipeline report
+    pub fn pipeline(&self, report: &PipelineReport) {
+        self.print_section("14. PIPELINE: ZERO-COPY MOVES ACROSS THREADS");
+
+        let (arrow, down) = (theme::glyphs().arrow, theme::glyphs().down);
+        say!(
+            self,
+            "\n{} One task per stage, connected by channels: source {arrow} {} {arrow} sink",
+            theme::icon("🔗").bright_cyan(),
+            report.stages.join(&format!(" {} ", arrow))
+        );
+        for journey in &report.journeys {
+            if !self.is_text() {
+                self.emit("journey", journey);
+                continue;
+            }
+
+            let hop = |sent: usize, received: usize| {
+                if sent == received {
+                    "channel: same buffer, handle moved".bright_green()
+                } else {
+                    "channel: DIFFERENT buffer".bright_red().bold()
+                }
+            };
+            let hops = journey.hops();
+
+            println!(
+                "\n   '{}' {} '{}'",
+                journey.input.bright_cyan(),
+                arrow,
+                journey.output
+            );
+            println!(
+                "      {:<24} {:<13} {:>16}",
+                "source",
+                "",
+                format!("{:#x}", journey.source)
+            );
+            for (stage, &(sent, received)) in journey.stages.iter().zip(&hops) {
+                println!("      {:>10} {}", down, hop(sent, received));
+                let verdict = if stage.reallocated() {
+                    format!("{} {:#x}  new buffer", arrow, stage.sent).bright_yellow()
+                } else {
+                    "kept the buffer".bright_green()
+                };
+                println!(
+                    "      {:<24} {:<13} {:>16} {}  {}",
+                    stage.stage,
+                    stage.thread,
+                    format!("{:#x}", stage.received),
+                    verdict,
+                    format!("cap {}", stage.capacity).dimmed()
+                );
+            }
+            if let Some(&(sent, received)) = hops.last() {
+                println!("      {:>10} {}", down, hop(sent, received));
+            }
+            println!(
+                "      {:<24} {:<13} {:>16}",
+                "sink",
+                "",
+                format!("{:#x}", journey.delivered)
+            );
+            println!(
+                "      {}",
+                format!(
+                    "{} of {} hops kept their buffer, {} stage(s) reallocated",
+                    hops.iter()
+                        .filter(|(sent, received)| sent == received)
+                        .count(),
+                    hops.len(),
+                    journey.reallocations()
+                )
+                .bold()
+            );
+        }
+
+        self.print_insight("A channel moves the handle, never the bytes.");
+        say!(
+            self,
+            "   Sending a String copies its pointer, length and capacity into"
+        );
+        say!(
+            self,
+            "   the channel; the receiving thread gets the very same buffer."
+        );
+        say!(
+            self,
+            "   Only a stage that builds a new String - or outgrows its"
+        );
+        say!(self, "   capacity - moves the bytes somewhere else.");
+    }
+
+    /// Renders the transformations report
+    pub fn transformations(&self, report: &TransformationsReport) {
+        self.print_section("6. STRING TRANSFORMATIONS WITH TIMING");
+
+        // Reverse
+        say!(self, "\n{} REVERSE", theme::icon("🔄").bright_cyan());
+        say!(self, "   Input:  '{}'", report.reverse_input);
+        say!(self, "   Output: '{}'", report.reverse.value);
+        self.timing(&report.reverse);
+
+        self.bytes(&report.reversed_bytes);
+
+        // Uppercase
+        let (input, result) = (&report.upper_input, &report.upper);
+        say!(
+            self,
+            "\n{} UPPERCASE (Unicode-aware)",
+            theme::icon("🔤").bright_cyan()
+        );
+        say!(self, "   Input:  '{}' ({} bytes)", input, input.len());
+        say!(
+            self,
+            "   Output: '{}' ({} bytes)",
+            result.value,
+            result.value.len()
+        );
+        self.timing(result);
+
+        if result.value.len() != input.len() {
+            say!(
+                self,
+                "   {} Case conversion changed byte length!",
+                theme::glyphs().warning.bright_yellow()
+            );
+            say!(
+                self,
+                "      'ß' (1 char, 2 bytes) {} 'SS' (2 chars, 2 bytes)",
+                theme::glyphs().arrow
+            );
+        }
+
+        // Repeat
+        say!(self, "\n{} REPEAT", theme::icon("🔁").bright_cyan());
+        say!(self, "   Pattern: '{}'", report.repeat_pattern);
+        say!(self, "   Count:   {}", report.repeat_count);
+        say!(self, "   Output:  '{}'", report.repeat.value);
+        say!(
+            self,
+            "   Capacity: {} bytes (pre-allocated, no reallocation)",
+            report.repeat.value.capacity()
+        );
+        self.timing(&report.repeat);
+
+        // Interleave
+        say!(self, "\n{} INTERLEAVE", theme::icon("🔀").bright_cyan());
+        say!(self, "   String 1: '{}'", report.interleave_inputs.0);
+        say!(self, "   String 2: '{}'", report.interleave_inputs.1);
+        say!(self, "   Output:   '{}'", report.interleave.value);
+        self.timing(&report.interleave);
+
+        // Benchmarks
+        say!(
+            self,
+            "\n{} BENCHMARKS (warmup + many samples)",
+            theme::icon("📈").bright_cyan()
+        );
+        say!(
+            self,
+            "   A single sample above is mostly timer noise. Statistically:"
+        );
+        for result in &report.benchmarks {
+            self.timing(result);
+        }
+
+        say!(
+            self,
+            "\n{} Total operations: {}",
+            theme::icon("📊").bright_yellow(),
+            report.operations_count.to_string().bright_green().bold()
+        );
+    }
+
+    /// Renders the Unicode report
+    pub fn unicode(&self, report: &UnicodeReport) {
+        self.print_section("7. UNICODE AND UTF-8 HANDLING");
+
+        for sample in &report.samples {
+            self.bytes(sample);
+        }
+
+        // Demonstrate the danger of byte indexing
+        let mixed = &report.mixed;
+        say!(
+            self,
+            "\n{} BYTE vs CHAR indexing:",
+            theme::glyphs().warning.bright_yellow().bold()
+        );
+        say!(self, "   String: '{}'", mixed);
+        say!(self, "   Length in bytes: {}", mixed.len());
+        say!(self, "   Length in chars: {}", mixed.chars().count());
+
+        say!(self, "\n   Character iteration:");
+        for (i, (ch, width)) in report.char_widths().into_iter().enumerate() {
+            say!(self, "      chars[{}] = '{}' ({} bytes)", i, ch, width);
+        }
+
+        // chars vs grapheme clusters
+        say!(
+            self,
+            "\n{} CHAR vs GRAPHEME reversal:",
+            theme::icon("🔄").bright_cyan()
+        );
+        say!(self, "   Input:              '{}'", report.cluster_input);
+        say!(
+            self,
+            "   By char (broken):   '{}'",
+            report.char_reverse.value
+        );
+        self.timing(&report.char_reverse);
+        say!(
+            self,
+            "   By grapheme:        '{}'",
+            report.grapheme_reverse.value
+        );
+        self.timing(&report.grapheme_reverse);
+
+        say!(
+            self,
+            "\n{} CHAR vs GRAPHEME interleave:",
+            theme::icon("🔀").bright_cyan()
+        );
+        say!(
+            self,
+            "   String 1:           '{}'",
+            report.interleave_inputs.0
+        );
+        say!(
+            self,
+            "   String 2:           '{}'",
+            report.interleave_inputs.1
+        );
+        say!(
+            self,
+            "   By char (broken):   '{}'",
+            report.char_interleave.value
+        );
+        self.timing(&report.char_interleave);
+        say!(
+            self,
+            "   By grapheme:        '{}'",
+            report.grapheme_interleave.value
+        );
+        self.timing(&report.grapheme_interleave);
+
+        self.print_insight("A char is a code point, not what a reader sees as a character.");
+        say!(
+            self,
+            "   Accents, flags and emoji families span several chars;"
+        );
+        say!(
+            self,
+            "   operate on grapheme clusters (UAX #29) to keep them intact."
+        );
+    }
+
+    /// Prints one line per normalization form for a pair of inputs
+    fn normalized_pairs(&self, pairs: &[NormalizedPair]) {
+        for pair in pairs {
+            let verdict = if pair.equal() {
+                "equal".bright_green().bold()
+            } else {
+                "different".bright_red().bold()
+            };
+            say!(
+                self,
+                "\n   {:<5} '{}' ({} bytes, quick check: {}) vs '{}' ({} bytes, quick check: {}) {} {}",
+                pair.form.name(),
+                pair.left.value,
+                pair.left.value.len(),
+                pair.quick_checks.0,
+                pair.right.value,
+                pair.right.value.len(),
+                pair.quick_checks.1,
+                theme::glyphs().arrow,
+                verdict
+            );
+            self.timing(&pair.left);
+            self.timing(&pair.right);
+        }
+    }
+
+    /// Renders the Unicode normalization report
+    pub fn normalization(&self, report: &NormalizationReport) {
+        self.print_section("8. UNICODE NORMALIZATION");
+
+        self.bytes(&report.composed);
+        self.bytes(&report.decomposed);
+
+        let verdict = if report.raw_equal {
+            "equal".bright_green().bold()
+        } else {
+            "NOT equal".bright_red().bold()
+        };
+        say!(
+            self,
+            "\n{} '{}' == '{}' {} {}",
+            theme::icon("⚖").bright_yellow().bold(),
+            report.inputs.0,
+            report.inputs.1,
+            theme::glyphs().arrow,
+            verdict
+        );
+
+        say!(
+            self,
+            "\n{} CANONICAL EQUIVALENCE:",
+            theme::icon("🔣").bright_cyan()
+        );
+        self.normalized_pairs(&report.canonical);
+
+        say!(
+            self,
+            "\n{} COMPATIBILITY EQUIVALENCE:",
+            theme::icon("🔣").bright_cyan()
+        );
+        say!(
+            self,
+            "   '{}' vs '{}'",
+            report.compatibility_inputs.0,
+            report.compatibility_inputs.1
+        );
+        self.normalized_pairs(&report.compatibility);
+
+        say!(
+            self,
+            "\n{} QUICK-CHECK FAST PATH (NFC):",
+            theme::icon("📈").bright_cyan()
+        );
+        let inputs = [&report.inputs.0, &report.inputs.1];
+        for (input, result) in inputs.into_iter().zip(&report.benchmarks) {
+            say!(self, "   Input: '{}' ({} bytes)", input, input.len());
+            self.timing(result);
+        }
+
+        self.print_insight("Equal-looking strings are not necessarily equal bytes.");
+        say!(
+            self,
+            "   Normalize user input (usually to NFC) before comparing,"
+        );
+        say!(
+            self,
+            "   hashing or storing it; NFKC also folds ligatures and the like."
+        );
+        say!(
+            self,
+            "   Already-normalized text passes the quick check without allocating."
+        );
+    }
+
+    /// Renders the case mapping report
+    pub fn case_mapping(&self, report: &CaseMappingReport) {
+        self.print_section("9. FULL UNICODE CASE MAPPING");
+
+        for sample in &report.samples {
+            let result = &sample.result;
+            say!(
+                self,
+                "\n{} {} ({}, {})",
+                theme::icon("🔤").bright_cyan(),
+                theme::arrows(sample.description),
+                sample.mapping,
+                sample.locale
+            );
+            say!(
+                self,
+                "   Input:  '{}' ({} bytes)",
+                sample.input,
+                sample.input.len()
+            );
+            say!(
+                self,
+                "   Output: '{}' ({} bytes, capacity {})",
+                result.value,
+                result.value.len(),
+                result.value.capacity()
+            );
+            self.timing(result);
+
+            if sample.length_delta() != 0 {
+                say!(
+                    self,
+                    "   {} Byte length changed by {:+}",
+                    theme::glyphs().warning.bright_yellow(),
+                    sample.length_delta()
+                );
+            }
+            if result.value.capacity() > sample.input.len() {
+                say!(
+                    self,
+                    "   {} Output outgrew the {} bytes reserved for it",
+                    theme::glyphs().warning.bright_yellow(),
+                    sample.input.len()
+                );
+            }
+        }
+
+        say!(
+            self,
+            "\n{} CASELESS COMPARISON:",
+            theme::icon("⚖").bright_cyan()
+        );
+        for comparison in &report.comparisons {
+            let verdict = |equal: bool| {
+                if equal {
+                    "equal".bright_green().bold()
+                } else {
+                    "different".bright_red().bold()
+                }
+            };
+            say!(
+                self,
+                "   '{}' vs '{}': to_lowercase {arrow} {}, case fold {arrow} {}",
+                comparison.left,
+                comparison.right,
+                verdict(comparison.lowercase_equal),
+                verdict(comparison.caseless_equal),
+                arrow = theme::glyphs().arrow
+            );
+        }
+
+        self.print_insight("Case is language- and context-dependent.");
+        say!(
+            self,
+            "   Compare with case folding (plus normalization), never with to_lowercase;"
+        );
+        say!(
+            self,
+            "   titlecase is its own mapping, and Turkish or Lithuanian text needs"
+        );
+        say!(
+            self,
+            "   its tailoring. Any of these can change the byte length."
+        );
+    }
+
+    /// Renders the capacity growth report
+    pub fn capacity_growth(&self, report: &GrowthReport) {
+        self.print_section("10. CAPACITY GROWTH CURVE");
+
+        let glyphs = theme::glyphs();
+
+        for curve in &report.curves {
+            if !self.is_text() {
+                self.emit("growth_curve", curve);
+                continue;
+            }
+
+            println!(
+                "\n{} push_str of {} byte(s) up to {} bytes: {} pushes, {} reallocations ({} moved the data)",
+                theme::icon("📈").bright_cyan(),
+                curve.chunk,
+                curve.target,
+                curve.pushes,
+                curve.events.len(),
+                curve.moves()
+            );
+            println!(
+                "   {:>6}  {:>7}  {:>17}  {:>10}  data",
+                "push", "len", "capacity", "std rule"
+            );
+            for event in &curve.events {
+                let rule = if event.matches_rule() {
+                    format!("{:>8} {}", event.expected_capacity, glyphs.check).bright_green()
+                } else {
+                    format!("{:>8} {}", event.expected_capacity, glyphs.cross).bright_red()
+                };
+                let data = match (event.old_capacity, event.moved) {
+                    (0, _) => "first allocation".normal(),
+                    (_, true) => "moved".bright_red(),
+                    (_, false) => "grew in place".bright_green(),
+                };
+                println!(
+                    "   {:>6}  {:>7}  {:>7} {} {:>7}  {}  {}",
+                    event.push,
+                    event.len,
+                    event.old_capacity,
+                    glyphs.arrow,
+                    event.new_capacity,
+                    rule,
+                    data
+                );
+            }
+            self.growth_plot(curve);
+        }
+
+        say!(
+            self,
+            "\n{} reserve / reserve_exact / shrink_to:",
+            theme::icon("🔧").bright_cyan()
+        );
+        say!(
+            self,
+            "   {:<22}  {:>5}  {:>17}  {:>10}  {}",
+            "operation",
+            "len",
+            "capacity",
+            "expected",
+            "data"
+        );
+        for step in &report.steps {
+            if !self.is_text() {
+                self.emit("capacity_step", step);
+                continue;
+            }
+
+            let expected = if step.matches_rule() {
+                format!("{:>8} {}", step.expected_capacity, glyphs.check).bright_green()
+            } else {
+                format!("{:>8} {}", step.expected_capacity, glyphs.cross).bright_red()
+            };
+            let data = match (step.old_capacity, step.new_capacity, step.moved) {
+                (old, new, _) if old == new => "unchanged".normal(),
+                (0, _, _) => "first allocation".normal(),
+                (_, _, true) => "moved".bright_red(),
+                (_, _, false) => "in place".bright_green(),
+            };
+            println!(
+                "   {:<22}  {:>5}  {:>7} {} {:>7}  {}  {}",
+                step.op.to_string(),
+                step.len,
+                step.old_capacity,
+                glyphs.arrow,
+                step.new_capacity,
+                expected,
+                data
+            );
+        }
+
+        self.print_insight(&format!(
+            "Capacity grows to max(2 {} capacity, required, 8).",
+            glyphs.times
+        ));
+        say!(
+            self,
+            "   Doubling keeps n pushes at O(n) total copying; a big push jumps"
+        );
+        say!(
+            self,
+            "   straight to what it needs. reserve follows the same rule,"
+        );
+        say!(
+            self,
+            "   reserve_exact asks for exactly len + n, and shrink_to never goes"
+        );
+        say!(
+            self,
+            "   below the length. Whether data moves is up to the allocator."
+        );
+    }
+
+    /// Plots length (solid) and spare capacity (shaded) against pushes
+    fn growth_plot(&self, curve: &GrowthCurve) {
+        const WIDTH: usize = 60;
+        const HEIGHT: usize = 10;
+
+        let samples = &curve.samples;
+        let max_capacity = samples
+            .iter()
+            .map(|&(_, capacity)| capacity)
+            .max()
+            .unwrap_or(0);
+        if samples.is_empty() || max_capacity == 0 {
+            return;
+        }
+
+        let glyphs = theme::glyphs();
+        let columns: Vec<(usize, usize)> = (0..WIDTH.min(samples.len()))
+            .map(|column| samples[(column + 1) * samples.len() / WIDTH.min(samples.len()) - 1])
+            .collect();
+
+        println!();
+        for row in 0..HEIGHT {
+            let threshold = (HEIGHT - row) * max_capacity / HEIGHT;
+            let label = match row {
+                0 => format!("{:>7}", max_capacity),
+                _ => " ".repeat(7),
+            };
+            let line: String = columns
+                .iter()
+                .map(|&(len, capacity)| {
+                    if len >= threshold {
+                        glyphs.full
+                    } else if capacity >= threshold {
+                        glyphs.shade
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            println!(
+                "   {} {}{}",
+                label,
+                glyphs.single.vertical,
+                line.bright_cyan()
+            );
+        }
+        println!(
+            "   {:>7} {}{}",
+            0,
+            glyphs.single.bottom_left,
+            glyphs.single.horizontal.repeat(columns.len())
+        );
+        println!(
+            "   {}  pushes {} ({} {} length, {} spare capacity)",
+            " ".repeat(7),
+            glyphs.arrow,
+            curve.pushes,
+            glyphs.full,
+            glyphs.shade
+        );
+    }
+
+    /// Renders the allocator size class report
+    pub fn size_classes(&self, report: &SizeClassReport) {
+        self.print_section("11. ALLOCATOR SIZE CLASSES");
+
+        if report.classes.is_empty() {
+            say!(
+                self,
+                "\n{} The allocator's block sizes are only available on Linux",
+                theme::glyphs().warning.bright_yellow()
+            );
+        } else {
+            say!(
+                self,
+                "\n{} Requested capacity {} allocator block:",
+                theme::icon("🧱").bright_cyan(),
+                theme::glyphs().arrow
+            );
+            say!(
+                self,
+                "   {:>17}  {:>10}  {:>13}",
+                "requested",
+                "block",
+                "max rounding"
+            );
+        }
+        for class in &report.classes {
+            if !self.is_text() {
+                self.emit("size_class", class);
+                continue;
+            }
+
+            let requested = if class.smallest == class.largest {
+                class.smallest.to_string()
+            } else {
+                format!(
+                    "{} {} {}",
+                    class.smallest,
+                    theme::glyphs().en_dash,
+                    class.largest
+                )
+            };
+            println!(
+                "   {:>17}  {:>10}  {:>13}",
+                requested,
+                class.block_size,
+                format!("+{}", class.max_rounding()).bright_yellow()
+            );
+        }
+
+        for layout in &report.layouts {
+            self.layout(layout);
+        }
+
+        self.print_insight("Capacity is what String asked for, not what it got.");
+        say!(
+            self,
+            "   The allocator rounds every request up to a size class, so a"
+        );
+        say!(
+            self,
+            "   buffer's block is often larger than its capacity. String cannot"
+        );
+        say!(
+            self,
+            "   use the difference: it only knows the capacity it requested."
+        );
+    }
+
+    /// Renders the layout of a piece of text in every string type
+    pub fn inspection(&self, report: &InspectionReport) {
+        self.print_section("INSPECT");
+
+        for layout in &report.layouts {
+            self.layout(layout);
+        }
+
+        self.bytes(&report.bytes);
+
+        for header in &report.headers {
+            self.raw_header(header);
+        }
+        self.heap_dump(&report.heap);
+    }
+
+    /// Renders a single transformation
+    pub fn transform(&self, report: &TransformReport) {
+        self.print_section(&format!("TRANSFORM: {}", report.result.operation));
+
+        self.bytes(&report.input);
+        self.bytes(&report.output);
+
+        say!(self);
+        self.timing(&report.result);
+        self.comparison(&report.layout);
+    }
+
+    /// Renders the outcome of a REPL statement
+    pub fn repl_step(&self, step: &Step) {
+        if !self.is_text() {
+            return self.emit("repl_step", step);
+        }
+
+        match &step.comparison {
+            Some(comparison) => comparison.print(),
+            None => {
+                println!("\n{}", step.layout);
+                if let Some(allocations) = &step.allocations {
+                    println!(
+                        "  {} allocator: {}",
+                        theme::icon("🧮"),
+                        allocations.to_string().bright_yellow()
+                    );
+                }
+            }
+        }
+    }
+
+    /// Renders a statement the REPL could not execute
+    pub fn repl_error(&self, error: &ReplError) {
+        if !self.is_text() {
+            #[derive(Serialize)]
+            struct Error {
+                message: String,
+            }
+            return self.emit(
+                "error",
+                &Error {
+                    message: error.to_string(),
+                },
+            );
+        }
+
+        println!("{} {}", "error:".bright_red().bold(), error);
+    }
+
+    /// Renders the statements of a REPL session so far
+    pub fn repl_history(&self, history: &[String]) {
+        if !self.is_text() {
+            #[derive(Serialize)]
+            struct History<'a> {
+                statements: &'a [String],
+            }
+            return self.emit(
+                "history",
+                &History {
+                    statements: history,
+                },
+            );
+        }
+
+        for (i, statement) in history.iter().enumerate() {
+            println!("  {:>3}  {}", i + 1, statement);
+        }
+    }
+
+    /// Renders every tracked value's life, scene by scene
+    ///
+    /// Each value gets a lane: ● created, ◆ cloned, ↓ moved, ✕ dropped, and
+    /// │ while it is alive.
+    pub fn timeline(&self, timeline: &Timeline) {
+        const LANE: usize = 4;
+
+        self.print_section("LIFETIME TIMELINE");
+
+        let glyphs = theme::glyphs();
+        let mut alive: Vec<u64> = Vec::new();
+        for scene in &timeline.scenes {
+            if !self.is_text() {
+                #[derive(Serialize)]
+                struct Event<'a> {
+                    scene: &'a str,
+                    #[serde(flatten)]
+                    event: &'a LifetimeEvent,
+                }
+                for event in &scene.events {
+                    self.emit(
+                        "lifetime_event",
+                        &Event {
+                            scene: &scene.name,
+                            event,
+                        },
+                    );
+                }
+                continue;
+            }
+
+            let mut lanes: Vec<u64> = Vec::new();
+            for event in &scene.events {
+                if !lanes.contains(&event.id) {
+                    lanes.push(event.id);
+                }
+            }
+
+            println!(
+                "\n{} {}",
+                theme::icon("🕰").bright_cyan(),
+                scene.name.bright_cyan().bold()
+            );
+            let header: String = lanes
+                .iter()
+                .map(|id| format!("{:<LANE$}", format!("#{}", id)))
+                .collect();
+            println!("   {}", header.bright_black());
+
+            for event in &scene.events {
+                if matches!(event.action, Action::Created | Action::Cloned { .. }) {
+                    alive.push(event.id);
+                }
+                let line: String = lanes
+                    .iter()
+                    .map(|&id| {
+                        let mark = if id != event.id {
+                            if alive.contains(&id) {
+                                glyphs.single.vertical
+                            } else {
+                                " "
+                            }
+                        } else {
+                            match event.action {
+                                Action::Created => glyphs.created,
+                                Action::Cloned { .. } => glyphs.cloned,
+                                Action::Moved { .. } => glyphs.down,
+                                Action::Dropped => glyphs.dropped,
+                            }
+                        };
+                        format!("{:<LANE$}", mark)
+                    })
+                    .collect();
+                let description = match event.action {
+                    Action::Dropped => event.to_string().bright_red(),
+                    Action::Moved { .. } => event.to_string().bright_yellow(),
+                    _ => event.to_string().bright_green(),
+                };
+                println!("   {} {}", line, description);
+                if event.action == Action::Dropped {
+                    alive.retain(|&id| id != event.id);
+                }
+            }
+        }
+
+        say!(self, "\n{} Lifetimes:", theme::icon("📜").bright_cyan());
+        for lifetime in timeline.lifetimes() {
+            if !self.is_text() {
+                self.emit("lifetime", &lifetime);
+                continue;
+            }
+
+            let origin = match lifetime.cloned_from {
+                Some(from) => format!(" (clone of #{})", from),
+                None => String::new(),
+            };
+            let span = match &lifetime.died {
+                Some(died) if *died == lifetime.born => format!("lived within {}", died),
+                Some(died) => format!("{} {} {}", lifetime.born, glyphs.arrow, died),
+                None => format!("{} {} still alive", lifetime.born, glyphs.arrow),
+            };
+            println!(
+                "   #{:<3} {:<28} {:<36} {} move(s)",
+                lifetime.id,
+                format!("{}{}", lifetime.name, origin),
+                span,
+                lifetime.moves
+            );
+        }
+
+        self.print_insight("A move copies the handle, never the buffer.");
+        say!(
+            self,
+            "   The buffer address survives every move and is freed exactly once,"
+        );
+        say!(
+            self,
+            "   when its last owner is dropped. A clone gets a buffer of its own."
+        );
+    }
+
+    /// Renders the spans of a session as an indented tree with call counts,
+    /// busy and idle time and the values recorded into their fields
+    pub fn span_tree(&self, nodes: &[SpanNode]) {
+        if nodes.is_empty() {
+            return;
+        }
+        if !self.is_text() {
+            for node in nodes {
+                self.emit("span", node);
+            }
+            return;
+        }
+
+        self.print_section("SPAN TREE");
+        println!(
+            "\n   {:<44} {:>6} {:>11} {:>11}  fields",
+            "span", "calls", "busy", "idle"
+        );
+        for node in nodes {
+            self.span_node(node, "", "");
+        }
+
+        self.print_insight("Busy is time inside a span, idle is time it waited.");
+        say!(
+            self,
+            "   An instrumented future is entered on every poll: its idle time"
+        );
+        say!(
+            self,
+            "   is spent awaiting I/O, timers or other tasks. Spans with the"
+        );
+        say!(
+            self,
+            "   same path are merged, so calls counts how often each ran."
+        );
+    }
+
+    /// Prints a span tree node and, below it, its children
+    fn span_node(&self, node: &SpanNode, branch: &str, indent: &str) {
+        let fields: Vec<String> = node
+            .fields
+            .iter()
+            .map(|field| format!("{}={}", field.name, field.values))
+            .collect();
+        let name = format!("{}{}", branch, node.name);
+        let line = format!(
+            "   {:<44} {:>6} {:>11} {:>11}  {}",
+            name,
+            node.calls,
+            bench::format_nanos(node.busy_nanos as f64).bright_yellow(),
+            bench::format_nanos(node.idle_nanos as f64).dimmed(),
+            fields.join(" ")
+        );
+        println!("{}", line.trim_end());
+
+        let single = theme::glyphs().single;
+        for (i, child) in node.children.iter().enumerate() {
+            let last = i + 1 == node.children.len();
+            let (corner, rail) = if last {
+                (single.bottom_left, " ")
+            } else {
+                (theme::glyphs().tee, single.vertical)
+            };
+            let branch = format!("{}{}{} ", indent, corner, single.horizontal);
+            let indent = format!("{}{}  ", indent, rail);
+            self.span_node(child, &branch, &indent);
+        }
+    }
+
+    /// Prints the closing summary of a laboratory session
+    pub fn takeaways(&self) {
+        if !self.is_text() {
+            return;
+        }
+
+        let sparkles = theme::icon("✨");
+        self.print_section(&format!(
+            "{} LABORATORY SESSION COMPLETE {}",
+            sparkles, sparkles
+        ));
+
+        let glyphs = theme::glyphs();
+        println!(
+            "\n{} Key Takeaways:",
+            theme::icon("📚").bright_green().bold()
+        );
+        println!("   1. String is heap-allocated, growable, and owned");
+        println!("   2. &str is a borrowed slice, can point to stack, heap, or static memory");
+        println!("   3. Moves are zero-cost, clones allocate and copy");
+        println!("   4. Capacity management affects performance (reallocation is O(n))");
+        println!("   5. Cow<str> delays allocation until mutation");
+        println!(
+            "   6. Rust is UTF-8 aware - chars {} bytes",
+            glyphs.not_equal
+        );
+        println!("   7. Async operations are lightweight and concurrent");
+        println!(
+            "   8. Normalize Unicode before comparing - equal-looking {} equal bytes",
+            glyphs.not_equal
+        );
+        println!("   9. Case mapping depends on context and language; fold to compare");
+        println!("  10. Capacity grows amortized; reserve_exact and shrink_to take control");
+        println!("  11. The allocator rounds capacity up to its size classes");
+        println!("  12. Rc<str>/Arc<str> clones share one allocation instead of copying");
+        println!("  13. Bound concurrency, time out and cancel tasks; join errors stay per task");
+        println!("  14. Sending a String to another thread moves the handle, not the buffer");
+
+        println!(
+            "\n{} Rust guarantees:",
+            theme::icon("🛡️").bright_blue().bold()
+        );
+        println!(
+            "   {} Memory safety without garbage collection",
+            glyphs.check
+        );
+        println!("   {} Thread safety enforced at compile time", glyphs.check);
+        println!("   {} Zero-cost abstractions", glyphs.check);
+        println!("   {} No null pointer exceptions", glyphs.check);
+        println!("   {} No data races", glyphs.check);
+    }
+}
@@ -0,0 +1,522 @@
+//! # Interactive String Experiments
+//!
+//! A [`Session`] holds named `String`s and executes a small subset of Rust
+//! on them, one statement at a time:
+//!
+//! ```text
+//! let s = String::with_capacity(4)
+//! s.push_str("Rust")
+//! s.push('!')           // exceeds the capacity: the buffer moves
+//! let t = s.clone()     // new allocation, bytes copied
+//! let u = t             // move: same buffer, new handle
+//! u.truncate(2)
+//! u.shrink_to_fit()
+//! drop(u)
+//! ```
+//!
+//! Every statement returns a [`Step`] with the layout of the string it
+//! touched, a before/after [`LayoutComparison`] and the allocator activity
+//! it caused. The session keeps the statements that succeeded as a history,
+//! which can be saved as a script and replayed later.
+//!
+//! Reading input and presenting steps is left to the caller.
+
+use crate::allocator::{track_if_installed, AllocStats};
+use crate::inspector::{Inspect, LayoutComparison, StringMemoryInfo};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Largest capacity a statement may ask for, so a typo cannot abort the
+/// process with an allocation failure
+pub const MAX_CAPACITY: usize = 1 << 30;
+
+/// Why a statement could not be executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplError {
+    /// The statement is not one the session understands
+    Syntax(String),
+    /// No string with this name exists
+    Unknown(String),
+    /// The string was moved into another binding or dropped
+    Moved(String),
+    /// The method exists on `String` but the session does not support it,
+    /// or it was given the wrong arguments
+    UnsupportedMethod(String),
+    /// An index that is past the end or inside a multi-byte character
+    NotCharBoundary { name: String, index: usize },
+    /// A capacity above [`MAX_CAPACITY`]
+    TooLarge(usize),
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::Syntax(message) => write!(f, "syntax error: {}", message),
+            ReplError::Unknown(name) => write!(f, "cannot find value `{}`", name),
+            ReplError::Moved(name) => write!(f, "use of moved value: `{}`", name),
+            ReplError::UnsupportedMethod(method) => {
+                write!(f, "unsupported method or arguments: `{}`", method)
+            }
+            ReplError::NotCharBoundary { name, index } => {
+                write!(
+                    f,
+                    "byte index {} is not a char boundary of `{}`",
+                    index, name
+                )
+            }
+            ReplError::TooLarge(capacity) => write!(
+                f,
+                "refusing to allocate {} bytes (limit {})",
+                capacity, MAX_CAPACITY
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplError {}
+
+/// The outcome of one statement
+#[derive(Debug, Clone, Serialize)]
+pub struct Step {
+    /// The statement as it was entered
+    pub statement: String,
+    /// Layout of the string the statement created or changed (for `drop`,
+    /// its last layout)
+    pub layout: StringMemoryInfo,
+    /// The string before against after, when there was a before
+    pub comparison: Option<LayoutComparison>,
+    /// Allocator activity of the string operation itself
+    pub allocations: Option<AllocStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Char(char),
+    Int(usize),
+    Punct(char),
+}
+
+/// How a `let` statement produces its string
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Init {
+    New,
+    WithCapacity(usize),
+    From(String),
+    Clone(String),
+    Move(String),
+}
+
+/// A mutating `String` method call
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Method {
+    PushStr(String),
+    Push(char),
+    Insert(usize, char),
+    InsertStr(usize, String),
+    Truncate(usize),
+    Pop,
+    Clear,
+    Reserve(usize),
+    ReserveExact(usize),
+    ShrinkToFit,
+    ShrinkTo(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Statement {
+    Let { name: String, init: Init },
+    Call { name: String, method: Method },
+    Drop(String),
+}
+
+fn syntax(message: impl Into<String>) -> ReplError {
+    ReplError::Syntax(message.into())
+}
+
+/// Reads the escape sequence after a backslash in a string or char literal
+fn escape(chars: &mut impl Iterator<Item = char>) -> Result<char, ReplError> {
+    match chars.next() {
+        Some('n') => Ok('\n'),
+        Some('t') => Ok('\t'),
+        Some('r') => Ok('\r'),
+        Some('0') => Ok('\0'),
+        Some(c @ ('\\' | '"' | '\'')) => Ok(c),
+        Some('u') => {
+            if chars.next() != Some('{') {
+                return Err(syntax("expected `{` after `\\u`"));
+            }
+            let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+            u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| syntax(format!("invalid unicode escape `\\u{{{}}}`", hex)))
+        }
+        Some(c) => Err(syntax(format!("unknown escape `\\{}`", c))),
+        None => Err(syntax("unterminated escape")),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ReplError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            // The rest of the line is a comment
+            '/' if chars.peek() == Some(&'/') => break,
+            '"' => {
+                let mut literal = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => literal.push(escape(&mut chars)?),
+                        Some(c) => literal.push(c),
+                        None => return Err(syntax("unterminated string literal")),
+                    }
+                }
+                tokens.push(Token::Str(literal));
+            }
+            '\'' => {
+                let c = match chars.next() {
+                    Some('\\') => escape(&mut chars)?,
+                    Some(c) => c,
+                    None => return Err(syntax("unterminated char literal")),
+                };
+                if chars.next() != Some('\'') {
+                    return Err(syntax("a char literal holds exactly one char"));
+                }
+                tokens.push(Token::Char(c));
+            }
+            '0'..='9' => {
+                let mut digits = String::from(c);
+                while let Some(&d) = chars.peek() {
+                    if !(d.is_ascii_digit() || d == '_') {
+                        break;
+                    }
+                    digits.push(d);
+                    chars.next();
+                }
+                let value = digits
+                    .replace('_', "")
+                    .parse()
+                    .map_err(|_| syntax(format!("integer `{}` is too large", digits)))?;
+                tokens.push(Token::Int(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some(&d) = chars.peek() {
+                    if !(d.is_alphanumeric() || d == '_') {
+                        break;
+                    }
+                    ident.push(d);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            '=' | '.' | '(' | ')' | ',' | ';' | ':' => tokens.push(Token::Punct(c)),
+            c => return Err(syntax(format!("unexpected character `{}`", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Splits call arguments on commas; every argument must be one literal
+fn arguments(tokens: &[Token]) -> Result<Vec<Token>, ReplError> {
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+    tokens
+        .split(|t| *t == Token::Punct(','))
+        .map(|arg| match arg {
+            [literal @ (Token::Str(_) | Token::Char(_) | Token::Int(_))] => Ok(literal.clone()),
+            _ => Err(syntax("arguments must be string, char or integer literals")),
+        })
+        .collect()
+}
+
+fn parse_init(tokens: &[Token]) -> Result<Init, ReplError> {
+    use Token::*;
+
+    match tokens {
+        [Ident(ty), Punct(':'), Punct(':'), Ident(ctor), Punct('('), args @ .., Punct(')')]
+            if ty == "String" =>
+        {
+            match (ctor.as_str(), arguments(args)?.as_slice()) {
+                ("new", []) => Ok(Init::New),
+                ("with_capacity", [Int(capacity)]) => Ok(Init::WithCapacity(*capacity)),
+                ("from", [Str(s)]) => Ok(Init::From(s.clone())),
+                _ => Err(ReplError::UnsupportedMethod(format!("String::{}", ctor))),
+            }
+        }
+        [Str(s), Punct('.'), Ident(method), Punct('('), Punct(')')]
+            if matches!(method.as_str(), "to_string" | "to_owned" | "into") =>
+        {
+            Ok(Init::From(s.clone()))
+        }
+        [Ident(source), Punct('.'), Ident(method), Punct('('), Punct(')')] if method == "clone" => {
+            Ok(Init::Clone(source.clone()))
+        }
+        [Ident(source)] => Ok(Init::Move(source.clone())),
+        _ => Err(syntax(
+            "expected `String::new()`, `String::with_capacity(n)`, `String::from(\"..\")`, \
+             `\"..\".to_string()`, `name.clone()` or `name`",
+        )),
+    }
+}
+
+fn parse_method(method: &str, args: &[Token]) -> Result<Method, ReplError> {
+    use Token::*;
+
+    Ok(match (method, arguments(args)?.as_slice()) {
+        ("push_str", [Str(s)]) => Method::PushStr(s.clone()),
+        ("push", [Char(c)]) => Method::Push(*c),
+        ("insert", [Int(i), Char(c)]) => Method::Insert(*i, *c),
+        ("insert_str", [Int(i), Str(s)]) => Method::InsertStr(*i, s.clone()),
+        ("truncate", [Int(n)]) => Method::Truncate(*n),
+        ("pop", []) => Method::Pop,
+        ("clear", []) => Method::Clear,
+        ("reserve", [Int(n)]) => Method::Reserve(*n),
+        ("reserve_exact", [Int(n)]) => Method::ReserveExact(*n),
+        ("shrink_to_fit", []) => Method::ShrinkToFit,
+        ("shrink_to", [Int(n)]) => Method::ShrinkTo(*n),
+        _ => return Err(ReplError::UnsupportedMethod(method.to_string())),
+    })
+}
+
+fn parse(line: &str) -> Result<Statement, ReplError> {
+    use Token::*;
+
+    let mut tokens = tokenize(line)?;
+    if tokens.last() == Some(&Punct(';')) {
+        tokens.pop();
+    }
+
+    match tokens.as_slice() {
+        [Ident(keyword), rest @ ..] if keyword == "let" => {
+            let rest = match rest {
+                [Ident(m), rest @ ..] if m == "mut" => rest,
+                rest => rest,
+            };
+            match rest {
+                [Ident(name), Punct('='), init @ ..] => Ok(Statement::Let {
+                    name: name.clone(),
+                    init: parse_init(init)?,
+                }),
+                _ => Err(syntax("expected `let name = ...`")),
+            }
+        }
+        [Ident(function), Punct('('), Ident(name), Punct(')')] if function == "drop" => {
+            Ok(Statement::Drop(name.clone()))
+        }
+        [Ident(name), Punct('.'), Ident(method), Punct('('), args @ .., Punct(')')] => {
+            Ok(Statement::Call {
+                name: name.clone(),
+                method: parse_method(method, args)?,
+            })
+        }
+        [] => Err(syntax("empty statement")),
+        _ => Err(syntax("expected `let`, a method call or `drop(name)`")),
+    }
+}
+
+/// What a statement produced: the layout after, the comparison with the
+/// layout before and the allocations
+type Outcome = (
+    StringMemoryInfo,
+    Option<LayoutComparison>,
+    Option<AllocStats>,
+);
+
+fn check_capacity(capacity: usize) -> Result<(), ReplError> {
+    if capacity > MAX_CAPACITY {
+        return Err(ReplError::TooLarge(capacity));
+    }
+    Ok(())
+}
+
+/// Named strings and the history of the statements applied to them
+#[derive(Debug, Default)]
+pub struct Session {
+    strings: BTreeMap<String, String>,
+    /// Names whose value was moved out or dropped
+    moved: BTreeSet<String>,
+    history: Vec<String>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The statements that succeeded so far, in order
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Layouts of every live string, by name
+    pub fn layouts(&self) -> Vec<StringMemoryInfo> {
+        self.strings
+            .iter()
+            .map(|(name, s)| s.inspect(name))
+            .collect()
+    }
+
+    /// Layout of the string called `name`
+    pub fn layout(&self, name: &str) -> Result<StringMemoryInfo, ReplError> {
+        Ok(self.get(name)?.inspect(name))
+    }
+
+    fn get(&self, name: &str) -> Result<&String, ReplError> {
+        self.strings.get(name).ok_or_else(|| self.missing(name))
+    }
+
+    fn missing(&self, name: &str) -> ReplError {
+        if self.moved.contains(name) {
+            ReplError::Moved(name.to_string())
+        } else {
+            ReplError::Unknown(name.to_string())
+        }
+    }
+
+    /// Executes one statement; on success it is appended to the history
+    pub fn execute(&mut self, line: &str) -> Result<Step, ReplError> {
+        let statement = line.trim().trim_end_matches(';').trim_end().to_string();
+        let (layout, comparison, allocations) = match parse(&statement)? {
+            Statement::Let { name, init } => self.bind(&statement, name, init)?,
+            Statement::Call { name, method } => self.call(&statement, &name, method)?,
+            Statement::Drop(name) => {
+                let layout = self.get(&name)?.inspect(&format!("{} (dropped)", name));
+                let value = self.strings.remove(&name).expect("checked above");
+                let ((), allocations) = track_if_installed(|| drop(value));
+                self.moved.insert(name);
+                (layout, None, allocations)
+            }
+        };
+
+        self.history.push(statement.clone());
+        Ok(Step {
+            statement,
+            layout,
+            comparison,
+            allocations,
+        })
+    }
+
+    fn bind(&mut self, statement: &str, name: String, init: Init) -> Result<Outcome, ReplError> {
+        let (value, before, allocations) = match init {
+            Init::New => {
+                let (value, allocations) = track_if_installed(String::new);
+                (value, None, allocations)
+            }
+            Init::WithCapacity(capacity) => {
+                check_capacity(capacity)?;
+                let (value, allocations) = track_if_installed(|| String::with_capacity(capacity));
+                (value, None, allocations)
+            }
+            Init::From(literal) => {
+                let (value, allocations) = track_if_installed(|| String::from(literal.as_str()));
+                (value, None, allocations)
+            }
+            Init::Clone(source) => {
+                let original = self.get(&source)?;
+                let before = original.inspect(&source);
+                let (value, allocations) = track_if_installed(|| original.clone());
+                (value, Some(before), allocations)
+            }
+            Init::Move(source) => {
+                // Inspected in place, before the handle leaves its binding
+                let before = self.get(&source)?.inspect(&source);
+                let original = self.strings.remove(&source).expect("checked above");
+                let (value, allocations) = track_if_installed(|| original);
+                if source != name {
+                    self.moved.insert(source);
+                }
+                (value, Some(before), allocations)
+            }
+        };
+
+        self.moved.remove(&name);
+        self.strings.insert(name.clone(), value);
+        let layout = self.strings[&name].inspect(&name);
+        let comparison = before.map(|before| {
+            LayoutComparison::between(&before, &layout, statement).with_allocations(allocations)
+        });
+        Ok((layout, comparison, allocations))
+    }
+
+    fn call(&mut self, statement: &str, name: &str, method: Method) -> Result<Outcome, ReplError> {
+        let missing = self.missing(name);
+        let s = self.strings.get_mut(name).ok_or(missing)?;
+        let boundary = |s: &String, index: usize| {
+            if s.is_char_boundary(index) {
+                Ok(())
+            } else {
+                Err(ReplError::NotCharBoundary {
+                    name: name.to_string(),
+                    index,
+                })
+            }
+        };
+
+        match &method {
+            Method::Insert(index, _) | Method::InsertStr(index, _) => boundary(s, *index)?,
+            Method::Truncate(len) if *len < s.len() => boundary(s, *len)?,
+            Method::Reserve(extra) | Method::ReserveExact(extra) => {
+                check_capacity(s.len().saturating_add(*extra))?
+            }
+            Method::PushStr(tail) => check_capacity(s.len() + tail.len())?,
+            _ => {}
+        }
+
+        // Borrowing `method` keeps its owned argument alive until after the
+        // tracked window, so freeing it is not counted against the call
+        let ((), comparison) = LayoutComparison::measure(s, name, statement, |s| match &method {
+            Method::PushStr(tail) => s.push_str(tail),
+            Method::Push(c) => s.push(*c),
+            Method::Insert(index, c) => s.insert(*index, *c),
+            Method::InsertStr(index, inserted) => s.insert_str(*index, inserted),
+            Method::Truncate(len) => s.truncate(*len),
+            Method::Pop => {
+                s.pop();
+            }
+            Method::Clear => s.clear(),
+            Method::Reserve(extra) => s.reserve(*extra),
+            Method::ReserveExact(extra) => s.reserve_exact(*extra),
+            Method::ShrinkToFit => s.shrink_to_fit(),
+            Method::ShrinkTo(capacity) => s.shrink_to(*capacity),
+        });
+
+        let (layout, allocations) = (comparison.after.clone(), comparison.allocations);
+        Ok((layout, Some(comparison), allocations))
+    }
+
+    /// Writes the history as a script that [`replay`](Session::replay) can
+    /// run again
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut script = String::from("// lab repl session\n");
+        for statement in &self.history {
+            script.push_str(statement);
+            script.push('\n');
+        }
+        fs::write(path, script)
+    }
+
+    /// Executes every statement of a saved script, skipping blank lines and
+    /// `//` comments
+    pub fn replay(&mut self, script: &str) -> Vec<Result<Step, ReplError>> {
+        script
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("//"))
+            .map(|line| self.execute(line))
+            .collect()
+    }
+}
@@ -0,0 +1,323 @@
+//! # Span Tree
+//!
+//! A `tracing` [`Layer`] that collects the span hierarchy in memory instead
+//! of logging every enter and exit. Spans are merged by their path from the
+//! root - every `async_process_string` under `async_operations` adds to the
+//! same node - and each node keeps:
+//!
+//! - how many spans closed there;
+//! - **busy** time, while a span was entered, and **idle** time, between
+//!   its creation and close while it was not. An instrumented future is
+//!   entered on every poll, so idle is the time it spent waiting;
+//! - the values recorded into the spans' fields, such as `input_len`.
+//!
+//! ```ignore
+//! let layer = SpanTreeLayer::new();
+//! let tree = layer.tree();
+//! tracing_subscriber::registry().with(layer).init();
+//! // ... run instrumented code ...
+//! for node in tree.snapshot() {
+//!     println!("{} × {}", node.name, node.calls);
+//! }
+//! ```
+
+use crate::theme;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Distinct text values shown per field before only counting the rest
+const SHOWN_TEXT_VALUES: usize = 3;
+
+/// Values recorded into one field over every span of a node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldValues {
+    /// Integers, summarized by their range
+    Numbers { min: i128, max: i128 },
+    /// Anything else, as distinct values
+    Text { values: BTreeSet<String> },
+}
+
+impl FieldValues {
+    fn new(value: Value) -> Self {
+        match value {
+            Value::Number(n) => FieldValues::Numbers { min: n, max: n },
+            Value::Text(text) => FieldValues::Text {
+                values: BTreeSet::from([text]),
+            },
+        }
+    }
+
+    fn add(&mut self, value: Value) {
+        match (self, value) {
+            (FieldValues::Numbers { min, max }, Value::Number(n)) => {
+                *min = (*min).min(n);
+                *max = (*max).max(n);
+            }
+            (FieldValues::Text { values }, Value::Text(text)) => {
+                values.insert(text);
+            }
+            (this, value) => {
+                // The field changed kind: keep it as text
+                let mut text = FieldValues::Text {
+                    values: BTreeSet::from([this.to_string()]),
+                };
+                text.add(Value::Text(match value {
+                    Value::Number(n) => n.to_string(),
+                    Value::Text(text) => text,
+                }));
+                *this = text;
+            }
+        }
+    }
+}
+
+impl fmt::Display for FieldValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValues::Numbers { min, max } if min == max => write!(f, "{}", min),
+            FieldValues::Numbers { min, max } => write!(f, "{}..{}", min, max),
+            FieldValues::Text { values } => {
+                let shown: Vec<&str> = values
+                    .iter()
+                    .take(SHOWN_TEXT_VALUES)
+                    .map(String::as_str)
+                    .collect();
+                write!(f, "{}", shown.join("|"))?;
+                if values.len() > SHOWN_TEXT_VALUES {
+                    write!(f, "|{} ({} values)", theme::glyphs().ellipsis, values.len())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A field and the values recorded into it
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldSummary {
+    pub name: &'static str,
+    pub values: FieldValues,
+}
+
+/// Spans merged by their path from the root
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanNode {
+    pub name: &'static str,
+    /// Spans that closed at this node
+    pub calls: u64,
+    pub busy_nanos: u128,
+    pub idle_nanos: u128,
+    pub fields: Vec<FieldSummary>,
+    /// Child nodes, in the order they first closed
+    pub children: Vec<SpanNode>,
+}
+
+impl SpanNode {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            calls: 0,
+            busy_nanos: 0,
+            idle_nanos: 0,
+            fields: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a closed span's timings and fields to the node at `path` below
+    fn insert(&mut self, path: &[&'static str], timings: Timings) {
+        let Some((&name, rest)) = path.split_first() else {
+            self.calls += 1;
+            self.busy_nanos += timings.busy_nanos;
+            self.idle_nanos += timings.idle_nanos;
+            for (name, value) in timings.fields {
+                match self.fields.iter_mut().find(|f| f.name == name) {
+                    Some(field) => field.values.add(value),
+                    None => self.fields.push(FieldSummary {
+                        name,
+                        values: FieldValues::new(value),
+                    }),
+                }
+            }
+            return;
+        };
+
+        let child = match self.children.iter().position(|c| c.name == name) {
+            Some(index) => &mut self.children[index],
+            None => {
+                self.children.push(SpanNode::new(name));
+                self.children.last_mut().expect("a child was just pushed")
+            }
+        };
+        child.insert(rest, timings);
+    }
+
+    /// Busy plus idle time: how long the spans were open
+    pub fn total_nanos(&self) -> u128 {
+        self.busy_nanos + self.idle_nanos
+    }
+}
+
+/// Handle to the tree a [`SpanTreeLayer`] builds
+#[derive(Debug, Clone)]
+pub struct SpanTree {
+    root: Arc<Mutex<SpanNode>>,
+}
+
+impl SpanTree {
+    /// The top-level nodes collected so far, with their subtrees
+    ///
+    /// Spans appear once they close, so open ones are not included yet.
+    pub fn snapshot(&self) -> Vec<SpanNode> {
+        let root = self.root.lock().unwrap_or_else(|e| e.into_inner());
+        root.children.clone()
+    }
+}
+
+/// A value recorded into a span field
+#[derive(Debug)]
+enum Value {
+    Number(i128),
+    Text(String),
+}
+
+/// Collects a span's field values
+struct FieldVisitor<'a>(&'a mut Vec<(&'static str, Value)>);
+
+impl FieldVisitor<'_> {
+    fn record(&mut self, field: &Field, value: Value) {
+        match self.0.iter_mut().find(|(name, _)| *name == field.name()) {
+            Some((_, old)) => *old = value,
+            None => self.0.push((field.name(), value)),
+        }
+    }
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, Value::Number(value.into()));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, Value::Number(value.into()));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, Value::Text(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, Value::Text(format!("{:?}", value)));
+    }
+}
+
+/// Timings and fields of an open span, kept in its extensions
+struct Timings {
+    busy_nanos: u128,
+    idle_nanos: u128,
+    /// Start of the current busy or idle stretch
+    last: Instant,
+    /// Enters without a matching exit yet
+    entered: usize,
+    fields: Vec<(&'static str, Value)>,
+}
+
+/// A layer collecting spans into a [`SpanTree`]
+#[derive(Debug)]
+pub struct SpanTreeLayer {
+    tree: Arc<Mutex<SpanNode>>,
+}
+
+impl SpanTreeLayer {
+    pub fn new() -> Self {
+        Self {
+            tree: Arc::new(Mutex::new(SpanNode::new("root"))),
+        }
+    }
+
+    /// A handle to read the tree while the layer keeps building it
+    pub fn tree(&self) -> SpanTree {
+        SpanTree {
+            root: Arc::clone(&self.tree),
+        }
+    }
+}
+
+impl Default for SpanTreeLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for SpanTreeLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        span.extensions_mut().insert(Timings {
+            busy_nanos: 0,
+            idle_nanos: 0,
+            last: Instant::now(),
+            entered: 0,
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            values.record(&mut FieldVisitor(&mut timings.fields));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            if timings.entered == 0 {
+                let now = Instant::now();
+                timings.idle_nanos += (now - timings.last).as_nanos();
+                timings.last = now;
+            }
+            timings.entered += 1;
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            timings.entered = timings.entered.saturating_sub(1);
+            if timings.entered == 0 {
+                let now = Instant::now();
+                timings.busy_nanos += (now - timings.last).as_nanos();
+                timings.last = now;
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(mut timings) = span.extensions_mut().remove::<Timings>() else {
+            return;
+        };
+        timings.idle_nanos += timings.last.elapsed().as_nanos();
+
+        let path: Vec<&'static str> = span.scope().from_root().map(|s| s.name()).collect();
+        let mut root = self.tree.lock().unwrap_or_else(|e| e.into_inner());
+        root.insert(&path, timings);
+    }
+}
@@ -0,0 +1,318 @@
+//! # Rendering Themes
+//!
+//! How text output looks, independent of what it says. A [`Theme`] combines
+//! two switches:
+//!
+//! - **color** - ANSI colors and styles, or plain text;
+//! - **charset** - emoji, arrows and box-drawing characters, or their
+//!   plain-ASCII stand-ins for terminals and log collectors that garble
+//!   anything else.
+//!
+//! Both default to `auto`, resolved from the terminal stdout writes to:
+//! colors need a terminal whose `TERM` is not `dumb` and no `NO_COLOR`
+//! (see <https://no-color.org>); Unicode needs a terminal whose `TERM` is
+//! not `dumb`. Output piped to a file or a collector is therefore plain
+//! ASCII.
+//!
+//! The theme is process-wide, like the `colored` override it drives, so
+//! `Display` impls can follow it without a renderer at hand. Only the
+//! decoration changes: strings under inspection are printed as they are,
+//! and report data does not depend on the theme - its labels spell arrows
+//! `->`, which [`arrows`] draws with the theme's glyph when rendering.
+//!
+//! ```ignore
+//! Theme::detect(ColorChoice::Auto, Charset::Ascii).install();
+//! println!("{} {}", theme::icon("💡"), theme::glyphs().arrow); // "* ->"
+//! ```
+
+use std::borrow::Cow;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+
+/// Whether to color output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when writing to a capable terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Which characters decorate output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// Unicode when writing to a capable terminal
+    #[default]
+    Auto,
+    /// Emoji, arrows and box drawing
+    Unicode,
+    /// Plain ASCII only
+    Ascii,
+}
+
+/// What the auto choices are resolved from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Terminal {
+    /// Whether output goes to a terminal
+    pub is_terminal: bool,
+    /// Whether `NO_COLOR` is set to a non-empty value
+    pub no_color: bool,
+    /// The `TERM` variable
+    pub term: Option<String>,
+}
+
+impl Terminal {
+    /// The terminal stdout writes to, as the environment describes it
+    pub fn stdout() -> Self {
+        Self::of(&io::stdout())
+    }
+
+    /// The terminal `stream` writes to, as the environment describes it
+    pub fn of(stream: &impl IsTerminal) -> Self {
+        Self {
+            is_terminal: stream.is_terminal(),
+            no_color: std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+            term: std::env::var("TERM").ok(),
+        }
+    }
+
+    /// A terminal that can show more than plain text
+    fn is_capable(&self) -> bool {
+        self.is_terminal && self.term.as_deref() != Some("dumb")
+    }
+
+    /// Whether to color output for this terminal
+    pub fn color(&self, choice: ColorChoice) -> bool {
+        match choice {
+            ColorChoice::Auto => self.is_capable() && !self.no_color,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// The six characters of a box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxChars {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+}
+
+/// The characters text output is decorated with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Section headers and the banner
+    pub double: BoxChars,
+    /// Memory layouts, trees and plots
+    pub single: BoxChars,
+    /// A tree branch with siblings below it
+    pub tee: &'static str,
+    /// Becomes, leads to
+    pub arrow: &'static str,
+    /// Flows down to
+    pub down: &'static str,
+    /// Points at an analysis line
+    pub pointer: &'static str,
+    pub check: &'static str,
+    pub cross: &'static str,
+    pub warning: &'static str,
+    /// A value created, cloned or dropped on a timeline lane
+    pub created: &'static str,
+    pub cloned: &'static str,
+    pub dropped: &'static str,
+    /// Plot cells: filled, and shaded
+    pub full: char,
+    pub shade: char,
+    /// Stands in for a control character in a hexdump
+    pub unprintable: char,
+    pub times: &'static str,
+    pub not_equal: &'static str,
+    pub en_dash: &'static str,
+    pub ellipsis: &'static str,
+    /// Separates the figures of a distribution
+    pub separator: &'static str,
+    /// The micro prefix and the standard deviation symbol
+    pub micro: &'static str,
+    pub sigma: &'static str,
+}
+
+impl Glyphs {
+    pub const UNICODE: Glyphs = Glyphs {
+        double: BoxChars {
+            top_left: "╔",
+            top_right: "╗",
+            bottom_left: "╚",
+            bottom_right: "╝",
+            horizontal: "═",
+            vertical: "║",
+        },
+        single: BoxChars {
+            top_left: "┌",
+            top_right: "┐",
+            bottom_left: "└",
+            bottom_right: "┘",
+            horizontal: "─",
+            vertical: "│",
+        },
+        tee: "├",
+        arrow: "→",
+        down: "↓",
+        pointer: "➜",
+        check: "✓",
+        cross: "✗",
+        warning: "⚠",
+        created: "●",
+        cloned: "◆",
+        dropped: "✕",
+        full: '█',
+        shade: '░',
+        unprintable: '·',
+        times: "×",
+        not_equal: "≠",
+        en_dash: "–",
+        ellipsis: "…",
+        separator: "·",
+        micro: "μ",
+        sigma: "σ",
+    };
+
+    pub const ASCII: Glyphs = Glyphs {
+        double: BoxChars {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            horizontal: "=",
+            vertical: "|",
+        },
+        single: BoxChars {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            horizontal: "-",
+            vertical: "|",
+        },
+        tee: "+",
+        arrow: "->",
+        down: "v",
+        pointer: "=>",
+        check: "+",
+        cross: "x",
+        warning: "!",
+        created: "o",
+        cloned: "+",
+        dropped: "x",
+        full: '#',
+        shade: '.',
+        unprintable: '.',
+        times: "x",
+        not_equal: "!=",
+        en_dash: "-",
+        ellipsis: "...",
+        separator: "|",
+        micro: "u",
+        sigma: "sd",
+    };
+}
+
+/// Stands in for every emoji icon in ASCII output
+const ASCII_ICON: &str = "*";
+
+/// How text output is decorated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub color: bool,
+    pub unicode: bool,
+}
+
+impl Theme {
+    /// Colors and Unicode: the look the lab was designed with
+    pub const FANCY: Theme = Theme {
+        color: true,
+        unicode: true,
+    };
+
+    /// No colors, ASCII only
+    pub const PLAIN: Theme = Theme {
+        color: false,
+        unicode: false,
+    };
+
+    /// Resolves the choices against the terminal stdout writes to
+    pub fn detect(color: ColorChoice, charset: Charset) -> Self {
+        Self::resolve(color, charset, &Terminal::stdout())
+    }
+
+    /// Resolves the choices against `terminal`
+    pub fn resolve(color: ColorChoice, charset: Charset, terminal: &Terminal) -> Self {
+        Self {
+            color: terminal.color(color),
+            unicode: match charset {
+                Charset::Auto => terminal.is_capable(),
+                Charset::Unicode => true,
+                Charset::Ascii => false,
+            },
+        }
+    }
+
+    pub fn glyphs(&self) -> &'static Glyphs {
+        if self.unicode {
+            &Glyphs::UNICODE
+        } else {
+            &Glyphs::ASCII
+        }
+    }
+
+    /// The emoji, or its ASCII stand-in
+    pub fn icon(&self, emoji: &'static str) -> &'static str {
+        if self.unicode {
+            emoji
+        } else {
+            ASCII_ICON
+        }
+    }
+
+    /// Makes this the process-wide theme, switching `colored` on or off
+    pub fn install(self) {
+        colored::control::set_override(self.color);
+        *CURRENT.lock().unwrap_or_else(|e| e.into_inner()) = self;
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::FANCY
+    }
+}
+
+/// The installed theme
+static CURRENT: Mutex<Theme> = Mutex::new(Theme::FANCY);
+
+/// The process-wide theme: [`Theme::FANCY`] until another is installed
+pub fn current() -> Theme {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The glyphs of the process-wide theme
+pub fn glyphs() -> &'static Glyphs {
+    current().glyphs()
+}
+
+/// The emoji, or its ASCII stand-in under the process-wide theme
+pub fn icon(emoji: &'static str) -> &'static str {
+    current().icon(emoji)
+}
+
+/// A report label with each `->` drawn as the process-wide theme's arrow
+pub fn arrows(label: &str) -> Cow<'_, str> {
+    match glyphs().arrow {
+        "->" => Cow::Borrowed(label),
+        arrow => Cow::Owned(label.replace("->", arrow)),
+    }
+}
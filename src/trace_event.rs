@@ -0,0 +1,313 @@
+//! # Trace Event Export
+//!
+//! A `tracing` [`Layer`] recording spans as Chrome trace events, the JSON
+//! format `chrome://tracing` and [Perfetto](https://ui.perfetto.dev) open.
+//! Every stretch a span is entered becomes a complete (`"ph": "X"`) event
+//! on the thread it ran on, so an instrumented future shows up once per
+//! poll. A span entered more than once - a future polled again after
+//! waiting - also gets an async begin/end pair (`"b"`/`"e"`) covering its
+//! whole life on a track of its own, so tasks in flight at the same time
+//! are visibly parallel even when one worker thread polled them all. The
+//! span's fields become the events' `args`.
+//!
+//! ```ignore
+//! let layer = TraceEventLayer::new();
+//! let trace = layer.trace();
+//! tracing_subscriber::registry().with(layer).init();
+//! // ... run instrumented code ...
+//! trace.write(Path::new("lab.trace.json"))?;
+//! ```
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// One Chrome trace event
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceEvent {
+    pub name: String,
+    /// Category, for filtering in the viewer
+    pub cat: &'static str,
+    /// Phase: `X` for a complete event, `b`/`e` for the begin and end of
+    /// an async event, `M` for metadata
+    pub ph: &'static str,
+    /// Start, in microseconds since the layer was created
+    pub ts: f64,
+    /// Duration in microseconds, for complete events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<f64>,
+    pub pid: u32,
+    pub tid: u64,
+    /// Pairs the begin and end of an async event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub args: Map<String, Value>,
+}
+
+/// Events recorded by a [`TraceEventLayer`]
+#[derive(Debug, Default)]
+struct Recording {
+    /// Metadata events naming the threads
+    threads: Vec<TraceEvent>,
+    spans: Vec<TraceEvent>,
+}
+
+/// Handle to the events a [`TraceEventLayer`] records
+#[derive(Debug, Clone)]
+pub struct Trace {
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl Trace {
+    /// The events recorded so far, thread names first
+    pub fn events(&self) -> Vec<TraceEvent> {
+        let recording = self.recording.lock().unwrap_or_else(|e| e.into_inner());
+        recording
+            .threads
+            .iter()
+            .chain(&recording.spans)
+            .cloned()
+            .collect()
+    }
+
+    /// The events as a trace file's JSON object
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "traceEvents": self.events(),
+            "displayTimeUnit": "ns",
+        })
+    }
+
+    /// Writes the events to a trace file
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string(&self.to_json()).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+/// Numbers threads in the order they first record an event: trace viewers
+/// want integer thread ids
+static NEXT_TID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// This thread's trace id, 0 until assigned
+    static TID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The current thread's trace id
+fn current_tid() -> u64 {
+    TID.with(|tid| {
+        if tid.get() == 0 {
+            tid.set(NEXT_TID.fetch_add(1, Ordering::Relaxed));
+        }
+        tid.get()
+    })
+}
+
+/// Collects a span's fields as event arguments
+struct ArgsVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for ArgsVisitor<'_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+/// Arguments and open enters of a span, kept in its extensions
+struct SpanTrace {
+    /// Pairs the span's async begin and end; tracing reuses a closed span's
+    /// `Id`, so that cannot tell spans apart
+    async_id: u64,
+    args: Map<String, Value>,
+    created: Instant,
+    /// Times the span was entered
+    enters: usize,
+    /// Thread and start of every enter without an exit yet
+    entered: Vec<(u64, Instant)>,
+}
+
+/// A layer recording spans as Chrome trace events
+#[derive(Debug)]
+pub struct TraceEventLayer {
+    start: Instant,
+    pid: u32,
+    next_async_id: AtomicU64,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl TraceEventLayer {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            pid: std::process::id(),
+            next_async_id: AtomicU64::new(1),
+            recording: Arc::new(Mutex::new(Recording::default())),
+        }
+    }
+
+    /// A handle to read or write the events the layer records
+    pub fn trace(&self) -> Trace {
+        Trace {
+            recording: Arc::clone(&self.recording),
+        }
+    }
+
+    /// Microseconds from the layer's creation to `instant`
+    fn micros(&self, instant: Instant) -> f64 {
+        instant.saturating_duration_since(self.start).as_nanos() as f64 / 1_000.0
+    }
+
+    /// Records a metadata event naming the current thread, once per layer
+    fn name_thread(&self, tid: u64, threads: &mut Vec<TraceEvent>) {
+        if threads.iter().any(|event| event.tid == tid) {
+            return;
+        }
+
+        let current = thread::current();
+        let name = format!("{} {:?}", current.name().unwrap_or("thread"), current.id());
+        let mut args = Map::new();
+        args.insert("name".to_string(), name.into());
+        threads.push(TraceEvent {
+            name: "thread_name".to_string(),
+            cat: "__metadata",
+            ph: "M",
+            ts: 0.0,
+            dur: None,
+            pid: self.pid,
+            tid,
+            id: None,
+            args,
+        });
+    }
+}
+
+impl Default for TraceEventLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for TraceEventLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut args = Map::new();
+        attrs.record(&mut ArgsVisitor(&mut args));
+        span.extensions_mut().insert(SpanTrace {
+            async_id: self.next_async_id.fetch_add(1, Ordering::Relaxed),
+            args,
+            created: Instant::now(),
+            enters: 0,
+            entered: Vec::new(),
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(trace) = extensions.get_mut::<SpanTrace>() {
+            values.record(&mut ArgsVisitor(&mut trace.args));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(trace) = extensions.get_mut::<SpanTrace>() {
+            trace.enters += 1;
+            trace.entered.push((current_tid(), Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let end = Instant::now();
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        let Some(trace) = extensions.get_mut::<SpanTrace>() else {
+            return;
+        };
+
+        let tid = current_tid();
+        let Some(position) = trace.entered.iter().rposition(|&(t, _)| t == tid) else {
+            return;
+        };
+        let (_, start) = trace.entered.remove(position);
+        let event = TraceEvent {
+            name: span.name().to_string(),
+            cat: "span",
+            ph: "X",
+            ts: self.micros(start),
+            dur: Some((end - start).as_nanos() as f64 / 1_000.0),
+            pid: self.pid,
+            tid,
+            id: None,
+            args: trace.args.clone(),
+        };
+
+        let mut recording = self.recording.lock().unwrap_or_else(|e| e.into_inner());
+        self.name_thread(tid, &mut recording.threads);
+        recording.spans.push(event);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let end = Instant::now();
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(trace) = span.extensions_mut().remove::<SpanTrace>() else {
+            return;
+        };
+        if trace.enters < 2 {
+            return;
+        }
+
+        let tid = current_tid();
+        let async_event = |ph, at| TraceEvent {
+            name: span.name().to_string(),
+            cat: "task",
+            ph,
+            ts: self.micros(at),
+            dur: None,
+            pid: self.pid,
+            tid,
+            id: Some(format!("{:#x}", trace.async_id)),
+            args: trace.args.clone(),
+        };
+        let (begin, end) = (async_event("b", trace.created), async_event("e", end));
+
+        let mut recording = self.recording.lock().unwrap_or_else(|e| e.into_inner());
+        self.name_thread(tid, &mut recording.threads);
+        recording.spans.extend([begin, end]);
+    }
+}
@@ -4,33 +4,64 @@
 //! Each transformation is instrumented with tracing and timing data,
 //! showing the cost and behavior of different string operations.
 
+use crate::allocator::{self, AllocStats};
+use crate::bench::{self, BenchConfig, Distribution};
+use crate::case::{self, CaseLocale, CaseMapping};
+use crate::grapheme;
+use crate::normalize::{self, NormalizationForm};
+use crate::theme;
+use crate::workload::{self, Workload, WorkloadReport};
 use colored::Colorize;
+use serde::Serialize;
 use std::borrow::Cow;
+use std::io;
 use std::time::Instant;
-use tracing::{debug, info, instrument, span, Level};
+use tracing::{debug, info, instrument};
 
 /// Represents the result of a timed string operation
+#[derive(Debug, Clone, Serialize)]
 pub struct TimedResult<T> {
     pub value: T,
     pub duration_nanos: u128,
     pub operation: String,
+    /// Allocator activity during the operation, if a counting allocator is
+    /// installed
+    pub allocations: Option<AllocStats>,
+    /// Full timing distribution when the operation was benchmarked;
+    /// `duration_nanos` is then the median
+    pub distribution: Option<Distribution>,
 }
 
 impl<T> TimedResult<T> {
     pub fn display_timing(&self) {
-        let duration_str = if self.duration_nanos < 1_000 {
-            format!("{} ns", self.duration_nanos)
-        } else if self.duration_nanos < 1_000_000 {
-            format!("{:.2} μs", self.duration_nanos as f64 / 1_000.0)
-        } else {
-            format!("{:.2} ms", self.duration_nanos as f64 / 1_000_000.0)
-        };
+        let duration_str = bench::format_nanos(self.duration_nanos as f64);
+        let stopwatch = theme::icon("⏱");
+
+        match &self.distribution {
+            Some(distribution) => {
+                println!(
+                    "  {}  {} median {}",
+                    stopwatch,
+                    self.operation.bright_cyan(),
+                    duration_str.bright_yellow().bold()
+                );
+                println!("     {}", distribution);
+            }
+            None => println!(
+                "  {}  {} took {}",
+                stopwatch,
+                self.operation.bright_cyan(),
+                duration_str.bright_yellow().bold()
+            ),
+        }
 
-        println!(
-            "  ⏱  {} took {}",
-            self.operation.bright_cyan(),
-            duration_str.bright_yellow().bold()
-        );
+        if let Some(allocations) = &self.allocations {
+            println!(
+                "  {} allocator: {}",
+                theme::icon("🧮"),
+                allocations.to_string().bright_yellow()
+            );
+        }
     }
 }
 
@@ -38,40 +69,184 @@ impl<T> TimedResult<T> {
 macro_rules! timed {
     ($op_name:expr, $block:expr) => {{
         let start = Instant::now();
-        let result = $block;
+        let (result, allocations) = allocator::track_if_installed(|| $block);
         let duration = start.elapsed().as_nanos();
         TimedResult {
             value: result,
             duration_nanos: duration,
             operation: $op_name.to_string(),
+            allocations,
+            distribution: None,
         }
     }};
 }
 
-/// Simulates an async string processing task
-///
-/// In real-world scenarios, this might be:
-/// - Fetching data from a database
-/// - Making an HTTP request
-/// - Reading from a file
-/// - Performing CPU-intensive transformation
-#[instrument(skip(input), fields(input_len = input.len()))]
-pub async fn async_process_string(input: String, task_name: &str) -> String {
-    let span = span!(Level::INFO, "async_task", task = task_name);
-    let _enter = span.enter();
+/// A string operation that [`StringManipulator`] can time or benchmark
+#[derive(Debug, Clone, Copy)]
+pub enum Operation<'a> {
+    /// Reverse by characters
+    Reverse(&'a str),
+    /// Unicode-aware uppercase
+    ToUpper(&'a str),
+    /// Repeat a pattern n times
+    Repeat(&'a str, usize),
+    /// Alternate the characters of two strings
+    Interleave(&'a str, &'a str),
+    /// Reverse by extended grapheme clusters
+    ReverseGraphemes(&'a str),
+    /// Alternate the grapheme clusters of two strings
+    InterleaveGraphemes(&'a str, &'a str),
+    /// Convert to a Unicode normalization form
+    Normalize(&'a str, NormalizationForm),
+    /// Full case mapping with a language tailoring
+    CaseMap(&'a str, CaseMapping, CaseLocale),
+}
 
-    info!("Starting async task: {}", task_name);
-    debug!("Input length: {} bytes", input.len());
+impl<'a> Operation<'a> {
+    /// Name used when reporting timings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::Reverse(_) => "reverse",
+            Operation::ToUpper(_) => "to_uppercase",
+            Operation::Repeat(..) => "repeat",
+            Operation::Interleave(..) => "interleave",
+            Operation::ReverseGraphemes(_) => "reverse_graphemes",
+            Operation::InterleaveGraphemes(..) => "interleave_graphemes",
+            Operation::Normalize(_, form) => match form {
+                NormalizationForm::Nfc => "normalize_nfc",
+                NormalizationForm::Nfd => "normalize_nfd",
+                NormalizationForm::Nfkc => "normalize_nfkc",
+                NormalizationForm::Nfkd => "normalize_nfkd",
+            },
+            Operation::CaseMap(_, mapping, _) => mapping.name(),
+        }
+    }
 
-    // Simulate async work (in real app, this would be actual I/O)
-    tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    /// The (first) string the operation works on
+    pub fn input(&self) -> &'a str {
+        match *self {
+            Operation::Reverse(s)
+            | Operation::ToUpper(s)
+            | Operation::Repeat(s, _)
+            | Operation::Interleave(s, _)
+            | Operation::ReverseGraphemes(s)
+            | Operation::InterleaveGraphemes(s, _)
+            | Operation::Normalize(s, _)
+            | Operation::CaseMap(s, ..) => s,
+        }
+    }
 
-    let result = format!("[{}]", input);
+    /// Performs the operation, borrowing the input when it comes out
+    /// unchanged
+    ///
+    /// Only normalization can leave its input as it is: text that passes
+    /// the quick check is returned without allocating.
+    pub fn output(&self) -> Cow<'a, str> {
+        match *self {
+            Operation::Normalize(s, form) => normalize::normalize(s, form),
+            _ => Cow::Owned(self.apply()),
+        }
+    }
 
-    info!("Completed async task: {}", task_name);
-    debug!("Output length: {} bytes", result.len());
+    /// Performs the operation, without any instrumentation
+    pub fn apply(&self) -> String {
+        match *self {
+            Operation::Reverse(s) => {
+                // Note: We reverse by characters, not bytes (Unicode-aware)
+                s.chars().rev().collect::<String>()
+            }
+            Operation::ToUpper(s) => {
+                // Unicode case mapping can change byte length!
+                // Example: "ß" (1 char, 2 bytes) -> "SS" (2 chars, 2 bytes)
+                s.to_uppercase()
+            }
+            Operation::Repeat(s, count) => {
+                // Pre-allocate exact capacity - avoids reallocations
+                let mut result = String::with_capacity(s.len() * count);
+                for _ in 0..count {
+                    result.push_str(s);
+                }
+                result
+            }
+            Operation::Interleave(s1, s2) => {
+                let mut result = String::new();
+                let mut chars1 = s1.chars();
+                let mut chars2 = s2.chars();
+
+                loop {
+                    match (chars1.next(), chars2.next()) {
+                        (Some(c1), Some(c2)) => {
+                            result.push(c1);
+                            result.push(c2);
+                        }
+                        (Some(c1), None) => result.push(c1),
+                        (None, Some(c2)) => result.push(c2),
+                        (None, None) => break,
+                    }
+                }
+
+                result
+            }
+            Operation::ReverseGraphemes(s) => {
+                // Whole clusters move, so accents stay on their letters and
+                // flags/ZWJ sequences stay intact
+                let clusters: Vec<&str> = grapheme::graphemes(s).collect();
+                let mut result = String::with_capacity(s.len());
+                for cluster in clusters.into_iter().rev() {
+                    result.push_str(cluster);
+                }
+                result
+            }
+            Operation::InterleaveGraphemes(s1, s2) => {
+                let mut result = String::with_capacity(s1.len() + s2.len());
+                let mut clusters1 = grapheme::graphemes(s1);
+                let mut clusters2 = grapheme::graphemes(s2);
+
+                loop {
+                    match (clusters1.next(), clusters2.next()) {
+                        (Some(g1), Some(g2)) => {
+                            result.push_str(g1);
+                            result.push_str(g2);
+                        }
+                        (Some(g1), None) => result.push_str(g1),
+                        (None, Some(g2)) => result.push_str(g2),
+                        (None, None) => break,
+                    }
+                }
+
+                result
+            }
+            Operation::Normalize(s, form) => normalize::normalize(s, form).into_owned(),
+            Operation::CaseMap(s, mapping, locale) => case::map(s, mapping, locale),
+        }
+    }
+}
 
-    result
+/// Runs a real local I/O or CPU workload on a string in an async task
+///
+/// The workloads - a file round trip through `tokio::fs`, a TCP echo round
+/// trip and a `spawn_blocking` transform - are described in
+/// [`workload`]. The report lists every step that copied the string's bytes
+/// or allocated a buffer for them.
+#[instrument(skip(input), fields(input_len = input.as_ref().len(), workload = %workload))]
+pub async fn async_process_string<S>(input: S, workload: Workload) -> io::Result<WorkloadReport>
+where
+    S: AsRef<str> + Send + 'static,
+{
+    info!("Starting async task: {}", workload);
+    debug!("Input length: {} bytes", input.as_ref().len());
+
+    let report = workload::run(input, workload).await?;
+
+    info!(
+        copied = report.copied(),
+        allocated = report.allocated(),
+        "Completed async task: {}",
+        workload
+    );
+    debug!("Output length: {} bytes", report.output.len());
+
+    Ok(report)
 }
 
 /// Demonstrates String vs &str ownership and borrowing
@@ -126,7 +301,7 @@ pub fn demonstrate_capacity() -> String {
     let ptr_after = s.as_ptr() as usize;
 
     if ptr_before != ptr_after {
-        info!("⚠ REALLOCATION occurred!");
+        info!("REALLOCATION occurred!");
         info!("Old pointer: {:#x}", ptr_before);
         info!("New pointer: {:#x}", ptr_after);
         info!("New capacity: {}", s.capacity());
@@ -158,6 +333,25 @@ pub fn demonstrate_cow<'a>(input: &'a str, should_modify: bool) -> Cow<'a, str>
     cow
 }
 
+/// Logs how a case conversion changed the byte length and whether the
+/// output outgrew the buffer reserved for it
+fn log_size_change(input: &str, output: &String) {
+    if output.len() != input.len() {
+        info!(
+            "Length changed during case conversion: {} -> {} bytes",
+            input.len(),
+            output.len()
+        );
+    }
+    if output.capacity() > input.len() {
+        info!(
+            "Output outgrew its buffer: {} bytes reserved, capacity now {} bytes",
+            input.len(),
+            output.capacity()
+        );
+    }
+}
+
 /// Performs various string manipulations with detailed tracking
 pub struct StringManipulator {
     pub operations_count: usize,
@@ -175,10 +369,7 @@ impl StringManipulator {
     pub fn reverse(&mut self, s: &str) -> TimedResult<String> {
         self.operations_count += 1;
 
-        let result = timed!("reverse", {
-            // Note: We reverse by characters, not bytes (Unicode-aware)
-            s.chars().rev().collect::<String>()
-        });
+        let result = timed!("reverse", Operation::Reverse(s).apply());
 
         info!(
             "Reversed '{}' -> '{}' in {} ns",
@@ -188,24 +379,59 @@ impl StringManipulator {
         result
     }
 
+    /// Reverses a string by grapheme clusters (what a reader sees as
+    /// characters), keeping combining marks, flags and emoji sequences intact
+    #[instrument(skip(self))]
+    pub fn reverse_graphemes(&mut self, s: &str) -> TimedResult<String> {
+        self.operations_count += 1;
+
+        let result = timed!("reverse_graphemes", Operation::ReverseGraphemes(s).apply());
+
+        info!(
+            "Reversed {} grapheme clusters of '{}' -> '{}' in {} ns",
+            grapheme::grapheme_count(s),
+            s,
+            result.value,
+            result.duration_nanos
+        );
+
+        result
+    }
+
     /// Converts to uppercase (demonstrates case mapping complexity)
+    #[allow(clippy::wrong_self_convention)]
     #[instrument(skip(self))]
     pub fn to_upper(&mut self, s: &str) -> TimedResult<String> {
         self.operations_count += 1;
 
-        let result = timed!("to_uppercase", {
-            // Unicode case mapping can change byte length!
-            // Example: "ß" (1 char, 2 bytes) -> "SS" (2 chars, 2 bytes)
-            s.to_uppercase()
-        });
+        let result = timed!("to_uppercase", Operation::ToUpper(s).apply());
+        log_size_change(s, &result.value);
 
-        if result.value.len() != s.len() {
-            info!(
-                "⚠ Length changed during case conversion: {} -> {} bytes",
-                s.len(),
-                result.value.len()
-            );
-        }
+        result
+    }
+
+    /// Applies a full case mapping (lowercase, uppercase, titlecase or case
+    /// folding) with a language tailoring
+    #[instrument(skip(self))]
+    pub fn case_map(
+        &mut self,
+        s: &str,
+        mapping: CaseMapping,
+        locale: CaseLocale,
+    ) -> TimedResult<String> {
+        self.operations_count += 1;
+
+        let name = match locale {
+            CaseLocale::Root => mapping.name().to_string(),
+            _ => format!("{}[{}]", mapping, locale),
+        };
+        let result = timed!(name, Operation::CaseMap(s, mapping, locale).apply());
+
+        info!(
+            "Case mapped '{}' -> '{}' ({})",
+            s, result.value, result.operation
+        );
+        log_size_change(s, &result.value);
 
         result
     }
@@ -215,14 +441,7 @@ impl StringManipulator {
     pub fn repeat(&mut self, s: &str, count: usize) -> TimedResult<String> {
         self.operations_count += 1;
 
-        let result = timed!("repeat", {
-            // Pre-allocate exact capacity - avoids reallocations
-            let mut result = String::with_capacity(s.len() * count);
-            for _ in 0..count {
-                result.push_str(s);
-            }
-            result
-        });
+        let result = timed!("repeat", Operation::Repeat(s, count).apply());
 
         info!(
             "Repeated '{}' {}x = {} bytes (capacity: {})",
@@ -240,33 +459,122 @@ impl StringManipulator {
     pub fn interleave(&mut self, s1: &str, s2: &str) -> TimedResult<String> {
         self.operations_count += 1;
 
-        let result = timed!("interleave", {
-            let mut result = String::new();
-            let mut chars1 = s1.chars();
-            let mut chars2 = s2.chars();
+        let result = timed!("interleave", Operation::Interleave(s1, s2).apply());
 
-            loop {
-                match (chars1.next(), chars2.next()) {
-                    (Some(c1), Some(c2)) => {
-                        result.push(c1);
-                        result.push(c2);
-                    }
-                    (Some(c1), None) => result.push(c1),
-                    (None, Some(c2)) => result.push(c2),
-                    (None, None) => break,
-                }
-            }
+        info!("Interleaved '{}' and '{}' -> '{}'", s1, s2, result.value);
 
-            result
-        });
+        result
+    }
+
+    /// Interleaves two strings by grapheme clusters
+    #[instrument(skip(self))]
+    pub fn interleave_graphemes(&mut self, s1: &str, s2: &str) -> TimedResult<String> {
+        self.operations_count += 1;
+
+        let result = timed!(
+            "interleave_graphemes",
+            Operation::InterleaveGraphemes(s1, s2).apply()
+        );
 
         info!(
-            "Interleaved '{}' and '{}' -> '{}'",
+            "Interleaved clusters of '{}' and '{}' -> '{}'",
             s1, s2, result.value
         );
 
         result
     }
+
+    /// Converts a string to a Unicode normalization form
+    ///
+    /// Already-normalized input passes the quick check and is returned
+    /// borrowed, without allocating.
+    #[instrument(skip(self))]
+    pub fn normalize<'a>(
+        &mut self,
+        s: &'a str,
+        form: NormalizationForm,
+    ) -> TimedResult<Cow<'a, str>> {
+        self.operations_count += 1;
+
+        let quick_check = normalize::quick_check(s, form);
+        let result = timed!(
+            Operation::Normalize(s, form).name(),
+            normalize::normalize(s, form)
+        );
+
+        info!(
+            "Normalized to {} (quick check: {}, {}) in {} ns",
+            form,
+            quick_check,
+            if matches!(result.value, Cow::Borrowed(_)) {
+                "borrowed"
+            } else {
+                "allocated"
+            },
+            result.duration_nanos
+        );
+        if result.value.len() != s.len() {
+            info!(
+                "Length changed during normalization: {} -> {} bytes",
+                s.len(),
+                result.value.len()
+            );
+        }
+
+        result
+    }
+
+    /// Times a single run of any operation
+    #[instrument(skip(self), fields(operation = operation.name()))]
+    pub fn run(&mut self, operation: Operation<'_>) -> TimedResult<String> {
+        self.operations_count += 1;
+
+        let result = timed!(operation.name(), operation.apply());
+
+        info!(
+            "Ran {} on {} bytes -> {} bytes in {} ns",
+            operation.name(),
+            operation.input().len(),
+            result.value.len(),
+            result.duration_nanos
+        );
+
+        result
+    }
+
+    /// Benchmarks an operation statistically instead of timing one run
+    ///
+    /// The returned [`TimedResult`] carries the full timing
+    /// [`Distribution`], with `duration_nanos` set to the median.
+    #[instrument(skip(self, config), fields(operation = operation.name()))]
+    pub fn benchmark(
+        &mut self,
+        operation: Operation<'_>,
+        config: &BenchConfig,
+    ) -> TimedResult<String> {
+        self.operations_count += 1;
+
+        // The output is measured as the operation produces it: converting a
+        // borrowed result to a `String` would charge every run an allocation
+        let (value, distribution) = bench::run(config, operation, Operation::output);
+        // Allocations are deterministic, so one extra tracked run suffices
+        let (_, allocations) = allocator::track_if_installed(|| operation.output());
+
+        info!(
+            "Benchmarked {} over {} samples: median {} ns",
+            operation.name(),
+            distribution.samples,
+            distribution.median_nanos
+        );
+
+        TimedResult {
+            value: value.into_owned(),
+            duration_nanos: distribution.median_nanos as u128,
+            operation: operation.name().to_string(),
+            allocations,
+            distribution: Some(distribution),
+        }
+    }
 }
 
 impl Default for StringManipulator {
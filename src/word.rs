@@ -0,0 +1,209 @@
+//! # Word Boundaries
+//!
+//! Titlecasing needs to know where words start, and splitting on spaces is
+//! not enough: "can't" is one word, "3.14" is one number, and "Hello," is a
+//! word followed by punctuation.
+//!
+//! This module implements the default word boundary rules of
+//! [UAX #29](https://www.unicode.org/reports/tr29/#Word_Boundaries)
+//! (Unicode 17.0.0). Like the grapheme iterator, it yields every segment
+//! between two boundaries, including runs of spaces and punctuation.
+
+mod tables;
+
+use crate::grapheme::{self, range_cmp, GraphemeCat};
+
+/// The `Word_Break` property of a code point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordCat {
+    CR,
+    LF,
+    Newline,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Format,
+    Katakana,
+    HebrewLetter,
+    ALetter,
+    SingleQuote,
+    DoubleQuote,
+    MidNumLet,
+    MidLetter,
+    MidNum,
+    Numeric,
+    ExtendNumLet,
+    WSegSpace,
+    Other,
+}
+
+use WordCat::*;
+
+impl WordCat {
+    /// `AHLetter` in the rules: any letter
+    fn is_letter(self) -> bool {
+        matches!(self, ALetter | HebrewLetter)
+    }
+
+    /// `(MidLetter | MidNumLetQ)` in the rules
+    fn is_mid_letter(self) -> bool {
+        matches!(self, MidLetter | MidNumLet | SingleQuote)
+    }
+
+    /// `(MidNum | MidNumLetQ)` in the rules
+    fn is_mid_num(self) -> bool {
+        matches!(self, MidNum | MidNumLet | SingleQuote)
+    }
+
+    /// Characters rule WB4 attaches to whatever precedes them
+    fn is_ignorable(self) -> bool {
+        matches!(self, Extend | Format | ZWJ)
+    }
+}
+
+/// Looks up the word break category of a code point
+pub fn category(c: char) -> WordCat {
+    tables::WORD_CAT
+        .binary_search_by(|&(lo, hi, _)| range_cmp(c, lo, hi))
+        .map_or(Other, |i| tables::WORD_CAT[i].2)
+}
+
+/// Category of the first character of `s` that WB4 does not ignore, for the
+/// rules that look one character ahead (WB6, WB7b, WB12)
+fn next_significant(s: &str) -> Option<WordCat> {
+    s.chars().map(category).find(|cat| !cat.is_ignorable())
+}
+
+/// What the rules need to remember about the segment so far
+#[derive(Debug, Clone, Copy)]
+struct BreakState {
+    /// Category of the previous code point
+    prev: WordCat,
+    /// Category of the previous code point not ignored by WB4
+    last: WordCat,
+    /// The one before `last`, for the rules that look one character back
+    /// (WB7, WB7c, WB11)
+    before_last: Option<WordCat>,
+    /// WB15/16: number of consecutive regional indicators
+    regional_indicators: usize,
+}
+
+impl BreakState {
+    fn new(first: WordCat) -> Self {
+        Self {
+            prev: first,
+            last: first,
+            before_last: None,
+            regional_indicators: usize::from(first == RegionalIndicator),
+        }
+    }
+
+    /// Records a code point of category `cat` as the latest of the segment
+    fn advance(&mut self, cat: WordCat) {
+        self.prev = cat;
+        if cat.is_ignorable() {
+            return;
+        }
+
+        self.before_last = Some(self.last);
+        self.last = cat;
+        self.regional_indicators = if cat == RegionalIndicator {
+            self.regional_indicators + 1
+        } else {
+            0
+        };
+    }
+
+    /// Whether there is a word boundary before `next`, which is followed by
+    /// `after`
+    fn is_boundary(&self, next: char, next_cat: WordCat, after: &str) -> bool {
+        match (self.prev, next_cat) {
+            // WB3
+            (CR, LF) => return false,
+            // WB3a, WB3b
+            (Newline | CR | LF, _) | (_, Newline | CR | LF) => return true,
+            // WB3c: emoji ZWJ sequences
+            (ZWJ, _) if grapheme::category(next) == GraphemeCat::ExtendedPictographic => {
+                return false
+            }
+            // WB3d: runs of horizontal whitespace
+            (WSegSpace, WSegSpace) => return false,
+            // WB4
+            (_, Extend | Format | ZWJ) => return false,
+            _ => {}
+        }
+
+        let before_last = self.before_last.unwrap_or(Other);
+        match (self.last, next_cat) {
+            // WB5
+            (a, b) if a.is_letter() && b.is_letter() => false,
+            // WB6, WB7: letters around an apostrophe or a period ("can't")
+            (a, mid)
+                if a.is_letter()
+                    && mid.is_mid_letter()
+                    && next_significant(after).is_some_and(WordCat::is_letter) =>
+            {
+                false
+            }
+            (mid, b) if mid.is_mid_letter() && b.is_letter() && before_last.is_letter() => false,
+            // WB7a, WB7b, WB7c
+            (HebrewLetter, SingleQuote) => false,
+            (HebrewLetter, DoubleQuote) if next_significant(after) == Some(HebrewLetter) => false,
+            (DoubleQuote, HebrewLetter) if before_last == HebrewLetter => false,
+            // WB8, WB9, WB10
+            (Numeric, Numeric) => false,
+            (a, Numeric) if a.is_letter() => false,
+            (Numeric, b) if b.is_letter() => false,
+            // WB11, WB12: digits around a separator ("3.14", "1,000")
+            (mid, Numeric) if mid.is_mid_num() && before_last == Numeric => false,
+            (Numeric, mid) if mid.is_mid_num() && next_significant(after) == Some(Numeric) => false,
+            // WB13
+            (Katakana, Katakana) => false,
+            // WB13a, WB13b
+            (ALetter | HebrewLetter | Numeric | Katakana | ExtendNumLet, ExtendNumLet) => false,
+            (ExtendNumLet, ALetter | HebrewLetter | Numeric | Katakana) => false,
+            // WB15, WB16: flags are pairs of regional indicators
+            (RegionalIndicator, RegionalIndicator) => self.regional_indicators.is_multiple_of(2),
+            // WB999
+            _ => true,
+        }
+    }
+}
+
+/// Iterator over the segments between word boundaries of a string
+#[derive(Debug, Clone)]
+pub struct WordBounds<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for WordBounds<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut state = BreakState::new(category(first));
+
+        let end = chars
+            .find(|&(i, c)| {
+                let cat = category(c);
+                let after = &self.rest[i + c.len_utf8()..];
+                let boundary = state.is_boundary(c, cat, after);
+                state.advance(cat);
+                boundary
+            })
+            .map_or(self.rest.len(), |(i, _)| i);
+
+        let (segment, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(segment)
+    }
+}
+
+/// Splits `s` at every word boundary
+///
+/// Words, runs of whitespace and punctuation all come out as separate
+/// segments; concatenating them gives back `s`.
+pub fn word_bounds(s: &str) -> WordBounds<'_> {
+    WordBounds { rest: s }
+}
@@ -0,0 +1,285 @@
+//! # Async I/O Workloads
+//!
+//! Real local workloads for the async demos, each reporting where the
+//! string's bytes were copied and which buffers were allocated on the way:
+//!
+//! - **File round trip** - `tokio::fs::write` then `tokio::fs::read_to_string`
+//!   on a temporary file. `tokio::fs` runs blocking calls on a thread pool,
+//!   so `write` first copies the bytes into a buffer that pool can own.
+//! - **TCP echo** - a round trip through an [`EchoServer`] the lab spawns on
+//!   the loopback interface. Reading into a `Vec<u8>` and converting it with
+//!   `String::from_utf8` keeps the same buffer: validation, not a copy.
+//! - **CPU transform** - uppercasing on `spawn_blocking`. The input is moved
+//!   into the closure, so a `String` or `Arc<str>` crosses threads without
+//!   copying its bytes.
+//!
+//! Copies into and out of the kernel count as copies: that is where I/O
+//! spends its memory bandwidth.
+
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{self, JoinHandle};
+use tracing::{debug, warn};
+
+/// Size of the buffer the echo server reads into, one per connection
+pub const ECHO_BUFFER: usize = 4096;
+
+/// A local workload for an async task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    /// Write the string to a temporary file and read it back
+    FileRoundTrip,
+    /// Send the string through the echo server at this address
+    TcpEcho(SocketAddr),
+    /// Uppercase the string on the blocking thread pool
+    CpuTransform,
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Workload::FileRoundTrip => write!(f, "File Round Trip (tokio::fs)"),
+            Workload::TcpEcho(addr) => write!(f, "TCP Echo ({})", addr),
+            Workload::CpuTransform => write!(f, "CPU Transform (spawn_blocking)"),
+        }
+    }
+}
+
+/// One step of a workload that moved the string's bytes
+#[derive(Debug, Clone, Serialize)]
+pub struct CopyStep {
+    /// What the step did, with arrows spelled `->`
+    pub step: String,
+    /// Bytes copied by this step
+    pub copied: usize,
+    /// Bytes of new heap buffers this step allocated
+    pub allocated: usize,
+    /// Address of the buffer holding the bytes afterwards, when the lab
+    /// can see it (the kernel's buffers it cannot)
+    pub buffer: Option<usize>,
+}
+
+impl CopyStep {
+    fn new(
+        step: impl Into<String>,
+        copied: usize,
+        allocated: usize,
+        buffer: Option<usize>,
+    ) -> Self {
+        let step = Self {
+            step: step.into(),
+            copied,
+            allocated,
+            buffer,
+        };
+        debug!(
+            copied,
+            allocated,
+            buffer = ?buffer.map(|b| format!("{:#x}", b)),
+            "{}",
+            step.step
+        );
+        step
+    }
+}
+
+/// What a workload produced and the copies it took
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadReport {
+    pub workload: String,
+    pub output: String,
+    pub steps: Vec<CopyStep>,
+}
+
+impl WorkloadReport {
+    /// Bytes copied over all steps
+    pub fn copied(&self) -> usize {
+        self.steps.iter().map(|s| s.copied).sum()
+    }
+
+    /// Bytes of heap buffers allocated over all steps
+    pub fn allocated(&self) -> usize {
+        self.steps.iter().map(|s| s.allocated).sum()
+    }
+}
+
+/// A TCP echo server on the loopback interface, stopped when dropped
+#[derive(Debug)]
+pub struct EchoServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl EchoServer {
+    /// Binds an ephemeral loopback port and starts serving
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let addr = listener.local_addr()?;
+        let task = task::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        task::spawn(echo(stream));
+                    }
+                    Err(error) => {
+                        warn!("Echo server stopped accepting: {}", error);
+                        break;
+                    }
+                }
+            }
+        });
+        debug!(%addr, "Echo server listening");
+        Ok(Self { addr, task })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for EchoServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Sends back everything it reads until the client stops writing
+async fn echo(mut stream: TcpStream) {
+    let mut buffer = vec![0u8; ECHO_BUFFER];
+    loop {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                if stream.write_all(&buffer[..n]).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Runs `workload` on `input`
+pub async fn run<S>(input: S, workload: Workload) -> io::Result<WorkloadReport>
+where
+    S: AsRef<str> + Send + 'static,
+{
+    let (output, steps) = match workload {
+        Workload::FileRoundTrip => file_round_trip(input.as_ref()).await?,
+        Workload::TcpEcho(addr) => tcp_echo(input.as_ref(), addr).await?,
+        Workload::CpuTransform => cpu_transform(input).await?,
+    };
+    Ok(WorkloadReport {
+        workload: workload.to_string(),
+        output,
+        steps,
+    })
+}
+
+/// Distinguishes the temporary files of concurrent round trips
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+async fn file_round_trip(input: &str) -> io::Result<(String, Vec<CopyStep>)> {
+    let n = input.len();
+    let path: PathBuf = std::env::temp_dir().join(format!(
+        "introspective-strings-{}-{}.txt",
+        std::process::id(),
+        NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    tokio::fs::write(&path, input).await?;
+    let mut steps = vec![
+        CopyStep::new(
+            "fs::write: copy into an owned buffer for the blocking pool",
+            n,
+            n,
+            None,
+        ),
+        CopyStep::new("write(2): owned buffer -> page cache", n, 0, None),
+    ];
+
+    let read = tokio::fs::read_to_string(&path).await;
+    if let Err(error) = tokio::fs::remove_file(&path).await {
+        warn!("Could not remove {}: {}", path.display(), error);
+    }
+    let output = read?;
+    steps.push(CopyStep::new(
+        "read(2): page cache -> a new String",
+        output.len(),
+        output.capacity(),
+        Some(output.as_ptr() as usize),
+    ));
+
+    Ok((output, steps))
+}
+
+async fn tcp_echo(input: &str, addr: SocketAddr) -> io::Result<(String, Vec<CopyStep>)> {
+    let n = input.len();
+    let mut stream = TcpStream::connect(addr).await?;
+
+    stream.write_all(input.as_bytes()).await?;
+    stream.shutdown().await?;
+    let mut steps = vec![
+        CopyStep::new("write_all: the borrowed bytes -> socket buffer", n, 0, None),
+        CopyStep::new(
+            "echo server: socket -> its buffer -> socket",
+            2 * n,
+            ECHO_BUFFER,
+            None,
+        ),
+    ];
+
+    let mut bytes = Vec::with_capacity(n);
+    stream.read_to_end(&mut bytes).await?;
+    steps.push(CopyStep::new(
+        "read_to_end: socket buffer -> a new Vec<u8>",
+        bytes.len(),
+        bytes.capacity(),
+        Some(bytes.as_ptr() as usize),
+    ));
+
+    let output =
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    steps.push(CopyStep::new(
+        "String::from_utf8: validated in place, same buffer",
+        0,
+        0,
+        Some(output.as_ptr() as usize),
+    ));
+
+    Ok((output, steps))
+}
+
+async fn cpu_transform<S>(input: S) -> io::Result<(String, Vec<CopyStep>)>
+where
+    S: AsRef<str> + Send + 'static,
+{
+    let buffer = input.as_ref().as_ptr() as usize;
+    let mut steps = vec![CopyStep::new(
+        "move into the spawn_blocking closure: the handle moves, the bytes stay",
+        0,
+        0,
+        Some(buffer),
+    )];
+
+    let output = task::spawn_blocking(move || input.as_ref().to_uppercase()).await?;
+    steps.push(CopyStep::new(
+        "to_uppercase: input -> a new String",
+        output.len(),
+        output.capacity(),
+        Some(output.as_ptr() as usize),
+    ));
+    steps.push(CopyStep::new(
+        "return through the JoinHandle: moved, not copied",
+        0,
+        0,
+        Some(output.as_ptr() as usize),
+    ));
+
+    Ok((output, steps))
+}
@@ -0,0 +1,102 @@
+//! Concurrent batches: every task reports its own outcome
+
+use introspective_strings::batch::{Batch, BatchConfig, JoinErrorKind, Outcome};
+use introspective_strings::demos::{self, DemoInputs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn outcomes_are_reported_per_task() {
+    let config = BatchConfig {
+        max_concurrent: 4,
+        timeout: Duration::from_millis(50),
+    };
+    let batch = Batch::spawn(
+        0..4,
+        &config,
+        &CancellationToken::new(),
+        |n: u32| async move {
+            match n {
+                1 => Err("odd input"),
+                2 => {
+                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    Ok(n)
+                }
+                3 => panic!("input {}", n),
+                _ => Ok(n * 10),
+            }
+        },
+    );
+    let results = batch.join().await;
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].outcome.value(), Some(&0));
+    assert!(matches!(&results[1].outcome, Outcome::Failed(e) if e == "odd input"));
+    assert!(matches!(results[2].outcome, Outcome::TimedOut));
+    assert!(matches!(&results[3].outcome, Outcome::Panicked(m) if m == "input 3"));
+    assert_eq!(results[3].join_error, Some(JoinErrorKind::Panic));
+    assert!(results[..3].iter().all(|r| r.join_error.is_none()));
+}
+
+#[tokio::test]
+async fn limit_and_cancellation_hold_back_queued_tasks() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let config = BatchConfig {
+        max_concurrent: 2,
+        timeout: Duration::from_secs(10),
+    };
+    let cancel = CancellationToken::new();
+    let batch = Batch::spawn(0..6, &config, &cancel, {
+        let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+        move |n: u32| {
+            let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                // Only the first two finish before the token fires
+                let pause = if n < 2 { 10 } else { 10_000 };
+                tokio::time::sleep(Duration::from_millis(pause)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(n)
+            }
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    cancel.cancel();
+    let results = batch.join().await;
+
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert!(results[..2].iter().all(|r| r.outcome.is_ok()));
+    assert!(results[2..]
+        .iter()
+        .all(|r| matches!(r.outcome, Outcome::Cancelled)));
+    // Two more started once permits freed up; the rest never ran
+    assert_eq!(results.iter().filter(|r| r.run_nanos.is_none()).count(), 2);
+}
+
+/// The demo's default batch ends in every outcome but a returned error
+#[tokio::test]
+async fn the_concurrency_demo_shows_timeouts_panics_and_cancellation() {
+    let report = demos::concurrency(&DemoInputs::default()).await;
+    let outcome = |word: &str| {
+        &report
+            .tasks
+            .iter()
+            .find(|task| task.input == word)
+            .unwrap()
+            .result
+    };
+
+    assert_eq!(outcome("crash💥").join_error, Some(JoinErrorKind::Panic));
+    assert!(matches!(outcome("slowpoke🐌").outcome, Outcome::TimedOut));
+    assert!(matches!(
+        outcome("sleepyhead🐌").outcome,
+        Outcome::Cancelled
+    ));
+    // Every other word finishes well within the timeout
+    assert_eq!(report.count(Outcome::is_ok), report.tasks.len() - 3);
+}
@@ -0,0 +1,214 @@
+//! Handle and pointee locations, and allocator block sizes, reported by
+//! `Inspect`
+
+use introspective_strings::allocator::{self, CountingAllocator};
+use introspective_strings::{raw, region};
+use introspective_strings::{Inspect, MemoryRegion, StringMemoryInfo};
+use std::alloc::System;
+use std::borrow::Cow;
+use std::mem::size_of;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// Block sizes are only asked for once the allocator is declared malloc's
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+fn assume_global() {
+    // SAFETY: GLOBAL is this test binary's #[global_allocator]
+    unsafe { GLOBAL.assume_global() }
+}
+
+fn address_of<T: ?Sized>(value: &T) -> usize {
+    value as *const T as *const () as usize
+}
+
+/// Boxed values keep their handle and their data in different places
+#[test]
+fn boxed_handles_differ_from_their_data() {
+    let layouts: Vec<StringMemoryInfo> = vec![
+        String::from("owned").inspect("String"),
+        Box::<str>::from("boxed").inspect("Box<str>"),
+        Rc::<str>::from("rc").inspect("Rc<str>"),
+        Arc::<str>::from("arc").inspect("Arc<str>"),
+        b"bytes".to_vec().inspect("Vec<u8>"),
+    ];
+
+    for layout in &layouts {
+        assert_ne!(layout.handle_ptr, layout.data_ptr, "{}", layout.description);
+        assert!(
+            !(layout.handle_ptr..layout.handle_ptr + layout.handle_size).contains(&layout.data_ptr),
+            "{}: data inside its own handle",
+            layout.description
+        );
+    }
+}
+
+/// A local's handle is the local itself, on this thread's stack
+#[test]
+fn local_handles_are_in_the_stack_frame() {
+    let owned = String::from("owned");
+    let text: &str = "borrowed";
+    let boxed: Box<str> = "boxed".into();
+    let cow: Cow<str> = Cow::Borrowed(text);
+
+    let cases = [
+        (
+            owned.inspect("String"),
+            address_of(&owned),
+            size_of::<String>(),
+        ),
+        (text.inspect("&str"), address_of(&text), size_of::<&str>()),
+        (
+            boxed.inspect("Box<str>"),
+            address_of(&boxed),
+            size_of::<Box<str>>(),
+        ),
+        (
+            cow.inspect("Cow<str>"),
+            address_of(&cow),
+            size_of::<Cow<str>>(),
+        ),
+    ];
+
+    for (layout, local, size) in &cases {
+        assert_eq!(layout.handle_ptr, *local, "{}", layout.description);
+        assert_eq!(layout.handle_size, *size, "{}", layout.description);
+        if cfg!(target_os = "linux") {
+            assert_eq!(
+                layout.handle_region,
+                MemoryRegion::Stack,
+                "{}",
+                layout.description
+            );
+        }
+    }
+}
+
+/// Boxed data lives on the heap while the local handle stays on the stack
+#[cfg(target_os = "linux")]
+#[test]
+fn boxed_data_is_on_the_heap() {
+    let boxed: Box<str> = "boxed".into();
+    let layout = boxed.inspect("Box<str>");
+
+    assert_eq!(layout.handle_region, MemoryRegion::Stack);
+    assert!(layout.data_region.is_heap(), "{:?}", layout.data_region);
+}
+
+/// A handle that is itself boxed lives on the heap, apart from its data
+#[cfg(target_os = "linux")]
+#[test]
+fn handles_can_live_on_the_heap() {
+    let handle: Box<String> = Box::new(String::from("nested"));
+    let layout = (*handle).inspect("String in a Box");
+
+    assert_eq!(layout.handle_ptr, address_of(&*handle));
+    assert!(layout.handle_region.is_heap(), "{:?}", layout.handle_region);
+    assert_ne!(layout.handle_ptr, layout.data_ptr);
+}
+
+/// A string literal's data is in the binary, its handle is not
+#[cfg(target_os = "linux")]
+#[test]
+fn literal_data_is_static() {
+    let literal: &'static str = "literal";
+    let layout = literal.inspect("&'static str");
+
+    assert_eq!(layout.handle_region, MemoryRegion::Stack);
+    assert!(layout.data_region.is_static(), "{:?}", layout.data_region);
+}
+
+/// A thread that has classified an address makes its stack known to the
+/// others
+#[cfg(target_os = "linux")]
+#[test]
+fn other_threads_stacks_are_recognised() {
+    use std::sync::mpsc;
+
+    let (address, receive_address) = mpsc::channel();
+    let (done, wait) = mpsc::channel::<()>();
+    let thread = std::thread::spawn(move || {
+        let local = String::from("local");
+        assert_eq!(local.inspect("local").handle_region, MemoryRegion::Stack);
+        address.send(address_of(&local)).unwrap();
+        wait.recv().unwrap();
+    });
+
+    let address = receive_address.recv().unwrap();
+    assert_eq!(region::classify(address), MemoryRegion::OtherThreadStack);
+    done.send(()).unwrap();
+    thread.join().unwrap();
+}
+
+/// The allocator's block holds at least the requested capacity
+#[cfg(target_os = "linux")]
+#[test]
+fn heap_buffers_report_their_block_size() {
+    assume_global();
+    let owned = String::with_capacity(5);
+    let layout = owned.inspect("String");
+
+    let block_size = layout.block_size.expect("a heap buffer has a block");
+    assert!(
+        block_size >= layout.capacity,
+        "{} < {}",
+        block_size,
+        layout.capacity
+    );
+    assert_eq!(layout.wasted_bytes(), block_size);
+}
+
+/// Without a heap buffer of its own there is no block to ask about
+#[test]
+fn unallocated_data_has_no_block_size() {
+    let layouts: Vec<StringMemoryInfo> = vec![
+        String::new().inspect("empty String"),
+        "literal".inspect("&str"),
+        Cow::Borrowed("borrowed").inspect("Cow<str>"),
+        Rc::<str>::from("rc").inspect("Rc<str>"),
+    ];
+
+    for layout in &layouts {
+        assert_eq!(layout.block_size, None, "{}", layout.description);
+    }
+}
+
+/// Size classes cover every request once, each in a block that fits it
+#[cfg(target_os = "linux")]
+#[test]
+fn size_classes_round_requests_up() {
+    assume_global();
+    let classes = allocator::size_classes(1..=128);
+
+    assert_eq!(classes.first().map(|class| class.smallest), Some(1));
+    assert_eq!(classes.last().map(|class| class.largest), Some(128));
+    for pair in classes.windows(2) {
+        assert_eq!(pair[0].largest + 1, pair[1].smallest);
+    }
+    for class in &classes {
+        assert!(class.block_size >= class.largest, "{:?}", class);
+    }
+}
+
+/// The counts header in front of shared bytes holds the API's counts
+#[test]
+fn shared_allocations_start_with_their_counts() {
+    let rc: Rc<str> = Rc::from("shared");
+    let _clone = Rc::clone(&rc);
+    let _weak = Rc::downgrade(&rc);
+    let allocation = raw::rc_str_allocation(&rc);
+
+    assert!(allocation.header.verified, "{:?}", allocation.header);
+    assert_eq!((allocation.strong, allocation.weak), (2, 1));
+    assert_eq!(
+        allocation.header.address + allocation.header.size,
+        rc.as_ptr() as usize
+    );
+    assert_eq!(allocation.size(), allocation.header.size + rc.len());
+
+    let arc: Arc<str> = Arc::from("shared");
+    let allocation = raw::arc_str_allocation(&arc);
+    assert!(allocation.header.verified, "{:?}", allocation.header);
+    assert_eq!((allocation.strong, allocation.weak), (1, 0));
+}
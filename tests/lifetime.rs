@@ -0,0 +1,58 @@
+//! Events recorded by `Tracked` values
+
+use introspective_strings::lifetime::{Action, Timeline, Tracked};
+
+/// The log is shared by the whole process, so everything is checked in one
+/// test rather than in tests racing to take each other's events
+#[test]
+fn tracked_values_record_their_lives() {
+    // Without a timeline nothing is kept
+    let unobserved = Tracked::new("unobserved", "gone").id();
+
+    let mut timeline = Timeline::new();
+    let original = Tracked::new("original", "tracked");
+    let buffer = original.as_ptr() as usize;
+    let cloned = original.clone();
+    let boxed = Box::new(original);
+    assert_eq!(boxed.len(), 7);
+    let (original_id, cloned_id) = (boxed.id(), cloned.id());
+    drop(cloned);
+    drop(boxed);
+
+    timeline.record("test");
+    assert!(timeline.scenes[0].events.iter().all(|e| e.id != unobserved));
+    let events: Vec<_> = timeline.scenes[0]
+        .events
+        .iter()
+        .filter(|e| e.id == original_id || e.id == cloned_id)
+        .collect();
+
+    let actions: Vec<(u64, Action)> = events.iter().map(|e| (e.id, e.action)).collect();
+    assert!(matches!(
+        actions.as_slice(),
+        [
+            (_, Action::Created),
+            (_, Action::Cloned { from }),
+            (_, Action::Moved { .. }),
+            (_, Action::Dropped),
+            (_, Action::Dropped),
+        ] if *from == original_id
+    ));
+    assert_eq!(events[3].id, cloned_id);
+
+    // Moving the handle into a box leaves the buffer where it was
+    assert!(events
+        .iter()
+        .filter(|e| e.id == original_id)
+        .all(|e| e.buffer == buffer));
+    assert_ne!(events[1].buffer, buffer);
+
+    let lifetimes = timeline.lifetimes();
+    let original = lifetimes.iter().find(|l| l.id == original_id).unwrap();
+    assert_eq!(
+        (original.moves, original.died.as_deref()),
+        (1, Some("test"))
+    );
+    let cloned = lifetimes.iter().find(|l| l.id == cloned_id).unwrap();
+    assert_eq!(cloned.cloned_from, Some(original_id));
+}
@@ -0,0 +1,63 @@
+//! Logging configuration: filters, destinations and errors
+
+use introspective_strings::logging::{LogConfig, LogDestination, LogError, LogFormat};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::Registry;
+
+#[test]
+fn directives_and_destinations_configure_the_layer() {
+    let config = LogConfig {
+        directives: Some("warn,introspective_strings::pipeline=debug".to_string()),
+        ..LogConfig::default()
+    };
+    let filter = config.filter().unwrap().to_string();
+    assert!(
+        filter.contains("introspective_strings::pipeline=debug"),
+        "{}",
+        filter
+    );
+
+    let bad = LogConfig {
+        directives: Some("info,[".to_string()),
+        ..LogConfig::default()
+    };
+    assert!(matches!(bad.layer::<Registry>(), Err(LogError::Filter(_))));
+
+    let off = LogConfig {
+        destination: LogDestination::Off,
+        ..LogConfig::default()
+    };
+    assert!(off.layer::<Registry>().unwrap().is_none());
+
+    let path = std::env::temp_dir().join(format!("lab-logging-{}.log", std::process::id()));
+    let file = LogConfig {
+        directives: Some("info".to_string()),
+        format: LogFormat::Json,
+        destination: LogDestination::File(path.clone()),
+        ..LogConfig::default()
+    };
+    // A bad directive is reported before the file is truncated
+    std::fs::write(&path, "earlier run\n").unwrap();
+    let bad_file = LogConfig {
+        directives: Some("info,[".to_string()),
+        ..file.clone()
+    };
+    assert!(matches!(
+        bad_file.layer::<Registry>(),
+        Err(LogError::Filter(_))
+    ));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "earlier run\n");
+
+    let layer = file
+        .layer::<Registry>()
+        .unwrap()
+        .expect("logging to a file");
+    tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+        tracing::info!(answer = 42, "logged");
+        tracing::debug!("filtered out");
+    });
+    let logged = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(logged.lines().count(), 1);
+    assert!(logged.contains(r#""answer":42"#), "{}", logged);
+}
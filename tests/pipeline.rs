@@ -0,0 +1,33 @@
+//! Channel pipelines: hops move the handle, transforms may replace the buffer
+
+use introspective_strings::case::{CaseLocale, CaseMapping};
+use introspective_strings::normalize::NormalizationForm;
+use introspective_strings::pipeline::{self, Stage};
+
+#[tokio::test]
+async fn hops_keep_the_buffer_and_transforms_replace_it() {
+    let mut text = String::with_capacity(32);
+    text.push_str("Grüße");
+    let source = text.as_ptr() as usize;
+
+    let stages = [
+        Stage::Forward,
+        Stage::Push("!".to_string()),
+        Stage::Normalize(NormalizationForm::Nfc),
+        Stage::CaseMap(CaseMapping::Upper, CaseLocale::Root),
+        Stage::Forward,
+    ];
+    let journeys = pipeline::run([text, String::from("second")], &stages, 1).await;
+
+    assert_eq!(journeys.len(), 2);
+    let journey = &journeys[0];
+    assert_eq!(journey.output, "GRÜSSE!");
+    assert_eq!(journey.source, source);
+    assert!(journey.zero_copy_hops());
+    // Forward, push_str within capacity and NFC of NFC text keep the buffer
+    assert!(journey.stages[..3].iter().all(|s| s.sent == source));
+    assert!(journey.stages[3].reallocated());
+    assert_eq!(journey.reallocations(), 1);
+    assert_eq!(journey.delivered, journey.stages[3].sent);
+    assert_eq!(journeys[1].output, "SECOND!");
+}
@@ -0,0 +1,99 @@
+//! The demo registry: built-in demos, tags and lessons added from outside
+
+use futures::future::LocalBoxFuture;
+use introspective_strings::demos::DemoInputs;
+use introspective_strings::registry::{Context, Demo, Registry};
+use introspective_strings::{OutputFormat, Renderer};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+#[test]
+fn builtin_demos_are_registered_in_session_order() {
+    let registry = Registry::builtin();
+    let names: Vec<&str> = registry.iter().map(|demo| demo.name()).collect();
+
+    assert_eq!(names.first(), Some(&"string-types"));
+    let position = |name: &str| {
+        names
+            .iter()
+            .position(|&n| n == name)
+            .unwrap_or_else(|| panic!("{} is not registered", name))
+    };
+    assert!(position("ownership") < position("capacity"));
+    assert!(position("concurrency") < position("pipeline"));
+    let unique: HashSet<&str> = names.iter().copied().collect();
+    assert_eq!(unique.len(), names.len());
+    for demo in registry.iter() {
+        assert!(!demo.description().is_empty(), "{}", demo.name());
+        assert!(!demo.tags().is_empty(), "{}", demo.name());
+        assert_eq!(
+            registry.get(demo.name()).map(|d| d.name()),
+            Some(demo.name())
+        );
+    }
+
+    let unicode: Vec<&str> = registry.tagged("unicode").map(|demo| demo.name()).collect();
+    assert_eq!(
+        unicode,
+        ["transformations", "unicode", "normalization", "case"]
+    );
+    assert!(registry.get("no-such-demo").is_none());
+}
+
+/// A lesson recording the text it was given
+#[derive(Default)]
+struct Lesson {
+    seen: Rc<RefCell<Vec<String>>>,
+}
+
+impl Demo for Lesson {
+    fn name(&self) -> &'static str {
+        "lesson"
+    }
+
+    fn description(&self) -> &'static str {
+        "A lesson registered from outside the crate"
+    }
+
+    fn tags(&self) -> &'static [&'static str] {
+        &["testing"]
+    }
+
+    fn run<'a>(&'a self, cx: &'a Context<'_>) -> LocalBoxFuture<'a, ()> {
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            let (text, _stats) = cx.track(|| cx.inputs.text_or("default").to_string());
+            self.seen.borrow_mut().push(text);
+        })
+    }
+}
+
+#[tokio::test]
+async fn lessons_run_with_the_session_context() {
+    let lesson = Lesson::default();
+    let seen = Rc::clone(&lesson.seen);
+    let mut registry = Registry::new();
+    registry.register(lesson);
+
+    let render = Renderer::new(OutputFormat::Json);
+    let inputs = DemoInputs {
+        text: Some("from the command line".to_string()),
+        ..DemoInputs::default()
+    };
+    let cx = Context::new(&render, &inputs);
+    for demo in registry.tagged("testing") {
+        demo.run(&cx).await;
+    }
+
+    assert_eq!(*seen.borrow(), ["from the command line"]);
+}
+
+#[test]
+#[should_panic(expected = "already registered")]
+fn names_are_unique() {
+    let mut registry = Registry::new();
+    registry
+        .register(Lesson::default())
+        .register(Lesson::default());
+}
@@ -0,0 +1,29 @@
+//! REPL sessions: what a statement costs is what the string operation costs
+
+use introspective_strings::allocator::CountingAllocator;
+use introspective_strings::repl::Session;
+use std::alloc::System;
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+/// Pushing within the capacity touches the allocator neither for the string
+/// nor for the statement's own argument
+#[test]
+fn push_str_within_capacity_does_not_allocate() {
+    let mut session = Session::new();
+    session.execute("let s = String::with_capacity(4)").unwrap();
+    let step = session.execute(r#"s.push_str("Rust")"#).unwrap();
+
+    let stats = step
+        .allocations
+        .expect("the counting allocator is installed");
+    assert_eq!(
+        (stats.allocs, stats.deallocs, stats.reallocs),
+        (0, 0, 0),
+        "{}",
+        stats
+    );
+    assert_eq!(step.layout.length, 4);
+    assert_eq!(step.layout.capacity, 4);
+}
@@ -0,0 +1,34 @@
+//! The span tree layer merges spans by path and summarizes their fields
+
+use introspective_strings::span_tree::{FieldValues, SpanTreeLayer};
+use tracing::info_span;
+use tracing_subscriber::prelude::*;
+
+#[test]
+fn spans_merge_by_path_with_their_fields() {
+    let layer = SpanTreeLayer::new();
+    let tree = layer.tree();
+
+    tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+        let demo = info_span!("demo");
+        let _entered = demo.enter();
+        for input_len in [3u64, 8, 5] {
+            let _op = info_span!("op", input_len, name = "reverse").entered();
+        }
+        let _other = info_span!("other").entered();
+    });
+
+    let nodes = tree.snapshot();
+    assert_eq!(nodes.len(), 1);
+    let demo = &nodes[0];
+    assert_eq!((demo.name, demo.calls), ("demo", 1));
+
+    let names: Vec<&str> = demo.children.iter().map(|c| c.name).collect();
+    assert_eq!(names, ["op", "other"]);
+    let op = &demo.children[0];
+    assert_eq!(op.calls, 3);
+    assert_eq!(op.fields[0].name, "input_len");
+    assert_eq!(op.fields[0].values, FieldValues::Numbers { min: 3, max: 8 });
+    assert_eq!(op.fields[1].values.to_string(), "reverse");
+    assert!(demo.busy_nanos >= op.busy_nanos);
+}
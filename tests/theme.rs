@@ -0,0 +1,79 @@
+//! Rendering themes: detection from the terminal, and plain-ASCII output
+
+use colored::Colorize;
+use introspective_strings::theme::{self, Charset, ColorChoice, Terminal, Theme};
+use introspective_strings::Inspect;
+
+fn terminal(is_terminal: bool, no_color: bool, term: Option<&str>) -> Terminal {
+    Terminal {
+        is_terminal,
+        no_color,
+        term: term.map(str::to_string),
+    }
+}
+
+#[test]
+fn auto_choices_follow_the_terminal() {
+    let auto = |terminal: &Terminal| Theme::resolve(ColorChoice::Auto, Charset::Auto, terminal);
+
+    assert_eq!(
+        auto(&terminal(true, false, Some("xterm-256color"))),
+        Theme::FANCY
+    );
+    assert_eq!(
+        auto(&terminal(false, false, Some("xterm-256color"))),
+        Theme::PLAIN
+    );
+    assert_eq!(auto(&terminal(true, false, Some("dumb"))), Theme::PLAIN);
+    assert_eq!(
+        auto(&terminal(true, true, Some("xterm"))),
+        Theme {
+            color: false,
+            unicode: true,
+        },
+        "NO_COLOR only turns colors off"
+    );
+
+    let piped = terminal(false, true, None);
+    assert_eq!(
+        Theme::resolve(ColorChoice::Always, Charset::Unicode, &piped),
+        Theme::FANCY,
+        "explicit choices win over the environment"
+    );
+    assert_eq!(
+        Theme::resolve(
+            ColorChoice::Never,
+            Charset::Ascii,
+            &terminal(true, false, Some("xterm"))
+        ),
+        Theme::PLAIN
+    );
+}
+
+/// The one test installing themes, since the theme is process-wide
+#[test]
+fn the_plain_theme_prints_ascii_without_escapes() {
+    Theme::PLAIN.install();
+    assert_eq!(theme::current(), Theme::PLAIN);
+    assert_eq!(theme::icon("💡"), "*");
+    assert_eq!(theme::glyphs().arrow, "->");
+    assert_eq!("red".red().to_string(), "red");
+    assert_eq!(theme::arrows("a -> b"), "a -> b");
+
+    let layout = String::from("héllo").inspect("String").to_string();
+    assert!(!layout.contains('\u{1b}'), "{:?}", layout);
+    let decoration: String = layout.lines().map(|line| &line[..2]).collect();
+    assert!(decoration.is_ascii(), "{:?}", layout);
+    assert!(layout
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("| Handle Location:"));
+
+    Theme::FANCY.install();
+    assert_eq!(theme::icon("💡"), "💡");
+    assert_eq!(theme::arrows("a -> b"), "a → b");
+    assert!("red".red().to_string().contains('\u{1b}'));
+    let layout = String::from("héllo").inspect("String").to_string();
+    assert!(layout.contains("┌─ String Memory Layout"), "{:?}", layout);
+}
@@ -0,0 +1,76 @@
+//! Chrome trace events for spans, with async tracks for concurrent tasks
+
+use introspective_strings::trace_event::TraceEventLayer;
+use std::time::Duration;
+use tracing::{info_span, Instrument};
+use tracing_subscriber::prelude::*;
+
+#[tokio::test]
+async fn concurrent_tasks_get_overlapping_async_events() {
+    let layer = TraceEventLayer::new();
+    let trace = layer.trace();
+    let _default = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+
+    let tasks: Vec<_> = (0..3u64)
+        .map(|n| {
+            tokio::spawn(
+                tokio::time::sleep(Duration::from_millis(20)).instrument(info_span!("task", n)),
+            )
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+    info_span!("sync").in_scope(|| {});
+
+    let events = trace.events();
+    assert_eq!(events[0].ph, "M");
+    let slices = events.iter().filter(|e| e.ph == "X" && e.name == "task");
+    assert!(
+        slices.clone().count() >= 6,
+        "each task is polled at least twice"
+    );
+    assert!(slices
+        .clone()
+        .all(|e| e.dur.is_some() && e.tid == events[0].tid));
+
+    let begins: Vec<_> = events.iter().filter(|e| e.ph == "b").collect();
+    let ends: Vec<_> = events.iter().filter(|e| e.ph == "e").collect();
+    assert_eq!((begins.len(), ends.len()), (3, 3));
+    // Every task began before any of them ended
+    let last_begin = begins.iter().map(|e| e.ts).fold(0.0, f64::max);
+    assert!(ends.iter().all(|e| e.ts > last_begin));
+    assert!(begins.iter().all(|e| e.args.contains_key("n")));
+
+    // A span entered once is only a slice
+    assert!(events
+        .iter()
+        .filter(|e| e.name == "sync")
+        .all(|e| e.ph == "X"));
+
+    let json = trace.to_json();
+    assert_eq!(json["traceEvents"].as_array().unwrap().len(), events.len());
+}
+
+/// Async ids are numbered by the layer rather than taken from span `Id`s,
+/// which a subscriber may hand out again once a span has closed
+#[tokio::test]
+async fn sequential_tasks_get_distinct_async_ids() {
+    let layer = TraceEventLayer::new();
+    let trace = layer.trace();
+    let _default = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+
+    for n in 0..2u64 {
+        tokio::time::sleep(Duration::from_millis(1))
+            .instrument(info_span!("task", n))
+            .await;
+    }
+
+    let ids: Vec<_> = trace
+        .events()
+        .into_iter()
+        .filter(|e| e.ph == "b")
+        .filter_map(|e| e.id)
+        .collect();
+    assert_eq!(ids, ["0x1", "0x2"]);
+}
@@ -0,0 +1,140 @@
+//! Segmentation and case mapping against the test cases of the Unicode
+//! Character Database
+//!
+//! The break tests in `tests/ucd` are written by
+//! `tools/unicode_tables.py` along with the property tables.
+
+use introspective_strings::case::{self, CaseLocale};
+use introspective_strings::grapheme::graphemes;
+use introspective_strings::word::word_bounds;
+
+/// Parses a line of a UCD break test - code points separated by `÷` (a
+/// boundary) or `×` (none) - into the text and its expected segments
+fn parse(line: &str) -> (String, Vec<String>) {
+    let mut segments: Vec<String> = Vec::new();
+    let mut joined = false;
+    for token in line.split_whitespace() {
+        match token {
+            "÷" => joined = false,
+            "×" => joined = true,
+            hex => {
+                let c = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .expect(line);
+                match segments.last_mut() {
+                    Some(segment) if joined => segment.push(c),
+                    _ => segments.push(c.to_string()),
+                }
+            }
+        }
+    }
+    (segments.concat(), segments)
+}
+
+/// The cases of a break test `split` gets wrong
+fn failures(test: &str, split: impl Fn(&str) -> Vec<&str>) -> Vec<String> {
+    test.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (text, expected) = parse(line);
+            let actual = split(&text);
+            (actual != expected).then(|| format!("{}: got {:?}", line, actual))
+        })
+        .collect()
+}
+
+#[test]
+fn graphemes_pass_the_grapheme_break_test() {
+    let failed = failures(include_str!("ucd/GraphemeBreakTest.txt"), |s| {
+        graphemes(s).collect()
+    });
+    assert!(
+        failed.is_empty(),
+        "{} failures:\n{}",
+        failed.len(),
+        failed.join("\n")
+    );
+}
+
+#[test]
+fn word_bounds_pass_the_word_break_test() {
+    let failed = failures(include_str!("ucd/WordBreakTest.txt"), |s| {
+        word_bounds(s).collect()
+    });
+    assert!(
+        failed.is_empty(),
+        "{} failures:\n{}",
+        failed.len(),
+        failed.join("\n")
+    );
+}
+
+/// Mappings of `SpecialCasing.txt`: the unconditional ones std's mappings
+/// already cover, then every conditional one
+#[test]
+fn case_mapping_follows_special_casing() {
+    use case::CaseMapping::{Fold, Lower, Title, Upper};
+    use CaseLocale::{Lithuanian, Root, Turkic};
+
+    #[rustfmt::skip]
+    let cases = [
+        // 00DF; 00DF; 0053 0073; 0053 0053; # LATIN SMALL LETTER SHARP S
+        ("ß", Upper, Root, "SS"), ("ß", Title, Root, "Ss"), ("ß", Fold, Root, "ss"),
+        // FB01; FB01; 0046 0069; 0046 0049; # LATIN SMALL LIGATURE FI
+        ("ﬁ", Upper, Root, "FI"), ("ﬁ", Title, Root, "Fi"),
+        // 0149; 0149; 02BC 004E; 02BC 004E; # LATIN SMALL LETTER N PRECEDED BY APOSTROPHE
+        ("ŉ", Upper, Root, "ʼN"), ("ŉ", Title, Root, "ʼN"),
+        // 01F0; 01F0; 004A 030C; 004A 030C; # LATIN SMALL LETTER J WITH CARON
+        ("ǰ", Upper, Root, "J\u{30c}"),
+        // 1F80; 1F80; 1F88; 1F08 0399; # GREEK SMALL LETTER ALPHA WITH PSILI AND YPOGEGRAMMENI
+        ("ᾀ", Upper, Root, "ἈΙ"), ("ᾀ", Title, Root, "ᾈ"),
+        // 0130; 0069 0307; 0130; 0130; # LATIN CAPITAL LETTER I WITH DOT ABOVE
+        ("İ", Lower, Root, "i\u{307}"),
+        // 03A3; 03C2; 03A3; 03A3; Final_Sigma; # GREEK CAPITAL LETTER SIGMA
+        ("ΟΔΟΣ", Lower, Root, "οδος"), ("ΟΔΟΣ ΟΔΟΣ.", Lower, Root, "οδος οδος."),
+        ("Σ", Lower, Root, "σ"), ("ΣΑ", Lower, Root, "σα"), ("ΑΣ'Α", Lower, Root, "ασ'α"),
+        // 0307; ; 0307; 0307; lt After_Soft_Dotted; # COMBINING DOT ABOVE
+        ("i\u{307}", Upper, Lithuanian, "I"), ("i\u{307}", Title, Lithuanian, "I"),
+        ("i\u{307}", Upper, Root, "I\u{307}"),
+        // 0049; 0069 0307; 0049; 0049; lt More_Above; # LATIN CAPITAL LETTER I
+        ("I\u{300}", Lower, Lithuanian, "i\u{307}\u{300}"), ("I", Lower, Lithuanian, "i"),
+        // 004A; 006A 0307; 004A; 004A; lt More_Above; # LATIN CAPITAL LETTER J
+        ("J\u{301}", Lower, Lithuanian, "j\u{307}\u{301}"),
+        // 012E; 012F 0307; 012E; 012E; lt More_Above; # LATIN CAPITAL LETTER I WITH OGONEK
+        ("Į\u{301}", Lower, Lithuanian, "į\u{307}\u{301}"),
+        // 00CC; 0069 0307 0300; 00CC; 00CC; lt; # LATIN CAPITAL LETTER I WITH GRAVE
+        ("Ì", Lower, Lithuanian, "i\u{307}\u{300}"),
+        // 00CD; 0069 0307 0301; 00CD; 00CD; lt; # LATIN CAPITAL LETTER I WITH ACUTE
+        ("Í", Lower, Lithuanian, "i\u{307}\u{301}"),
+        // 0128; 0069 0307 0303; 0128; 0128; lt; # LATIN CAPITAL LETTER I WITH TILDE
+        ("Ĩ", Lower, Lithuanian, "i\u{307}\u{303}"),
+        // 0130; 0069; 0130; 0130; tr; # LATIN CAPITAL LETTER I WITH DOT ABOVE
+        ("İ", Lower, Turkic, "i"),
+        // 0307; ; 0307; 0307; tr After_I; # COMBINING DOT ABOVE
+        ("I\u{307}", Lower, Turkic, "i"), ("I\u{316}\u{307}", Lower, Turkic, "i\u{316}"),
+        // 0049; 0131; 0049; 0049; tr Not_Before_Dot; # LATIN CAPITAL LETTER I
+        ("I", Lower, Turkic, "ı"), ("ISTANBUL", Lower, Turkic, "ıstanbul"),
+        // 0069; 0069; 0130; 0130; tr; # LATIN SMALL LETTER I
+        ("i", Upper, Turkic, "İ"), ("izmir", Title, Turkic, "İzmir"),
+    ];
+
+    let failed: Vec<String> = cases
+        .iter()
+        .filter_map(|&(input, mapping, locale, expected)| {
+            let actual = case::map(input, mapping, locale);
+            (actual != expected).then(|| {
+                format!(
+                    "{} {:?} ({}): {:?}, expected {:?}",
+                    mapping, input, locale, actual, expected
+                )
+            })
+        })
+        .collect();
+    assert!(
+        failed.is_empty(),
+        "{} failures:\n{}",
+        failed.len(),
+        failed.join("\n")
+    );
+}
@@ -0,0 +1,40 @@
+//! Real I/O workloads and the copies they report
+
+use introspective_strings::workload::{self, EchoServer, Workload};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn workloads_round_trip_the_input() {
+    let echo = EchoServer::start().await.expect("loopback is available");
+
+    for workload in [Workload::FileRoundTrip, Workload::TcpEcho(echo.addr())] {
+        let report = workload::run(String::from("héllo"), workload)
+            .await
+            .unwrap();
+        assert_eq!(report.output, "héllo", "{}", workload);
+        assert!(report.copied() >= 2 * "héllo".len(), "{}", workload);
+    }
+
+    let shared: Arc<str> = Arc::from("héllo");
+    let report = workload::run(Arc::clone(&shared), Workload::CpuTransform)
+        .await
+        .unwrap();
+    assert_eq!(report.output, "HÉLLO");
+    // Moving the Arc into the closure copies nothing
+    assert_eq!(report.steps[0].copied, 0);
+    assert_eq!(report.steps[0].buffer, Some(shared.as_ptr() as usize));
+}
+
+/// `String::from_utf8` keeps the buffer `read_to_end` filled
+#[tokio::test]
+async fn echo_output_keeps_its_read_buffer() {
+    let echo = EchoServer::start().await.expect("loopback is available");
+    let report = workload::run("echo", Workload::TcpEcho(echo.addr()))
+        .await
+        .unwrap();
+
+    let buffers: Vec<Option<usize>> = report.steps.iter().map(|s| s.buffer).collect();
+    let read = buffers[buffers.len() - 2];
+    assert!(read.is_some());
+    assert_eq!(buffers.last(), Some(&read));
+}
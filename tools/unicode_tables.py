@@ -0,0 +1,397 @@
+#!/usr/bin/env python3
+"""Generates the Unicode tables of the grapheme, word and case modules.
+
+Reads the Unicode Character Database files of one pinned version and writes:
+
+- src/grapheme/tables.rs   Grapheme_Cluster_Break, Extended_Pictographic and
+                           Indic_Conjunct_Break
+- src/word/tables.rs       Word_Break
+- src/case/tables.rs       Case_Ignorable, Lt, Soft_Dotted, and the full case
+                           folding and titlecase mappings std does not cover
+- tests/ucd/*.txt          the cases of GraphemeBreakTest.txt and
+                           WordBreakTest.txt, sorted, comments removed
+
+Usage, from the repository root:
+
+    python3 tools/unicode_tables.py              # downloads the UCD files
+    python3 tools/unicode_tables.py --ucd DIR    # uses files already in DIR
+
+Bumping UNICODE_VERSION means updating the version in the module docs of
+src/grapheme.rs and src/word.rs as well, then running the tests.
+"""
+
+import argparse
+import os
+import re
+import sys
+import urllib.request
+
+UNICODE_VERSION = "17.0.0"
+UCD_URL = f"https://www.unicode.org/Public/{UNICODE_VERSION}/ucd"
+
+UCD_FILES = {
+    "UnicodeData.txt": "UnicodeData.txt",
+    "SpecialCasing.txt": "SpecialCasing.txt",
+    "CaseFolding.txt": "CaseFolding.txt",
+    "PropList.txt": "PropList.txt",
+    "DerivedCoreProperties.txt": "DerivedCoreProperties.txt",
+    "emoji-data.txt": "emoji/emoji-data.txt",
+    "GraphemeBreakProperty.txt": "auxiliary/GraphemeBreakProperty.txt",
+    "WordBreakProperty.txt": "auxiliary/WordBreakProperty.txt",
+    "GraphemeBreakTest.txt": "auxiliary/GraphemeBreakTest.txt",
+    "WordBreakTest.txt": "auxiliary/WordBreakTest.txt",
+}
+
+# Generated lines stay within rustfmt's width, like the rest of the code
+MAX_WIDTH = 100
+INDENT = "    "
+
+# Hangul syllables are computed arithmetically in src/grapheme.rs
+HANGUL_SYLLABLES = {"LV", "LVT"}
+
+
+def fetch(ucd_dir):
+    """Downloads every UCD file missing from `ucd_dir`"""
+    os.makedirs(ucd_dir, exist_ok=True)
+    for name, path in UCD_FILES.items():
+        target = os.path.join(ucd_dir, name)
+        if not os.path.exists(target):
+            print(f"downloading {UCD_URL}/{path}", file=sys.stderr)
+            urllib.request.urlretrieve(f"{UCD_URL}/{path}", target)
+
+
+def check_version(ucd_dir):
+    """Fails unless the UCD files carry the pinned version in their header"""
+    for name in ("DerivedCoreProperties.txt", "GraphemeBreakProperty.txt", "WordBreakProperty.txt"):
+        with open(os.path.join(ucd_dir, name), encoding="utf-8") as file:
+            header = file.readline()
+        if f"-{UNICODE_VERSION}.txt" not in header:
+            sys.exit(f"{name} is not version {UNICODE_VERSION}: {header.strip()}")
+
+
+def data_lines(ucd_dir, name):
+    """The fields of every data line of a UCD file, comments removed"""
+    with open(os.path.join(ucd_dir, name), encoding="utf-8") as file:
+        for line in file:
+            line = line.split("#", 1)[0].strip()
+            if line:
+                yield [field.strip() for field in line.split(";")]
+
+
+def code_points(field):
+    """The code points of `0041` or `0041..005A`"""
+    lo, _, hi = field.partition("..")
+    return range(int(lo, 16), int(hi or lo, 16) + 1)
+
+
+def property_values(ucd_dir, name, prefix=None):
+    """Maps each code point to its value in a `code points; value` file
+
+    With a prefix, only `code points; prefix; value` lines are read (for
+    `InCB; Extend`-style properties).
+    """
+    values = {}
+    for fields in data_lines(ucd_dir, name):
+        if prefix:
+            if len(fields) < 3 or fields[1] != prefix:
+                continue
+            value = fields[2]
+        else:
+            value = fields[1]
+        for cp in code_points(fields[0]):
+            values[cp] = value
+    return values
+
+
+def binary_property(ucd_dir, name, prop):
+    """The code points having a binary property listed in a file of many"""
+    return {
+        cp: None
+        for fields in data_lines(ucd_dir, name)
+        if len(fields) == 2 and fields[1] == prop
+        for cp in code_points(fields[0])
+    }
+
+
+def unicode_data(ucd_dir):
+    """General category and simple case mappings from UnicodeData.txt"""
+    category, upper, lower, title = {}, {}, {}, {}
+    first = None
+    for fields in data_lines(ucd_dir, "UnicodeData.txt"):
+        cp = int(fields[0], 16)
+        if fields[1].endswith(", First>"):
+            first = cp
+            continue
+        for c in range(first if fields[1].endswith(", Last>") else cp, cp + 1):
+            category[c] = fields[2]
+        first = None
+        if fields[12]:
+            upper[cp] = [int(fields[12], 16)]
+        if fields[13]:
+            lower[cp] = [int(fields[13], 16)]
+        # An empty titlecase field means the titlecase is the uppercase
+        if fields[14]:
+            title[cp] = [int(fields[14], 16)]
+        elif fields[12]:
+            title[cp] = [int(fields[12], 16)]
+    return category, upper, lower, title
+
+
+def special_casing(ucd_dir):
+    """The unconditional full mappings of SpecialCasing.txt"""
+    lower, title, upper = {}, {}, {}
+    for fields in data_lines(ucd_dir, "SpecialCasing.txt"):
+        # Conditional mappings have a fifth, non-empty field
+        if len(fields) > 4 and fields[4]:
+            continue
+        cp = int(fields[0], 16)
+        for mapping, field in ((lower, fields[1]), (title, fields[2]), (upper, fields[3])):
+            mapping[cp] = [int(c, 16) for c in field.split()]
+    return lower, title, upper
+
+
+def case_folding(ucd_dir):
+    """Full case folding: status F where there is one, C otherwise"""
+    fold = {}
+    for fields in data_lines(ucd_dir, "CaseFolding.txt"):
+        cp, status, mapping = int(fields[0], 16), fields[1], [int(c, 16) for c in fields[2].split()]
+        if status == "F" or (status == "C" and cp not in fold):
+            fold[cp] = mapping
+    return fold
+
+
+def ranges(values):
+    """Merges a code point -> value map into sorted (lo, hi, value) ranges"""
+    merged = []
+    for cp in sorted(values):
+        if merged and merged[-1][1] == cp - 1 and merged[-1][2] == values[cp]:
+            merged[-1][1] = cp
+        else:
+            merged.append([cp, cp, values[cp]])
+    return merged
+
+
+def char(cp):
+    return f"'\\u{{{cp:x}}}'"
+
+
+def string(cps):
+    """A Rust string literal: printable ASCII as is, the rest escaped"""
+    chars = "".join(chr(cp) if 0x20 <= cp < 0x7F and chr(cp) not in '"\\' else f"\\u{{{cp:x}}}" for cp in cps)
+    return f'"{chars}"'
+
+
+def table(doc, name, element, items):
+    """A `#[rustfmt::skip]` table, its items packed into lines"""
+    lines = [f"/// {line}" if line else "///" for line in doc.split("\n")]
+    lines += ["#[rustfmt::skip]", f"pub(super) const {name}: &[{element}] = &["]
+    line = ""
+    for item in items:
+        candidate = f"{line} {item}," if line else f"{INDENT}{item},"
+        if len(candidate) > MAX_WIDTH and line:
+            lines.append(line)
+            candidate = f"{INDENT}{item},"
+        line = candidate
+    if line:
+        lines.append(line)
+    lines.append("];")
+    return "\n".join(lines)
+
+
+def range_table(doc, name, values, enum=None):
+    element = f"(char, char, {enum})" if enum else "(char, char)"
+    items = [
+        f"({char(lo)}, {char(hi)}, {value})" if enum else f"({char(lo)}, {char(hi)})"
+        for lo, hi, value in ranges(values)
+    ]
+    return table(doc, name, element, items)
+
+
+def module(doc, uses, tables):
+    header = "\n".join(f"//! {line}" if line else "//!" for line in doc.split("\n"))
+    parts = [header] + ([uses] if uses else []) + tables
+    return "\n\n".join(parts) + "\n"
+
+
+def variant(value):
+    """`Regional_Indicator` -> `RegionalIndicator`"""
+    return value.replace("_", "")
+
+
+def grapheme_tables(ucd_dir):
+    cats = {
+        cp: variant(value)
+        for cp, value in property_values(ucd_dir, "GraphemeBreakProperty.txt").items()
+        if value not in HANGUL_SYLLABLES
+    }
+    incb = property_values(ucd_dir, "DerivedCoreProperties.txt", prefix="InCB")
+    pictographic = binary_property(ucd_dir, "emoji-data.txt", "Extended_Pictographic")
+    # Consonants and pictographs are Other as far as the break property goes
+    for cp, value in incb.items():
+        if value == "Consonant" and cp not in cats:
+            cats[cp] = "InCBConsonant"
+    for cp in pictographic:
+        if cp not in cats:
+            cats[cp] = "ExtendedPictographic"
+
+    extend = {cp: None for cp, value in incb.items() if value == "Extend"}
+    linkers = [char(cp) for cp, value in sorted(incb.items()) if value == "Linker"]
+    return module(
+        f"Unicode {UNICODE_VERSION} property tables for extended grapheme cluster segmentation\n"
+        "\n"
+        "Generated by `tools/unicode_tables.py` from the Unicode Character Database:\n"
+        "`GraphemeBreakProperty.txt`, the `Extended_Pictographic` property from\n"
+        "`emoji-data.txt`, and the `Indic_Conjunct_Break` property from\n"
+        "`DerivedCoreProperties.txt`. Do not edit by hand.\n"
+        "\n"
+        "Hangul syllables (`LV`/`LVT`) are omitted: they are computed\n"
+        "arithmetically in [`super::category`].",
+        "use super::GraphemeCat::{self, *};",
+        [
+            range_table(
+                "Sorted, non-overlapping ranges of code points and their break category.\n"
+                "Code points not listed are [`GraphemeCat::Other`].",
+                "GRAPHEME_CAT",
+                cats,
+                enum="GraphemeCat",
+            ),
+            range_table("Code points with `Indic_Conjunct_Break=Extend`", "INCB_EXTEND", extend),
+            table(
+                "Code points with `Indic_Conjunct_Break=Linker` (viramas)",
+                "INCB_LINKER",
+                "char",
+                linkers,
+            ),
+        ],
+    )
+
+
+def word_tables(ucd_dir):
+    cats = {cp: variant(value) for cp, value in property_values(ucd_dir, "WordBreakProperty.txt").items()}
+    return module(
+        f"Unicode {UNICODE_VERSION} property tables for word boundaries\n"
+        "\n"
+        "Generated by `tools/unicode_tables.py` from the Unicode Character Database:\n"
+        "`WordBreakProperty.txt`. Do not edit by hand.\n"
+        "\n"
+        "`Extended_Pictographic`, needed by rule WB3c, is shared with the\n"
+        "grapheme tables and looked up through [`crate::grapheme::category`].",
+        "use super::WordCat::{self, *};",
+        [
+            range_table(
+                "Sorted, non-overlapping ranges of code points and their break category.\n"
+                "Code points not listed are [`WordCat::Other`].",
+                "WORD_CAT",
+                cats,
+                enum="WordCat",
+            )
+        ],
+    )
+
+
+def case_tables(ucd_dir):
+    category, simple_upper, simple_lower, simple_title = unicode_data(ucd_dir)
+    special_lower, special_title, special_upper = special_casing(ucd_dir)
+    fold = case_folding(ucd_dir)
+    word_break = property_values(ucd_dir, "WordBreakProperty.txt")
+    soft_dotted = binary_property(ucd_dir, "PropList.txt", "Soft_Dotted")
+
+    ignorable = {
+        cp: None
+        for cp in range(0x110000)
+        if category.get(cp) in ("Mn", "Me", "Cf", "Lm", "Sk")
+        or word_break.get(cp) in ("MidLetter", "MidNumLet", "Single_Quote")
+    }
+    titlecase_letters = {cp: None for cp, gc in category.items() if gc == "Lt"}
+
+    # What std's `char::to_lowercase` and `char::to_uppercase` return
+    def full(special, simple, cp):
+        return special.get(cp) or simple.get(cp) or [cp]
+
+    folds, titles = [], []
+    for cp in range(0x110000):
+        folded = fold.get(cp, [cp])
+        if folded != full(special_lower, simple_lower, cp):
+            folds.append(f"({char(cp)}, {string(folded)})")
+        titled = full(special_title, simple_title, cp)
+        if titled != full(special_upper, simple_upper, cp):
+            titles.append(f"({char(cp)}, {string(titled)})")
+
+    return module(
+        f"Unicode {UNICODE_VERSION} property tables for full case mapping\n"
+        "\n"
+        "Generated by `tools/unicode_tables.py` from the Unicode Character Database:\n"
+        "`UnicodeData.txt`, `SpecialCasing.txt`, `CaseFolding.txt`, `PropList.txt`\n"
+        "and `WordBreakProperty.txt`. Do not edit by hand.\n"
+        "\n"
+        "The mapping tables only list code points whose mapping differs from the\n"
+        "one the standard library already implements, so most lookups fall\n"
+        "through to [`char::to_lowercase`] or [`char::to_uppercase`].",
+        None,
+        [
+            range_table(
+                "`Case_Ignorable`: general category Mn, Me, Cf, Lm or Sk, or word break\n"
+                "category MidLetter, MidNumLet or Single_Quote",
+                "CASE_IGNORABLE",
+                ignorable,
+            ),
+            range_table(
+                "General category `Lt`, the only cased letters that are neither\n"
+                "`Lowercase` nor `Uppercase`",
+                "TITLECASE_LETTER",
+                titlecase_letters,
+            ),
+            range_table("`Soft_Dotted`: letters whose dot disappears under an accent", "SOFT_DOTTED", soft_dotted),
+            table(
+                "Full case folding (statuses C and F) where it differs from lowercasing",
+                "FOLD",
+                "(char, &str)",
+                folds,
+            ),
+            table("Full titlecase mappings where they differ from uppercasing", "TITLE", "(char, &str)", titles),
+        ],
+    )
+
+
+def break_test(ucd_dir, name):
+    """The cases of a UCD break test, one per line, in code point order
+
+    Sorting keeps the file stable across versions, so regenerating it shows
+    only the cases that changed.
+    """
+    cases = {re.sub(r"\s+", " ", fields[0]) for fields in data_lines(ucd_dir, name)}
+    key = lambda case: [int(token, 16) for token in case.split() if token not in "÷×"]
+    header = (
+        f"# The test cases of {name}, Unicode {UNICODE_VERSION}: code points separated\n"
+        "# by ÷ (a boundary) or × (no boundary). Sorted, comments removed.\n"
+        "# Generated by tools/unicode_tables.py. Do not edit by hand.\n"
+    )
+    return header + "".join(f"{case}\n" for case in sorted(cases, key=key))
+
+
+def write(path, content):
+    with open(path, "w", encoding="utf-8", newline="\n") as file:
+        file.write(content)
+    print(f"wrote {path}", file=sys.stderr)
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.split("\n", 1)[0])
+    parser.add_argument("--ucd", help="directory with the UCD files (downloaded when missing)")
+    parser.add_argument("--out", default=".", help="repository root (default: current directory)")
+    args = parser.parse_args()
+
+    ucd_dir = args.ucd or os.path.join(args.out, "target", f"ucd-{UNICODE_VERSION}")
+    fetch(ucd_dir)
+    check_version(ucd_dir)
+
+    write(os.path.join(args.out, "src", "grapheme", "tables.rs"), grapheme_tables(ucd_dir))
+    write(os.path.join(args.out, "src", "word", "tables.rs"), word_tables(ucd_dir))
+    write(os.path.join(args.out, "src", "case", "tables.rs"), case_tables(ucd_dir))
+    os.makedirs(os.path.join(args.out, "tests", "ucd"), exist_ok=True)
+    for name in ("GraphemeBreakTest.txt", "WordBreakTest.txt"):
+        write(os.path.join(args.out, "tests", "ucd", name), break_test(ucd_dir, name))
+
+
+if __name__ == "__main__":
+    main()